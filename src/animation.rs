@@ -0,0 +1,388 @@
+//! Ramps a value over time, for smoothly changing settings or fading the view
+//! instead of jumping to the new value.
+//!
+//! ```no_run
+//! # use ovr_overlay::animation::{Easing, SettingRamp};
+//! # use ovr_overlay::settings::SettingsManager;
+//! # use std::ffi::CString;
+//! # use std::time::Duration;
+//! # fn example(settings: &mut SettingsManager) {
+//! let section = CString::new("steamvr").unwrap();
+//! let key = CString::new("analogGain").unwrap();
+//! let mut dim = SettingRamp::from_current(
+//!     settings,
+//!     &section,
+//!     &key,
+//!     0.5,
+//!     Duration::from_secs(60),
+//!     Easing::EaseInOut,
+//! )
+//! .unwrap();
+//! while !dim.tick(settings).unwrap() {
+//!     std::thread::sleep(Duration::from_millis(100));
+//! }
+//! # }
+//! ```
+
+use std::time::{Duration, Instant};
+
+/// How a [`Ramp`] progresses from start to end.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Starts slow and speeds up.
+    EaseIn,
+    /// Starts fast and slows down.
+    EaseOut,
+    /// Starts and ends slow.
+    EaseInOut,
+}
+impl Easing {
+    /// Maps progress `t` between 0 and 1 to the eased progress, also between 0 and 1.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => t * (2. - t),
+            Self::EaseInOut => t * t * (3. - 2. * t),
+        }
+    }
+}
+
+/// A value going from `from` to `to` over a duration.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Ramp {
+    from: f32,
+    to: f32,
+    duration: Duration,
+    easing: Easing,
+    start: Instant,
+}
+impl Ramp {
+    /// Starts a ramp now.
+    pub fn new(from: f32, to: f32, duration: Duration, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            easing,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn value_at(&self, now: Instant) -> f32 {
+        let elapsed = now.saturating_duration_since(self.start);
+        let t = if self.duration.is_zero() {
+            1.
+        } else {
+            elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        };
+        self.from + (self.to - self.from) * self.easing.apply(t)
+    }
+
+    pub fn value(&self) -> f32 {
+        self.value_at(Instant::now())
+    }
+
+    pub fn is_finished_at(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) >= self.duration
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.is_finished_at(Instant::now())
+    }
+}
+
+#[cfg(feature = "ovr_settings")]
+pub use self::settings::SettingRamp;
+#[cfg(feature = "ovr_settings")]
+mod settings {
+    use super::{Easing, Ramp};
+    use crate::errors::EVRSettingsError;
+    use crate::settings::SettingsManager;
+
+    use std::ffi::{CStr, CString};
+    use std::time::{Duration, Instant};
+
+    /// Ramps a float setting, such as `steamvr/analogGain`.
+    #[derive(Clone, Debug)]
+    pub struct SettingRamp {
+        section: CString,
+        key: CString,
+        ramp: Ramp,
+    }
+    impl SettingRamp {
+        pub fn new(section: &CStr, key: &CStr, ramp: Ramp) -> Self {
+            Self {
+                section: section.to_owned(),
+                key: key.to_owned(),
+                ramp,
+            }
+        }
+
+        /// Ramps from the setting's current value to `to`, starting now.
+        pub fn from_current(
+            settings: &mut SettingsManager,
+            section: &CStr,
+            key: &CStr,
+            to: f32,
+            duration: Duration,
+            easing: Easing,
+        ) -> Result<Self, EVRSettingsError> {
+            let from = settings.get_float(section, key)?;
+            Ok(Self::new(
+                section,
+                key,
+                Ramp::new(from, to, duration, easing),
+            ))
+        }
+
+        pub fn ramp(&self) -> &Ramp {
+            &self.ramp
+        }
+
+        /// Writes the current value of the ramp, returning whether it is finished.
+        pub fn tick(&mut self, settings: &mut SettingsManager) -> Result<bool, EVRSettingsError> {
+            let now = Instant::now();
+            settings.set_float(&self.section, &self.key, self.ramp.value_at(now))?;
+            Ok(self.ramp.is_finished_at(now))
+        }
+
+        /// Runs the ramp to completion, writing the value every `interval`.
+        ///
+        /// The settings manager is acquired for each write, so this panics if one is
+        /// alive on the thread that polls the future.
+        #[cfg(feature = "async")]
+        pub async fn run(
+            mut self,
+            ctx: &crate::Context,
+            interval: Duration,
+        ) -> Result<(), EVRSettingsError> {
+            // The manager holds a lock, so it must not be kept across awaits.
+            while !self.tick(&mut ctx.settings_mngr())? {
+                tokio::time::sleep(interval).await;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "ovr_compositor")]
+pub use self::fade::FadeRamp;
+#[cfg(feature = "ovr_compositor")]
+mod fade {
+    use super::{Easing, Ramp};
+    use crate::compositor::CompositorManager;
+    use crate::Color;
+
+    use std::time::{Duration, Instant};
+
+    /// Fades the view to a color with an easing curve, unlike the linear
+    /// [`CompositorManager::fade_to_color()`].
+    #[derive(Clone, Debug)]
+    pub struct FadeRamp {
+        from: Color,
+        to: Color,
+        background: bool,
+        ramp: Ramp,
+    }
+    impl FadeRamp {
+        /// Starts a fade from `from` to `to` now. A color with an alpha of 0 is no
+        /// fade at all.
+        pub fn new(
+            from: Color,
+            to: Color,
+            background: bool,
+            duration: Duration,
+            easing: Easing,
+        ) -> Self {
+            Self {
+                from,
+                to,
+                background,
+                ramp: Ramp::new(0., 1., duration, easing),
+            }
+        }
+
+        /// Applies the current color of the fade, returning whether it is finished.
+        pub fn tick(&mut self, compositor: &mut CompositorManager) -> bool {
+            let now = Instant::now();
+            let t = self.ramp.value_at(now);
+            let lerp = |a: f32, b: f32| a + (b - a) * t;
+            let color = Color {
+                r: lerp(self.from.r, self.to.r),
+                g: lerp(self.from.g, self.to.g),
+                b: lerp(self.from.b, self.to.b),
+                a: lerp(self.from.a, self.to.a),
+            };
+            compositor.fade_to_color(0., color, self.background);
+            self.ramp.is_finished_at(now)
+        }
+
+        /// Runs the fade to completion, updating the color every `interval`.
+        ///
+        /// The compositor manager is acquired for each update, so this panics if one
+        /// is alive on the thread that polls the future.
+        #[cfg(feature = "async")]
+        pub async fn run(mut self, ctx: &crate::Context, interval: Duration) {
+            while !self.tick(&mut ctx.compositor_mngr()) {
+                tokio::time::sleep(interval).await;
+            }
+        }
+    }
+}
+
+pub use self::overlay_fade::OverlayFade;
+mod overlay_fade {
+    use super::{Easing, Ramp};
+    use crate::errors::EVROverlayError;
+    use crate::overlay::OverlayHandle;
+    use crate::traits::Overlay;
+
+    use std::time::{Duration, Instant};
+
+    /// Fades an overlay in after showing it, or out before hiding it. Started by
+    /// [`Overlay::show_faded()`] and [`Overlay::hide_faded()`].
+    #[derive(Clone, Debug)]
+    pub struct OverlayFade {
+        overlay: OverlayHandle,
+        ramp: Ramp,
+        /// The opacity to restore once hidden, for fading out.
+        hide: Option<f32>,
+    }
+    impl OverlayFade {
+        /// Shows `overlay` at an opacity of 0, fading it in to its current opacity.
+        pub fn show(
+            mngr: &mut (impl Overlay + ?Sized),
+            overlay: OverlayHandle,
+            duration: Duration,
+        ) -> Result<Self, EVROverlayError> {
+            let alpha = mngr.opacity(overlay)?;
+            let from = if mngr.is_visible(overlay) { alpha } else { 0. };
+            mngr.set_opacity(overlay, from)?;
+            mngr.set_visibility(overlay, true)?;
+            Ok(Self {
+                overlay,
+                ramp: Ramp::new(from, alpha, duration, Easing::EaseOut),
+                hide: None,
+            })
+        }
+
+        /// Fades `overlay` out from its current opacity, then hides it and restores the
+        /// opacity, so it shows as before the next time.
+        pub fn hide(
+            mngr: &mut (impl Overlay + ?Sized),
+            overlay: OverlayHandle,
+            duration: Duration,
+        ) -> Result<Self, EVROverlayError> {
+            let alpha = mngr.opacity(overlay)?;
+            let duration = if mngr.is_visible(overlay) {
+                duration
+            } else {
+                Duration::ZERO
+            };
+            Ok(Self {
+                overlay,
+                ramp: Ramp::new(alpha, 0., duration, Easing::EaseIn),
+                hide: Some(alpha),
+            })
+        }
+
+        pub fn overlay(&self) -> OverlayHandle {
+            self.overlay
+        }
+
+        pub fn ramp(&self) -> &Ramp {
+            &self.ramp
+        }
+
+        /// Applies the current opacity of the fade, returning whether it is finished.
+        pub fn tick(
+            &mut self,
+            mngr: &mut (impl Overlay + ?Sized),
+        ) -> Result<bool, EVROverlayError> {
+            self.tick_at(mngr, Instant::now())
+        }
+
+        pub fn tick_at(
+            &mut self,
+            mngr: &mut (impl Overlay + ?Sized),
+            now: Instant,
+        ) -> Result<bool, EVROverlayError> {
+            let finished = self.ramp.is_finished_at(now);
+            match self.hide {
+                Some(alpha) if finished => {
+                    mngr.set_visibility(self.overlay, false)?;
+                    mngr.set_opacity(self.overlay, alpha)?;
+                }
+                _ => mngr.set_opacity(self.overlay, self.ramp.value_at(now))?,
+            }
+            Ok(finished)
+        }
+
+        /// Runs the fade to completion, updating the opacity every `interval`.
+        ///
+        /// The overlay manager is acquired for each update, so this panics if one is
+        /// alive on the thread that polls the future.
+        #[cfg(feature = "async")]
+        pub async fn run(
+            mut self,
+            ctx: &crate::Context,
+            interval: Duration,
+        ) -> Result<(), EVROverlayError> {
+            while !self.tick(&mut ctx.overlay_mngr())? {
+                tokio::time::sleep(interval).await;
+            }
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ramp_hits_endpoints() {
+        let ramp = Ramp::new(1., 3., Duration::from_secs(10), Easing::EaseInOut);
+        let start = ramp.start;
+        assert_eq!(ramp.value_at(start), 1.);
+        assert_eq!(ramp.value_at(start + Duration::from_secs(5)), 2.);
+        assert_eq!(ramp.value_at(start + Duration::from_secs(20)), 3.);
+        assert!(!ramp.is_finished_at(start + Duration::from_secs(5)));
+        assert!(ramp.is_finished_at(start + Duration::from_secs(10)));
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn overlay_fade_restores_opacity() {
+        use crate::mock::MockOverlay;
+        use crate::traits::Overlay;
+
+        let mut mngr = MockOverlay::new();
+        let overlay = mngr.create_overlay("fade", "Fade").unwrap();
+        mngr.set_opacity(overlay, 0.8).unwrap();
+
+        let mut fade = mngr.show_faded(overlay, Duration::from_secs(1)).unwrap();
+        let start = fade.ramp().start;
+        assert!(mngr.overlay(overlay).unwrap().visible);
+        assert_eq!(mngr.overlay(overlay).unwrap().alpha, 0.);
+        assert!(fade
+            .tick_at(&mut mngr, start + Duration::from_secs(2))
+            .unwrap());
+        assert_eq!(mngr.overlay(overlay).unwrap().alpha, 0.8);
+
+        let mut fade = mngr.hide_faded(overlay, Duration::from_secs(1)).unwrap();
+        let start = fade.ramp().start;
+        assert!(!fade.tick_at(&mut mngr, start).unwrap());
+        assert!(mngr.overlay(overlay).unwrap().visible);
+        assert!(fade
+            .tick_at(&mut mngr, start + Duration::from_secs(1))
+            .unwrap());
+        let state = mngr.overlay(overlay).unwrap();
+        assert!(!state.visible);
+        assert_eq!(state.alpha, 0.8);
+    }
+}
@@ -1,28 +1,121 @@
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
+use std::os::raw::c_char;
 use std::path::Path;
 use std::pin::Pin;
 
-use crate::{errors::EVRApplicationError, sys, Context};
+use crate::strings::{get_string, string_from};
+use crate::{errors::EVRApplicationError, sys, Context, Interface, InterfaceLock};
+
+#[cfg(feature = "manifest")]
+pub mod manifest;
 
 pub struct ApplicationsManager<'c> {
     ctx: PhantomData<&'c Context>,
     inner: Pin<&'c mut sys::IVRApplications>,
+    _lock: InterfaceLock<'c>,
 }
 
 type Result<T> = std::result::Result<T, EVRApplicationError>;
 
+/// Application properties that are stored as strings.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StringProperty {
+    Name,
+    LaunchType,
+    WorkingDirectory,
+    BinaryPath,
+    Arguments,
+    Url,
+    Description,
+    NewsUrl,
+    ImagePath,
+    Source,
+    ActionManifestUrl,
+}
+impl From<StringProperty> for sys::EVRApplicationProperty {
+    fn from(prop: StringProperty) -> Self {
+        use sys::EVRApplicationProperty::*;
+        match prop {
+            StringProperty::Name => VRApplicationProperty_Name_String,
+            StringProperty::LaunchType => VRApplicationProperty_LaunchType_String,
+            StringProperty::WorkingDirectory => VRApplicationProperty_WorkingDirectory_String,
+            StringProperty::BinaryPath => VRApplicationProperty_BinaryPath_String,
+            StringProperty::Arguments => VRApplicationProperty_Arguments_String,
+            StringProperty::Url => VRApplicationProperty_URL_String,
+            StringProperty::Description => VRApplicationProperty_Description_String,
+            StringProperty::NewsUrl => VRApplicationProperty_NewsURL_String,
+            StringProperty::ImagePath => VRApplicationProperty_ImagePath_String,
+            StringProperty::Source => VRApplicationProperty_Source_String,
+            StringProperty::ActionManifestUrl => VRApplicationProperty_ActionManifestURL_String,
+        }
+    }
+}
+
+/// Application properties that are stored as bools.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BoolProperty {
+    IsDashboardOverlay,
+    IsTemplate,
+    IsInstanced,
+    IsInternal,
+    WantsCompositorPauseInStandby,
+    IsHidden,
+}
+impl From<BoolProperty> for sys::EVRApplicationProperty {
+    fn from(prop: BoolProperty) -> Self {
+        use sys::EVRApplicationProperty::*;
+        match prop {
+            BoolProperty::IsDashboardOverlay => VRApplicationProperty_IsDashboardOverlay_Bool,
+            BoolProperty::IsTemplate => VRApplicationProperty_IsTemplate_Bool,
+            BoolProperty::IsInstanced => VRApplicationProperty_IsInstanced_Bool,
+            BoolProperty::IsInternal => VRApplicationProperty_IsInternal_Bool,
+            BoolProperty::WantsCompositorPauseInStandby => {
+                VRApplicationProperty_WantsCompositorPauseInStandby_Bool
+            }
+            BoolProperty::IsHidden => VRApplicationProperty_IsHidden_Bool,
+        }
+    }
+}
+
+/// Application properties that are stored as u64s.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum U64Property {
+    /// Seconds since the unix epoch at which the application was last launched.
+    LastLaunchTime,
+}
+impl From<U64Property> for sys::EVRApplicationProperty {
+    fn from(prop: U64Property) -> Self {
+        use sys::EVRApplicationProperty::*;
+        match prop {
+            U64Property::LastLaunchTime => VRApplicationProperty_LastLaunchTime_Uint64,
+        }
+    }
+}
+
+fn to_cstring(s: &str) -> Result<CString> {
+    CString::new(s).map_err(|_| {
+        EVRApplicationError::new(sys::EVRApplicationError::VRApplicationError_InvalidParameter)
+            .unwrap_err()
+    })
+}
+
 impl<'c> ApplicationsManager<'c> {
-    pub(super) fn new(_ctx: &'c Context) -> Self {
-        let inner = unsafe { Pin::new_unchecked(sys::VRApplications().as_mut::<'c>().unwrap()) };
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::Applications) };
         Self {
             ctx: Default::default(),
             inner,
+            _lock: lock,
         }
     }
 
     // ---- Handle Management ----
 
+    /// Registers the `.vrmanifest` at `path` with SteamVR. Temporary manifests are
+    /// forgotten when SteamVR exits.
+    ///
+    /// Wraps c++ `AddApplicationManifest`.
     pub fn add_application_manifest(&mut self, path: &Path, temporary: bool) -> Result<()> {
         let path = if let Ok(s) = CString::new(path.to_string_lossy().as_bytes()) {
             s
@@ -43,6 +136,9 @@ impl<'c> ApplicationsManager<'c> {
         EVRApplicationError::new(err)
     }
 
+    /// Removes a previously registered `.vrmanifest` from SteamVR.
+    ///
+    /// Wraps c++ `RemoveApplicationManifest`.
     pub fn remove_application_manifest(&mut self, path: &Path) -> Result<()> {
         let path = if let Ok(s) = CString::new(path.to_string_lossy().as_bytes()) {
             s
@@ -59,6 +155,9 @@ impl<'c> ApplicationsManager<'c> {
         EVRApplicationError::new(err)
     }
 
+    /// Returns true if an application with the given app key is registered.
+    ///
+    /// Wraps c++ `IsApplicationInstalled`.
     pub fn is_application_installed(&mut self, key: &str) -> Result<bool> {
         let name = if let Ok(s) = CString::new(key) {
             s
@@ -78,6 +177,285 @@ impl<'c> ApplicationsManager<'c> {
         Ok(installed)
     }
 
+    /// Returns the number of registered applications.
+    ///
+    /// Wraps c++ `GetApplicationCount`.
+    pub fn get_application_count(&mut self) -> u32 {
+        unsafe { self.inner.as_mut().GetApplicationCount() }
+    }
+
+    /// Returns the app key of the application at `index`, where `index` is in
+    /// `0..get_application_count()`.
+    ///
+    /// Wraps c++ `GetApplicationKeyByIndex`.
+    pub fn get_application_key_by_index(&mut self, index: u32) -> Result<String> {
+        let mut buf = [0 as c_char; sys::k_unMaxApplicationKeyLength as usize];
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .GetApplicationKeyByIndex(index, buf.as_mut_ptr(), buf.len() as u32)
+        };
+        EVRApplicationError::new(err)?;
+        let key = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        Ok(key.to_string_lossy().into_owned())
+    }
+
+    /// Iterates over the app keys of all registered applications.
+    pub fn applications(&mut self) -> impl Iterator<Item = Result<String>> + '_ {
+        let count = self.get_application_count();
+        (0..count).map(move |index| self.get_application_key_by_index(index))
+    }
+
+    // ---- Launching ----
+
+    /// Launches the application with the given app key.
+    ///
+    /// Wraps c++ `LaunchApplication`.
+    pub fn launch_application(&mut self, key: &str) -> Result<()> {
+        let key = to_cstring(key)?;
+        let err = unsafe { self.inner.as_mut().LaunchApplication(key.as_ptr()) };
+        EVRApplicationError::new(err)
+    }
+
+    /// Launches an instance of a template application under `new_key`, overriding
+    /// the template's manifest values with the given `(key, value)` pairs.
+    ///
+    /// Wraps c++ `LaunchTemplateApplication`.
+    pub fn launch_template_application(
+        &mut self,
+        template_key: &str,
+        new_key: &str,
+        properties: &[(&str, &str)],
+    ) -> Result<()> {
+        let template_key = to_cstring(template_key)?;
+        let new_key = to_cstring(new_key)?;
+        let properties = properties
+            .iter()
+            .map(|(k, v)| Ok((to_cstring(k)?, to_cstring(v)?)))
+            .collect::<Result<Vec<_>>>()?;
+        // `overrides` borrows from `properties`, which must outlive the call.
+        let overrides: Vec<sys::AppOverrideKeys_t> = properties
+            .iter()
+            .map(|(k, v)| sys::AppOverrideKeys_t {
+                pchKey: k.as_ptr(),
+                pchValue: v.as_ptr(),
+            })
+            .collect();
+        let err = unsafe {
+            self.inner.as_mut().LaunchTemplateApplication(
+                template_key.as_ptr(),
+                new_key.as_ptr(),
+                overrides.as_ptr(),
+                overrides.len() as u32,
+            )
+        };
+        EVRApplicationError::new(err)
+    }
+
+    /// Launches the application registered for `mime_type`, passing it `args`.
+    ///
+    /// Wraps c++ `LaunchApplicationFromMimeType`.
+    pub fn launch_application_from_mime_type(&mut self, mime_type: &str, args: &str) -> Result<()> {
+        let mime_type = to_cstring(mime_type)?;
+        let args = to_cstring(args)?;
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .LaunchApplicationFromMimeType(mime_type.as_ptr(), args.as_ptr())
+        };
+        EVRApplicationError::new(err)
+    }
+
+    /// Has SteamVR spawn `binary` as a child process of the current scene application,
+    /// inside the VR session context.
+    ///
+    /// Wraps c++ `LaunchInternalProcess`.
+    pub fn launch_internal_process(
+        &mut self,
+        binary: &Path,
+        args: &str,
+        working_dir: &Path,
+    ) -> Result<()> {
+        let binary = to_cstring(&binary.to_string_lossy())?;
+        let args = to_cstring(args)?;
+        let working_dir = to_cstring(&working_dir.to_string_lossy())?;
+        let err = unsafe {
+            self.inner.as_mut().LaunchInternalProcess(
+                binary.as_ptr(),
+                args.as_ptr(),
+                working_dir.as_ptr(),
+            )
+        };
+        EVRApplicationError::new(err)
+    }
+
+    /// Tells SteamVR that the process with the given id is the application with
+    /// the given app key. Used by processes that were not launched by SteamVR.
+    ///
+    /// Wraps c++ `IdentifyApplication`.
+    pub fn identify_application(&mut self, process_id: u32, key: &str) -> Result<()> {
+        let key = to_cstring(key)?;
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .IdentifyApplication(process_id, key.as_ptr())
+        };
+        EVRApplicationError::new(err)
+    }
+
+    /// Checks whether the application with the given app key can be launched
+    /// right now, and prepares SteamVR for it if so.
+    ///
+    /// Wraps c++ `PerformApplicationPrelaunchCheck`.
+    pub fn perform_application_prelaunch_check(&mut self, key: &str) -> Result<()> {
+        let key = to_cstring(key)?;
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .PerformApplicationPrelaunchCheck(key.as_ptr())
+        };
+        EVRApplicationError::new(err)
+    }
+
+    // ---- Mime Types ----
+
+    /// Returns the mime types that the application with the given app key supports.
+    ///
+    /// Fails with `UnknownApplication` if no such application is installed, and with
+    /// `BufferTooSmall` if the list is implausibly long.
+    ///
+    /// Wraps c++ `GetApplicationSupportedMimeTypes`.
+    pub fn get_application_supported_mime_types(&mut self, key: &str) -> Result<Vec<String>> {
+        let key = to_cstring(key)?;
+        // The call fails the same way for unknown applications and short buffers.
+        if !self.is_application_installed_raw(&key)? {
+            return Err(EVRApplicationError::new(
+                sys::EVRApplicationError::VRApplicationError_UnknownApplication,
+            )
+            .unwrap_err());
+        }
+        // This call doesn't report the length it needs, so grow the buffer until it fits.
+        let mut len = 1024;
+        while len <= MAX_MIME_TYPES_LEN {
+            let mut buf = vec![0 as c_char; len];
+            let success = unsafe {
+                self.inner.as_mut().GetApplicationSupportedMimeTypes(
+                    key.as_ptr(),
+                    buf.as_mut_ptr(),
+                    len as u32,
+                )
+            };
+            if success {
+                return Ok(split_list(&string_from(&buf)));
+            }
+            len *= 2;
+        }
+        Err(
+            EVRApplicationError::new(sys::EVRApplicationError::VRApplicationError_BufferTooSmall)
+                .unwrap_err(),
+        )
+    }
+
+    /// Returns the app keys of all applications that support `mime_type`.
+    ///
+    /// Wraps c++ `GetApplicationsThatSupportMimeType`.
+    pub fn get_applications_that_support_mime_type(
+        &mut self,
+        mime_type: &str,
+    ) -> Result<Vec<String>> {
+        let mime_type = to_cstring(mime_type)?;
+        let keys = unsafe {
+            get_string(|ptr, n| {
+                self.inner
+                    .as_mut()
+                    .GetApplicationsThatSupportMimeType(mime_type.as_ptr(), ptr, n)
+            })
+        };
+        Ok(split_list(&keys.unwrap_or_default()))
+    }
+
+    /// Returns the arguments for the launch identified by `handle`, which comes from
+    /// a `VREvent_ApplicationMimeTypeLoad` event.
+    ///
+    /// Wraps c++ `GetApplicationLaunchArguments`.
+    pub fn get_application_launch_arguments(&mut self, handle: u32) -> String {
+        let args = unsafe {
+            get_string(|ptr, n| {
+                self.inner
+                    .as_mut()
+                    .GetApplicationLaunchArguments(handle, ptr, n)
+            })
+        };
+        args.unwrap_or_default()
+    }
+
+    // ---- Properties ----
+
+    /// Wraps c++ `GetApplicationPropertyString`.
+    pub fn get_application_property_string(
+        &mut self,
+        key: &str,
+        prop: StringProperty,
+    ) -> Result<String> {
+        let key = to_cstring(key)?;
+        let prop: sys::EVRApplicationProperty = prop.into();
+        let mut err = sys::EVRApplicationError::VRApplicationError_None;
+        // Passing a null buffer merely returns the required length, including the nul.
+        let len = unsafe {
+            self.inner.as_mut().GetApplicationPropertyString(
+                key.as_ptr(),
+                prop.clone(),
+                std::ptr::null_mut(),
+                0,
+                &mut err,
+            )
+        };
+        if len == 0 {
+            EVRApplicationError::new(err)?;
+            return Ok(String::new());
+        }
+        let mut buf = vec![0 as c_char; len as usize];
+        unsafe {
+            self.inner.as_mut().GetApplicationPropertyString(
+                key.as_ptr(),
+                prop,
+                buf.as_mut_ptr(),
+                len,
+                &mut err,
+            )
+        };
+        EVRApplicationError::new(err)?;
+        let value = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        Ok(value.to_string_lossy().into_owned())
+    }
+
+    /// Wraps c++ `GetApplicationPropertyBool`.
+    pub fn get_application_property_bool(&mut self, key: &str, prop: BoolProperty) -> Result<bool> {
+        let key = to_cstring(key)?;
+        let mut err = sys::EVRApplicationError::VRApplicationError_None;
+        let value = unsafe {
+            self.inner
+                .as_mut()
+                .GetApplicationPropertyBool(key.as_ptr(), prop.into(), &mut err)
+        };
+        EVRApplicationError::new(err)?;
+        Ok(value)
+    }
+
+    /// Wraps c++ `GetApplicationPropertyUint64`.
+    pub fn get_application_property_u64(&mut self, key: &str, prop: U64Property) -> Result<u64> {
+        let key = to_cstring(key)?;
+        let mut err = sys::EVRApplicationError::VRApplicationError_None;
+        let value = unsafe {
+            self.inner
+                .as_mut()
+                .GetApplicationPropertyUint64(key.as_ptr(), prop.into(), &mut err)
+        };
+        EVRApplicationError::new(err)?;
+        Ok(value)
+    }
+
+    /// Wraps c++ `SetApplicationAutoLaunch`.
     pub fn set_application_auto_launch(&mut self, key: &str, auto_launch: bool) -> Result<()> {
         let key_raw = if let Ok(s) = CString::new(key) {
             s
@@ -94,3 +472,14 @@ impl<'c> ApplicationsManager<'c> {
         EVRApplicationError::new(err)
     }
 }
+
+/// Upper bound on the buffer used for `GetApplicationSupportedMimeTypes`.
+const MAX_MIME_TYPES_LEN: usize = 64 * 1024;
+
+/// Splits the comma separated lists that OpenVR returns.
+fn split_list(s: &str) -> Vec<String> {
+    s.split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_owned())
+        .collect()
+}
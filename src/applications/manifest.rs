@@ -0,0 +1,230 @@
+//! Types for the `.vrmanifest` format that SteamVR uses to register applications.
+//!
+//! ```no_run
+//! # use ovr_overlay::applications::manifest::{self, ApplicationBuilder, VrManifest};
+//! # use ovr_overlay::applications::ApplicationsManager;
+//! # fn example(applications: &mut ApplicationsManager) -> Result<(), manifest::RegisterError> {
+//! let app = ApplicationBuilder::for_current_exe("my.overlay", "My Overlay")?
+//!     .dashboard_overlay(true)
+//!     .build();
+//! let manifest = VrManifest::new(vec![app]);
+//! let path = std::env::current_dir()?.join("manifest.vrmanifest");
+//! manifest::register_self(applications, &manifest, &path, true)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::ApplicationsManager;
+use crate::errors::EVRApplicationError;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The root of a `.vrmanifest` file.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VrManifest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    pub applications: Vec<Application>,
+}
+impl VrManifest {
+    pub fn new(applications: Vec<Application>) -> Self {
+        Self {
+            source: Some("builtin".to_owned()),
+            applications,
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchType {
+    #[default]
+    Binary,
+    Url,
+}
+
+/// Name and description of an application in a single language.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct LocalizedStrings {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A single application entry of a [`VrManifest`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Application {
+    pub app_key: String,
+    #[serde(default)]
+    pub launch_type: LaunchType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_path_windows: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_path_linux: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_path_osx: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action_manifest_path: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_dashboard_overlay: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_template: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_instanced: bool,
+    /// Localized strings, keyed by language such as `en_us`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub strings: HashMap<String, LocalizedStrings>,
+}
+
+/// Builds an [`Application`] entry.
+#[derive(Debug, Clone)]
+pub struct ApplicationBuilder(Application);
+impl ApplicationBuilder {
+    /// Starts an application with the given app key and english name.
+    pub fn new(app_key: impl Into<String>, name: impl Into<String>) -> Self {
+        let mut strings = HashMap::new();
+        strings.insert(
+            "en_us".to_owned(),
+            LocalizedStrings {
+                name: name.into(),
+                description: None,
+            },
+        );
+        Self(Application {
+            app_key: app_key.into(),
+            strings,
+            ..Default::default()
+        })
+    }
+
+    /// Like [`Self::new()`], with the binary path for the current platform set to
+    /// the currently running executable.
+    pub fn for_current_exe(
+        app_key: impl Into<String>,
+        name: impl Into<String>,
+    ) -> std::io::Result<Self> {
+        let exe = std::env::current_exe()?;
+        Ok(Self::new(app_key, name).binary_path(exe.to_string_lossy()))
+    }
+
+    /// Sets the binary path for the platform this is compiled for.
+    pub fn binary_path(mut self, path: impl Into<String>) -> Self {
+        let path = Some(path.into());
+        if cfg!(target_os = "windows") {
+            self.0.binary_path_windows = path;
+        } else if cfg!(target_os = "macos") {
+            self.0.binary_path_osx = path;
+        } else {
+            self.0.binary_path_linux = path;
+        }
+        self.0.launch_type = LaunchType::Binary;
+        self
+    }
+
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.0.url = Some(url.into());
+        self.0.launch_type = LaunchType::Url;
+        self
+    }
+
+    pub fn arguments(mut self, arguments: impl Into<String>) -> Self {
+        self.0.arguments = Some(arguments.into());
+        self
+    }
+
+    pub fn working_directory(mut self, dir: impl Into<String>) -> Self {
+        self.0.working_directory = Some(dir.into());
+        self
+    }
+
+    pub fn image_path(mut self, path: impl Into<String>) -> Self {
+        self.0.image_path = Some(path.into());
+        self
+    }
+
+    pub fn action_manifest_path(mut self, path: impl Into<String>) -> Self {
+        self.0.action_manifest_path = Some(path.into());
+        self
+    }
+
+    pub fn dashboard_overlay(mut self, is_dashboard_overlay: bool) -> Self {
+        self.0.is_dashboard_overlay = is_dashboard_overlay;
+        self
+    }
+
+    /// Adds or replaces the strings for `language`, such as `en_us`.
+    pub fn strings(mut self, language: impl Into<String>, strings: LocalizedStrings) -> Self {
+        self.0.strings.insert(language.into(), strings);
+        self
+    }
+
+    pub fn build(self) -> Application {
+        self.0
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RegisterError {
+    #[error("failed to write manifest: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to serialize manifest: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("failed to register manifest: {0}")]
+    Application(#[from] EVRApplicationError),
+}
+
+/// Writes `manifest` to `manifest_path` and registers it with SteamVR, optionally
+/// marking every application in it to be launched automatically with SteamVR.
+///
+/// `manifest_path` should be absolute, as SteamVR remembers it across restarts.
+pub fn register_self(
+    mngr: &mut ApplicationsManager,
+    manifest: &VrManifest,
+    manifest_path: &Path,
+    autolaunch: bool,
+) -> Result<(), RegisterError> {
+    std::fs::write(manifest_path, manifest.to_json()?)?;
+
+    mngr.add_application_manifest(manifest_path, false)?;
+    for app in &manifest.applications {
+        mngr.set_application_auto_launch(&app.app_key, autolaunch)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_roundtrip() {
+        let app = ApplicationBuilder::new("test.overlay", "Test Overlay")
+            .binary_path("/opt/test/overlay")
+            .dashboard_overlay(true)
+            .build();
+        let manifest = VrManifest::new(vec![app]);
+        let json = manifest.to_json().unwrap();
+        assert!(json.contains("\"app_key\": \"test.overlay\""));
+        assert!(json.contains("\"launch_type\": \"binary\""));
+        assert!(!json.contains("is_template"));
+        assert_eq!(VrManifest::from_json(&json).unwrap(), manifest);
+    }
+}
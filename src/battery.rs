@@ -0,0 +1,196 @@
+//! Watches the battery of every connected device, reporting changes as events.
+//!
+//! ```no_run
+//! # use ovr_overlay::battery::{BatteryEvent, BatteryMonitor};
+//! # use ovr_overlay::system::SystemManager;
+//! # use std::time::Duration;
+//! # fn example(system: &mut SystemManager) {
+//! let mut monitor = BatteryMonitor::new(Duration::from_secs(10), 0.2);
+//! loop {
+//!     for event in monitor.tick(system) {
+//!         if let BatteryEvent::Low { device, level } = event {
+//!             println!("{device:?} is at {:.0}%", level * 100.);
+//!         }
+//!     }
+//!     std::thread::sleep(Duration::from_millis(100));
+//! }
+//! # }
+//! ```
+
+use crate::system::{PropRequest, PropValue};
+use crate::traits::System;
+use crate::{sys, TrackedDeviceIndex};
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Changes reported by [`BatteryMonitor::tick()`]. Levels are between 0 and 1.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BatteryEvent {
+    /// The level of `device` changed, or the device was seen for the first time.
+    LevelChanged {
+        device: TrackedDeviceIndex,
+        level: f32,
+    },
+    /// The level of `device` dropped below the low battery threshold while not
+    /// charging.
+    Low {
+        device: TrackedDeviceIndex,
+        level: f32,
+    },
+    ChargingStarted {
+        device: TrackedDeviceIndex,
+    },
+    ChargingStopped {
+        device: TrackedDeviceIndex,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct BatteryState {
+    level: f32,
+    charging: bool,
+}
+
+/// Polls the battery properties of connected devices every `poll_interval`.
+pub struct BatteryMonitor {
+    poll_interval: Duration,
+    low_threshold: f32,
+    next_poll: Instant,
+    devices: HashMap<TrackedDeviceIndex, BatteryState>,
+}
+impl BatteryMonitor {
+    /// `low_threshold` is the level, between 0 and 1, below which
+    /// [`BatteryEvent::Low`] is reported.
+    pub fn new(poll_interval: Duration, low_threshold: f32) -> Self {
+        Self {
+            poll_interval,
+            low_threshold,
+            next_poll: Instant::now(),
+            devices: HashMap::new(),
+        }
+    }
+
+    /// The last known level and charging state of `device`.
+    pub fn get(&self, device: TrackedDeviceIndex) -> Option<(f32, bool)> {
+        self.devices
+            .get(&device)
+            .map(|state| (state.level, state.charging))
+    }
+
+    /// Polls the devices if `poll_interval` has passed since the last poll, and
+    /// returns what changed.
+    pub fn tick(&mut self, system: &mut (impl System + ?Sized)) -> Vec<BatteryEvent> {
+        let now = Instant::now();
+        if now < self.next_poll {
+            return Vec::new();
+        }
+        self.next_poll = now + self.poll_interval;
+        self.poll(system)
+    }
+
+    /// Polls the devices immediately, and returns what changed.
+    pub fn poll(&mut self, system: &mut (impl System + ?Sized)) -> Vec<BatteryEvent> {
+        use sys::ETrackedDeviceProperty::*;
+        let mut events = Vec::new();
+        for index in 0..TrackedDeviceIndex::MAX as u32 {
+            let device = TrackedDeviceIndex(index);
+            let has_battery = system.is_tracked_device_connected(device)
+                && read_bool(system, device, Prop_DeviceProvidesBatteryStatus_Bool);
+            if !has_battery {
+                self.devices.remove(&device);
+                continue;
+            }
+            let Ok(PropValue::Float(level)) = system.read_any(
+                device,
+                &PropRequest::Float(Prop_DeviceBatteryPercentage_Float),
+            ) else {
+                continue;
+            };
+            let charging = read_bool(system, device, Prop_DeviceIsCharging_Bool);
+            let new = BatteryState { level, charging };
+            let old = self.devices.insert(device, new);
+            changes(device, old, new, self.low_threshold, &mut events);
+        }
+        events
+    }
+}
+
+/// Reads a `bool` property, treating errors as `false`.
+fn read_bool(
+    system: &mut (impl System + ?Sized),
+    device: TrackedDeviceIndex,
+    prop: sys::ETrackedDeviceProperty,
+) -> bool {
+    matches!(
+        system.read_any(device, &PropRequest::Bool(prop)),
+        Ok(PropValue::Bool(true))
+    )
+}
+
+fn changes(
+    device: TrackedDeviceIndex,
+    old: Option<BatteryState>,
+    new: BatteryState,
+    low_threshold: f32,
+    events: &mut Vec<BatteryEvent>,
+) {
+    if old.map(|old| old.level) != Some(new.level) {
+        events.push(BatteryEvent::LevelChanged {
+            device,
+            level: new.level,
+        });
+    }
+    if let Some(old) = old {
+        match (old.charging, new.charging) {
+            (false, true) => events.push(BatteryEvent::ChargingStarted { device }),
+            (true, false) => events.push(BatteryEvent::ChargingStopped { device }),
+            _ => {}
+        }
+    }
+    let was_low = old.is_some_and(|old| !old.charging && old.level < low_threshold);
+    if !was_low && !new.charging && new.level < low_threshold {
+        events.push(BatteryEvent::Low {
+            device,
+            level: new.level,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICE: TrackedDeviceIndex = TrackedDeviceIndex(1);
+
+    fn run(old: Option<(f32, bool)>, new: (f32, bool)) -> Vec<BatteryEvent> {
+        let state = |(level, charging)| BatteryState { level, charging };
+        let mut events = Vec::new();
+        changes(DEVICE, old.map(state), state(new), 0.2, &mut events);
+        events
+    }
+
+    #[test]
+    fn low_is_reported_once_when_crossing() {
+        let level = |level| BatteryEvent::LevelChanged {
+            device: DEVICE,
+            level,
+        };
+        assert_eq!(run(Some((0.3, false)), (0.3, false)), []);
+        assert_eq!(
+            run(Some((0.3, false)), (0.1, false)),
+            [
+                level(0.1),
+                BatteryEvent::Low {
+                    device: DEVICE,
+                    level: 0.1
+                }
+            ]
+        );
+        assert_eq!(run(Some((0.1, false)), (0.05, false)), [level(0.05)]);
+        assert_eq!(
+            run(Some((0.1, false)), (0.1, true)),
+            [BatteryEvent::ChargingStarted { device: DEVICE }]
+        );
+    }
+}
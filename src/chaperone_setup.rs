@@ -1,24 +1,33 @@
 use sys::{HmdMatrix34_t, HmdQuad_t, HmdVector2_t};
 
-use crate::{sys, Context};
+use crate::pose::{Quad, Vec2};
+use crate::{sys, Context, Interface, InterfaceLock};
 
 use std::ffi::CString;
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
+use std::ops::{Deref, DerefMut};
+use std::os::raw::c_char;
 use std::pin::Pin;
 use std::ptr::{self, null_mut};
+use std::time::Duration;
+
+#[cfg(feature = "ovr_settings")]
+pub mod bounds;
 
 pub struct ChaperoneSetupManager<'c> {
     ctx: PhantomData<&'c Context>,
     inner: Pin<&'c mut sys::IVRChaperoneSetup>,
+    _lock: InterfaceLock<'c>,
 }
 
 impl<'c> ChaperoneSetupManager<'c> {
-    pub(super) fn new(_ctx: &'c Context) -> Self {
-        let inner = unsafe { Pin::new_unchecked(sys::VRChaperoneSetup().as_mut::<'c>().unwrap()) };
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::ChaperoneSetup) };
         Self {
             ctx: Default::default(),
             inner,
+            _lock: lock,
         }
     }
 
@@ -35,7 +44,7 @@ impl<'c> ChaperoneSetupManager<'c> {
         let res = unsafe {
             self.inner
                 .as_mut()
-                .ExportLiveToBuffer(data.as_mut_ptr() as *mut i8, &mut len)
+                .ExportLiveToBuffer(data.as_mut_ptr() as *mut c_char, &mut len)
         };
         if res {
             CString::from_vec_with_nul(data).ok()
@@ -88,7 +97,13 @@ impl<'c> ChaperoneSetupManager<'c> {
         }
     }
 
-    pub fn get_live_collision_bounds_info(&mut self) -> Vec<HmdQuad_t> {
+    /// The walls of the live collision bounds, in the standing tracking universe.
+    pub fn get_live_collision_bounds_info(&mut self) -> Vec<Quad> {
+        let quads = self.get_live_collision_bounds_info_raw();
+        quads.into_iter().map(Quad::from).collect()
+    }
+
+    pub fn get_live_collision_bounds_info_raw(&mut self) -> Vec<HmdQuad_t> {
         let mut num_quads = 0u32;
         unsafe {
             self.inner
@@ -110,7 +125,20 @@ impl<'c> ChaperoneSetupManager<'c> {
         quads
     }
 
-    pub fn get_working_collision_bounds_info(&mut self) -> Vec<HmdQuad_t> {
+    /// Horizontal distance in meters from `point`, in the standing tracking universe,
+    /// to the nearest wall of the live collision bounds. `None` if there are no bounds.
+    ///
+    /// Pass the translation of a device pose to check how close the device is.
+    pub fn distance_to_live_bounds(&mut self, point: [f32; 3]) -> Option<f32> {
+        distance_to_bounds(&self.get_live_collision_bounds_info(), point)
+    }
+
+    pub fn get_working_collision_bounds_info(&mut self) -> Vec<Quad> {
+        let quads = self.get_working_collision_bounds_info_raw();
+        quads.into_iter().map(Quad::from).collect()
+    }
+
+    pub fn get_working_collision_bounds_info_raw(&mut self) -> Vec<HmdQuad_t> {
         let mut num_quads = 0u32;
         unsafe {
             self.inner
@@ -132,7 +160,12 @@ impl<'c> ChaperoneSetupManager<'c> {
         quads
     }
 
-    pub fn set_working_collision_bounds_info(&mut self, quads: &mut [HmdQuad_t]) {
+    pub fn set_working_collision_bounds_info(&mut self, quads: &[Quad]) {
+        let mut quads: Vec<HmdQuad_t> = quads.iter().copied().map(HmdQuad_t::from).collect();
+        self.set_working_collision_bounds_info_raw(&mut quads)
+    }
+
+    pub fn set_working_collision_bounds_info_raw(&mut self, quads: &mut [HmdQuad_t]) {
         unsafe {
             self.inner
                 .as_mut()
@@ -159,7 +192,11 @@ impl<'c> ChaperoneSetupManager<'c> {
         unsafe { self.inner.as_mut().SetWorkingPlayAreaSize(size_x, size_y) }
     }
 
-    pub fn get_working_play_area_rect(&mut self) -> Option<HmdQuad_t> {
+    pub fn get_working_play_area_rect(&mut self) -> Option<Quad> {
+        self.get_working_play_area_rect_raw().map(Quad::from)
+    }
+
+    pub fn get_working_play_area_rect_raw(&mut self) -> Option<HmdQuad_t> {
         let mut rect = MaybeUninit::uninit();
         let success = unsafe {
             self.inner
@@ -173,7 +210,15 @@ impl<'c> ChaperoneSetupManager<'c> {
         }
     }
 
-    pub fn set_working_perimeter(&mut self, points: &mut [HmdVector2_t]) {
+    /// Sets the perimeter of the play area, as points on the floor of the standing
+    /// tracking universe.
+    pub fn set_working_perimeter(&mut self, points: &[Vec2]) {
+        let mut points: Vec<HmdVector2_t> =
+            points.iter().copied().map(HmdVector2_t::from).collect();
+        self.set_working_perimeter_raw(&mut points)
+    }
+
+    pub fn set_working_perimeter_raw(&mut self, points: &mut [HmdVector2_t]) {
         unsafe {
             self.inner
                 .as_mut()
@@ -200,4 +245,117 @@ impl<'c> ChaperoneSetupManager<'c> {
     pub fn hide_working_set_preview(&mut self) {
         unsafe { self.inner.as_mut().HideWorkingSetPreview() }
     }
+
+    /// Shows the working set preview until the returned guard is dropped, so the
+    /// preview is hidden again even if editing fails with an error or panics. The
+    /// guard derefs to the manager, to keep editing the working set meanwhile.
+    pub fn preview_working_set_guard(&mut self) -> WorkingSetPreview<'_, 'c> {
+        self.show_working_set_preview();
+        WorkingSetPreview { mngr: self }
+    }
+
+    /// Shows the working set preview for `duration`, blocking meanwhile.
+    pub fn preview_working_set(&mut self, duration: Duration) {
+        let _preview = self.preview_working_set_guard();
+        std::thread::sleep(duration);
+    }
+
+    /// Shows the working set preview for `duration` without blocking. The preview is
+    /// also hidden if the future is dropped before it completes.
+    ///
+    /// The manager is only acquired to show and hide the preview, as it must not be
+    /// held across awaits. Doing so panics if a chaperone setup manager is alive on the
+    /// thread that polls or drops the future.
+    #[cfg(feature = "async")]
+    pub async fn preview_working_set_async(ctx: &Context, duration: Duration) {
+        struct HideOnDrop<'c>(&'c Context);
+        impl Drop for HideOnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.chaperone_setup_mngr().hide_working_set_preview();
+            }
+        }
+
+        ctx.chaperone_setup_mngr().show_working_set_preview();
+        let _hide = HideOnDrop(ctx);
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Returned by [`ChaperoneSetupManager::preview_working_set_guard()`], hides the
+/// working set preview when dropped.
+pub struct WorkingSetPreview<'m, 'c> {
+    mngr: &'m mut ChaperoneSetupManager<'c>,
+}
+
+impl<'c> Deref for WorkingSetPreview<'_, 'c> {
+    type Target = ChaperoneSetupManager<'c>;
+
+    fn deref(&self) -> &Self::Target {
+        self.mngr
+    }
+}
+
+impl DerefMut for WorkingSetPreview<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.mngr
+    }
+}
+
+impl Drop for WorkingSetPreview<'_, '_> {
+    fn drop(&mut self) {
+        self.mngr.hide_working_set_preview();
+    }
+}
+
+/// Horizontal distance from `point` to the nearest of the wall `quads`, ignoring height.
+/// `None` if `quads` is empty.
+pub fn distance_to_bounds(quads: &[Quad], point: [f32; 3]) -> Option<f32> {
+    let p = [point[0], point[2]];
+    quads
+        .iter()
+        .flat_map(Quad::edges)
+        .map(|(a, b)| distance_to_segment(p, [a[0], a[2]], [b[0], b[2]]))
+        .min_by(f32::total_cmp)
+}
+
+fn distance_to_segment(p: [f32; 2], a: [f32; 2], b: [f32; 2]) -> f32 {
+    let ab = [b[0] - a[0], b[1] - a[1]];
+    let ap = [p[0] - a[0], p[1] - a[1]];
+    let len_sq = ab[0] * ab[0] + ab[1] * ab[1];
+    let t = if len_sq > 0. {
+        ((ap[0] * ab[0] + ap[1] * ab[1]) / len_sq).clamp(0., 1.)
+    } else {
+        0.
+    };
+    let d = [ap[0] - ab[0] * t, ap[1] - ab[1] * t];
+    (d[0] * d[0] + d[1] * d[1]).sqrt()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn wall(from: [f32; 2], to: [f32; 2]) -> Quad {
+        let corner = |[x, z]: [f32; 2], y| [x, y, z];
+        Quad([
+            corner(from, 0.),
+            corner(to, 0.),
+            corner(to, 2.),
+            corner(from, 2.),
+        ])
+    }
+
+    #[test]
+    fn test_distance_to_bounds() {
+        let room = [
+            wall([-1., -1.], [1., -1.]),
+            wall([1., -1.], [1., 1.]),
+            wall([1., 1.], [-1., 1.]),
+            wall([-1., 1.], [-1., -1.]),
+        ];
+        assert_eq!(distance_to_bounds(&room, [0., 1.7, 0.]), Some(1.));
+        assert_eq!(distance_to_bounds(&room, [0.5, 0.2, 0.]), Some(0.5));
+        assert_eq!(distance_to_bounds(&room, [0., 0., 1.5]), Some(0.5));
+        assert_eq!(distance_to_bounds(&[], [0.; 3]), None);
+    }
 }
@@ -0,0 +1,112 @@
+//! How the chaperone bounds are drawn, from the `collisionBounds` settings section.
+//!
+//! The bounds geometry lives in [`ChaperoneSetupManager`](super::ChaperoneSetupManager),
+//! but its appearance is stored in the settings, so these are methods of
+//! [`SettingsManager`].
+//!
+//! ```no_run
+//! # use ovr_overlay::chaperone_setup::bounds::BoundsStyle;
+//! # use ovr_overlay::settings::SettingsManager;
+//! # fn example(settings: &mut SettingsManager) {
+//! settings.set_bounds_style(BoundsStyle::Squares).unwrap();
+//! settings.set_bounds_fade_distance(0.5).unwrap();
+//! # }
+//! ```
+
+use crate::errors::EVRSettingsError;
+use crate::settings::SettingsManager;
+
+use std::ffi::CStr;
+
+fn section() -> &'static CStr {
+    CStr::from_bytes_with_nul(b"collisionBounds\0").unwrap()
+}
+
+fn style_key() -> &'static CStr {
+    CStr::from_bytes_with_nul(b"CollisionBoundsStyle\0").unwrap()
+}
+
+fn fade_distance_key() -> &'static CStr {
+    CStr::from_bytes_with_nul(b"CollisionBoundsFadeDistance\0").unwrap()
+}
+
+fn ground_perimeter_key() -> &'static CStr {
+    CStr::from_bytes_with_nul(b"CollisionBoundsGroundPerimeterOn\0").unwrap()
+}
+
+/// The pattern the bounds walls are drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoundsStyle {
+    Beginner,
+    Intermediate,
+    Squares,
+    Advanced,
+    /// Only the ground perimeter, if enabled, is drawn.
+    None,
+}
+
+impl BoundsStyle {
+    pub const ALL: [Self; 5] = [
+        Self::Beginner,
+        Self::Intermediate,
+        Self::Squares,
+        Self::Advanced,
+        Self::None,
+    ];
+
+    /// The value stored in the settings.
+    pub fn as_setting(self) -> i32 {
+        self as i32
+    }
+
+    pub fn from_setting(value: i32) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|style| style.as_setting() == value)
+    }
+}
+
+impl SettingsManager<'_> {
+    /// The bounds style, or `None` if the setting holds an unknown value.
+    pub fn get_bounds_style(&mut self) -> Result<Option<BoundsStyle>, EVRSettingsError> {
+        self.get_int32(section(), style_key())
+            .map(BoundsStyle::from_setting)
+    }
+
+    pub fn set_bounds_style(&mut self, style: BoundsStyle) -> Result<(), EVRSettingsError> {
+        self.set_int32(section(), style_key(), style.as_setting())
+    }
+
+    /// How close, in meters, the user has to get to the bounds for them to fade in.
+    pub fn get_bounds_fade_distance(&mut self) -> Result<f32, EVRSettingsError> {
+        self.get_float(section(), fade_distance_key())
+    }
+
+    pub fn set_bounds_fade_distance(&mut self, meters: f32) -> Result<(), EVRSettingsError> {
+        self.set_float(section(), fade_distance_key(), meters)
+    }
+
+    /// Whether the outline of the play area is drawn on the floor.
+    pub fn get_bounds_ground_perimeter(&mut self) -> Result<bool, EVRSettingsError> {
+        self.get_bool(section(), ground_perimeter_key())
+    }
+
+    pub fn set_bounds_ground_perimeter(&mut self, on: bool) -> Result<(), EVRSettingsError> {
+        self.set_bool(section(), ground_perimeter_key(), on)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_style_settings() {
+        for style in BoundsStyle::ALL {
+            assert_eq!(BoundsStyle::from_setting(style.as_setting()), Some(style));
+        }
+        assert_eq!(BoundsStyle::Squares.as_setting(), 2);
+        assert_eq!(BoundsStyle::from_setting(5), None);
+    }
+}
@@ -0,0 +1,143 @@
+//! The RGBA color type used by overlay tints and compositor fades.
+
+use crate::sys;
+
+use std::str::FromStr;
+
+/// A linear RGBA color with channels between 0 and 1.
+///
+/// As an overlay tint, each channel of the overlay is multiplied with it. Parses from
+/// hex strings like `#ff8000` or `#ff800080`:
+///
+/// ```
+/// # use ovr_overlay::Color;
+/// let orange: Color = "#ff8000".parse().unwrap();
+/// assert_eq!(orange, Color::from([1., 128. / 255., 0., 1.]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+/// The former name of [`Color`].
+#[deprecated(note = "renamed to `Color`")]
+pub type ColorTint = Color;
+
+impl Color {
+    pub const WHITE: Self = Self::new(1., 1., 1., 1.);
+    pub const BLACK: Self = Self::new(0., 0., 0., 1.);
+    pub const TRANSPARENT: Self = Self::new(0., 0., 0., 0.);
+
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// The same color with a different alpha.
+    pub const fn with_alpha(self, a: f32) -> Self {
+        Self { a, ..self }
+    }
+}
+
+/// Opaque white, which leaves a tinted overlay unchanged.
+impl Default for Color {
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+impl From<[f32; 4]> for Color {
+    fn from([r, g, b, a]: [f32; 4]) -> Self {
+        Self { r, g, b, a }
+    }
+}
+
+impl From<Color> for [f32; 4] {
+    fn from(color: Color) -> Self {
+        [color.r, color.g, color.b, color.a]
+    }
+}
+
+impl From<sys::HmdColor_t> for Color {
+    fn from(color: sys::HmdColor_t) -> Self {
+        Self::new(color.r, color.g, color.b, color.a)
+    }
+}
+
+impl From<Color> for sys::HmdColor_t {
+    fn from(color: Color) -> Self {
+        sys::HmdColor_t {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+            a: color.a,
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<image::Rgba<u8>> for Color {
+    fn from(image::Rgba([r, g, b, a]): image::Rgba<u8>) -> Self {
+        let f = |c: u8| c as f32 / 255.;
+        Self::new(f(r), f(g), f(b), f(a))
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<Color> for image::Rgba<u8> {
+    fn from(color: Color) -> Self {
+        let u = |c: f32| (c.clamp(0., 1.) * 255.).round() as u8;
+        image::Rgba([u(color.r), u(color.g), u(color.b), u(color.a)])
+    }
+}
+
+/// Returned when parsing a [`Color`] from a string that isn't `#rrggbb` or `#rrggbbaa`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid hex color {0:?}, expected #rrggbb or #rrggbbaa")]
+pub struct ParseColorError(String);
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parses `#rrggbb` or `#rrggbbaa`, with or without the `#`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseColorError(s.to_owned());
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+            return Err(err());
+        }
+        let channel = |i: usize| match hex.get(i * 2..i * 2 + 2) {
+            Some(c) => u8::from_str_radix(c, 16)
+                .map(|c| c as f32 / 255.)
+                .map_err(|_| err()),
+            None => Ok(1.),
+        };
+        Ok(Self::new(
+            channel(0)?,
+            channel(1)?,
+            channel(2)?,
+            channel(3)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!("#ffffff".parse(), Ok(Color::WHITE));
+        assert_eq!("00000000".parse(), Ok(Color::TRANSPARENT));
+        assert_eq!(
+            "#ff000080".parse::<Color>().unwrap(),
+            Color::new(1., 0., 0., 128. / 255.)
+        );
+        for invalid in ["", "#fff", "#fffffff", "#gggggg", "#ff00ff00ff"] {
+            assert!(invalid.parse::<Color>().is_err(), "{invalid}");
+        }
+    }
+}
@@ -1,22 +1,38 @@
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
+use std::os::raw::c_char;
 use std::pin::Pin;
+use std::time::Duration;
 
-use crate::{errors::EVRCompositorError, sys, Context};
+pub mod frame_stats;
+
+use crate::{
+    errors::EVRCompositorError, sys, Color, Context, Interface, InterfaceLock, TextureBounds,
+};
 
 pub struct CompositorManager<'c> {
     ctx: PhantomData<&'c Context>,
     inner: Pin<&'c mut sys::IVRCompositor>,
+    _lock: InterfaceLock<'c>,
 }
 
 type Result<T> = std::result::Result<T, EVRCompositorError>;
 
+/// A frame timing with its size set, as OpenVR requires before filling it in.
+pub fn empty_frame_timing() -> sys::Compositor_FrameTiming {
+    // Safety: the struct is plain numbers.
+    let mut timing: sys::Compositor_FrameTiming = unsafe { std::mem::zeroed() };
+    timing.m_nSize = std::mem::size_of::<sys::Compositor_FrameTiming>() as u32;
+    timing
+}
+
 impl<'c> CompositorManager<'c> {
-    pub(super) fn new(_ctx: &'c Context) -> Self {
-        let inner = unsafe { Pin::new_unchecked(sys::VRCompositor().as_mut::<'c>().unwrap()) };
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::Compositor) };
         Self {
             ctx: Default::default(),
             inner,
+            _lock: lock,
         }
     }
 
@@ -36,14 +52,100 @@ impl<'c> CompositorManager<'c> {
         EVRCompositorError::new(err)
     }
 
+    /// Submits a Vulkan image as the frame for `eye`, optionally cropped to `bounds`.
+    pub fn submit_vulkan(
+        &mut self,
+        eye: sys::EVREye,
+        texture: &mut sys::VRVulkanTextureData_t,
+        bounds: Option<&TextureBounds>,
+    ) -> Result<()> {
+        let texture = sys::Texture_t {
+            handle: texture as *mut sys::VRVulkanTextureData_t as *mut _,
+            eType: sys::ETextureType::TextureType_Vulkan,
+            eColorSpace: sys::EColorSpace::ColorSpace_Auto,
+        };
+        let bounds = bounds.map_or(std::ptr::null(), |b| &b.0 as *const _);
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .Submit(eye, &texture, bounds, sys::EVRSubmitFlags::Submit_Default)
+        };
+        EVRCompositorError::new(err)
+    }
+
+    /// Fades the view to `color` over `seconds`. With `background`, only the scene
+    /// behind overlays is faded.
+    pub fn fade_to_color(&mut self, seconds: f32, color: impl Into<Color>, background: bool) {
+        let color = color.into();
+        unsafe {
+            self.inner
+                .as_mut()
+                .FadeToColor(seconds, color.r, color.g, color.b, color.a, background)
+        }
+    }
+
+    /// Timing of the frame `frames_ago` frames back, 0 being the most recent.
+    pub fn get_frame_timing(&mut self, frames_ago: u32) -> Option<sys::Compositor_FrameTiming> {
+        let mut timing = empty_frame_timing();
+        let ok = unsafe { self.inner.as_mut().GetFrameTiming(&mut timing, frames_ago) };
+        ok.then_some(timing)
+    }
+
+    /// Fills `timings` with the most recent frames, oldest first, and returns how many
+    /// were written.
+    pub fn get_frame_timings(&mut self, timings: &mut [sys::Compositor_FrameTiming]) -> usize {
+        for timing in timings.iter_mut() {
+            *timing = empty_frame_timing();
+        }
+        let n = unsafe {
+            self.inner
+                .as_mut()
+                .GetFrameTimings(timings.as_mut_ptr(), timings.len() as u32)
+        };
+        n as usize
+    }
+
     pub fn get_tracking_space(&mut self) -> sys::ETrackingUniverseOrigin {
         unsafe { self.inner.as_mut().GetTrackingSpace() }
     }
 
+    /// Sets the universe the compositor expects submitted poses in, either a
+    /// [`Universe`](crate::Universe) or the sys enum.
+    pub fn set_tracking_space(&mut self, origin: impl Into<sys::ETrackingUniverseOrigin>) {
+        unsafe { self.inner.as_mut().SetTrackingSpace(origin.into()) }
+    }
+
+    /// Seconds until the compositor starts the next frame, right after vsync.
     pub fn get_frame_time_remaining(&mut self) -> f32 {
         unsafe { self.inner.as_mut().GetFrameTimeRemaining() }
     }
 
+    /// The index of the most recent frame, counting up by one per vsync including
+    /// dropped frames.
+    pub fn get_last_frame_index(&mut self) -> Option<u32> {
+        self.get_frame_timing(0).map(|timing| timing.m_nFrameIndex)
+    }
+
+    /// How long until `margin` after the next frame starts. Work scheduled then gets the
+    /// whole frame before the next vsync.
+    ///
+    /// Sleep after dropping the manager, so other threads can use the compositor
+    /// meanwhile:
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # fn example(ctx: &ovr_overlay::Context) {
+    /// let wait = ctx
+    ///     .compositor_mngr()
+    ///     .time_until_frame_start(Duration::from_millis(1));
+    /// std::thread::sleep(wait);
+    /// # }
+    /// ```
+    pub fn time_until_frame_start(&mut self, margin: Duration) -> Duration {
+        let remaining = self.get_frame_time_remaining().max(0.);
+        Duration::try_from_secs_f32(remaining).unwrap_or_default() + margin
+    }
+
     pub fn get_current_scene_focus_process(&mut self) -> u32 {
         unsafe { self.inner.as_mut().GetCurrentSceneFocusProcess() }
     }
@@ -57,22 +159,32 @@ impl<'c> CompositorManager<'c> {
     }
 
     pub fn get_vulkan_instance_extensions_required(&mut self) -> Vec<String> {
-        let mut buf = [0i8; 1024];
+        split_extensions(&self.get_vulkan_instance_extensions_required_raw())
+    }
+
+    /// Like [`Self::get_vulkan_instance_extensions_required()`], but returns the
+    /// space separated list exactly as OpenVR reported it.
+    pub fn get_vulkan_instance_extensions_required_raw(&mut self) -> CString {
+        let mut buf = [0 as c_char; 1024];
         let len = unsafe {
             self.inner
                 .as_mut()
                 .GetVulkanInstanceExtensionsRequired(buf.as_mut_ptr(), buf.len() as u32)
         };
         if len == 0 {
-            return vec![];
+            return CString::default();
         }
-        let cstr = unsafe { CStr::from_ptr(buf.as_ptr()) };
-        let s = cstr.to_str().unwrap();
-        s.split(' ').map(|s| s.to_owned()).collect()
+        unsafe { CStr::from_ptr(buf.as_ptr()) }.to_owned()
     }
 
     pub fn get_vulkan_device_extensions_required(&mut self, device: u64) -> Vec<String> {
-        let mut buf = [0i8; 1024];
+        split_extensions(&self.get_vulkan_device_extensions_required_raw(device))
+    }
+
+    /// Like [`Self::get_vulkan_device_extensions_required()`], but returns the
+    /// space separated list exactly as OpenVR reported it.
+    pub fn get_vulkan_device_extensions_required_raw(&mut self, device: u64) -> CString {
+        let mut buf = [0 as c_char; 1024];
         unsafe {
             let len = self.inner.as_mut().GetVulkanDeviceExtensionsRequired(
                 device as usize as _,
@@ -80,11 +192,18 @@ impl<'c> CompositorManager<'c> {
                 buf.len() as u32,
             );
             if len == 0 {
-                return vec![];
+                return CString::default();
             }
         }
-        let cstr = unsafe { CStr::from_ptr(buf.as_ptr()) };
-        let s = cstr.to_str().unwrap();
-        s.split(' ').map(|s| s.to_owned()).collect()
+        unsafe { CStr::from_ptr(buf.as_ptr()) }.to_owned()
     }
 }
+
+fn split_extensions(extensions: &CStr) -> Vec<String> {
+    extensions
+        .to_string_lossy()
+        .split(' ')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_owned())
+        .collect()
+}
@@ -0,0 +1,213 @@
+//! Rolling statistics over the compositor's frame timings, such as for a performance
+//! overlay.
+//!
+//! ```no_run
+//! # use ovr_overlay::compositor::CompositorManager;
+//! # use ovr_overlay::compositor::frame_stats::FrameStats;
+//! # fn example(compositor: &mut CompositorManager) {
+//! let mut stats = FrameStats::new(90);
+//! loop {
+//!     stats.update(compositor);
+//!     let snapshot = stats.snapshot();
+//!     println!("gpu p99: {:.1}ms, dropped: {}", snapshot.gpu_ms.p99, snapshot.dropped);
+//! }
+//! # }
+//! ```
+
+use super::{empty_frame_timing, CompositorManager};
+use crate::sys;
+
+use std::collections::VecDeque;
+
+/// `m_nReprojectionFlags` bits set when a frame was reprojected.
+const REPROJECTION_FLAGS: u32 = 0x04 /* Async */ | 0x08 /* Motion */;
+
+/// The parts of a frame timing that are aggregated.
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Sample {
+    gpu_ms: f32,
+    cpu_ms: f32,
+    interval_ms: f32,
+    dropped: u32,
+    mispresented: u32,
+    reprojected: bool,
+}
+impl From<&sys::Compositor_FrameTiming> for Sample {
+    fn from(t: &sys::Compositor_FrameTiming) -> Self {
+        Self {
+            gpu_ms: t.m_flTotalRenderGpuMs,
+            cpu_ms: t.m_flCompositorRenderCpuMs,
+            interval_ms: t.m_flClientFrameIntervalMs,
+            dropped: t.m_nNumDroppedFrames,
+            mispresented: t.m_nNumMisPresented,
+            reprojected: t.m_nReprojectionFlags & REPROJECTION_FLAGS != 0,
+        }
+    }
+}
+
+/// Percentiles of a timing over the window, in milliseconds.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Percentiles {
+    pub p50: f32,
+    pub p90: f32,
+    pub p99: f32,
+    pub max: f32,
+}
+impl Percentiles {
+    fn of(mut values: Vec<f32>) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        values.sort_by(f32::total_cmp);
+        // Nearest rank, in integers to avoid rounding up past it.
+        let at = |percent: usize| values[(percent * values.len()).div_ceil(100).max(1) - 1];
+        Self {
+            p50: at(50),
+            p90: at(90),
+            p99: at(99),
+            max: values[values.len() - 1],
+        }
+    }
+}
+
+/// Statistics over the frames in the window at one point in time.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct FrameStatsSnapshot {
+    /// Number of frames in the window.
+    pub frames: usize,
+    /// GPU time of the application's frames.
+    pub gpu_ms: Percentiles,
+    /// CPU time the compositor spent rendering.
+    pub compositor_cpu_ms: Percentiles,
+    /// Time between the application's frames.
+    pub interval_ms: Percentiles,
+    /// Frames the compositor had to present again because no new one was ready.
+    pub dropped: u32,
+    /// Frames that were presented late.
+    pub mispresented: u32,
+    /// Frames that were reprojected.
+    pub reprojected: usize,
+}
+
+/// Keeps the timings of the last `window` frames.
+#[derive(Debug)]
+pub struct FrameStats {
+    window: usize,
+    samples: VecDeque<Sample>,
+    last_frame_index: Option<u32>,
+    buf: Vec<sys::Compositor_FrameTiming>,
+}
+impl FrameStats {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            samples: VecDeque::with_capacity(window),
+            last_frame_index: None,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Adds the frames completed since the last update.
+    pub fn update(&mut self, compositor: &mut CompositorManager) {
+        let mut buf = std::mem::take(&mut self.buf);
+        buf.resize_with(self.window, empty_frame_timing);
+        let n = compositor.get_frame_timings(&mut buf);
+        self.add_timings(&buf[..n]);
+        self.buf = buf;
+    }
+
+    /// Adds the timings, oldest first, that are newer than the last added one.
+    fn add_timings(&mut self, timings: &[sys::Compositor_FrameTiming]) {
+        // Frame indices start over when the compositor restarts.
+        let restarted = timings.last().is_some_and(|newest| {
+            self.last_frame_index
+                .is_some_and(|last| newest.m_nFrameIndex < last)
+        });
+        if restarted {
+            self.last_frame_index = None;
+        }
+        for timing in timings {
+            if !self
+                .last_frame_index
+                .is_some_and(|last| timing.m_nFrameIndex <= last)
+            {
+                self.push(Sample::from(timing));
+                self.last_frame_index = Some(timing.m_nFrameIndex);
+            }
+        }
+    }
+
+    fn push(&mut self, sample: Sample) {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Forgets all frames.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn snapshot(&self) -> FrameStatsSnapshot {
+        let collect = |f: fn(&Sample) -> f32| Percentiles::of(self.samples.iter().map(f).collect());
+        FrameStatsSnapshot {
+            frames: self.samples.len(),
+            gpu_ms: collect(|s| s.gpu_ms),
+            compositor_cpu_ms: collect(|s| s.cpu_ms),
+            interval_ms: collect(|s| s.interval_ms),
+            dropped: self.samples.iter().map(|s| s.dropped).sum(),
+            mispresented: self.samples.iter().map(|s| s.mispresented).sum(),
+            reprojected: self.samples.iter().filter(|s| s.reprojected).count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_and_percentiles() {
+        let mut stats = FrameStats::new(100);
+        for i in 1..=150 {
+            stats.push(Sample {
+                gpu_ms: i as f32,
+                cpu_ms: 1.,
+                interval_ms: 11.,
+                dropped: 0,
+                mispresented: 0,
+                reprojected: i % 10 == 0,
+            });
+        }
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.frames, 100);
+        assert_eq!(snapshot.gpu_ms.p50, 100.);
+        assert_eq!(snapshot.gpu_ms.p90, 140.);
+        assert_eq!(snapshot.gpu_ms.p99, 149.);
+        assert_eq!(snapshot.gpu_ms.max, 150.);
+        assert_eq!(snapshot.reprojected, 10);
+    }
+
+    #[test]
+    fn frame_index_restart() {
+        let timings = |indices: &[u32]| {
+            indices
+                .iter()
+                .map(|&i| {
+                    let mut timing = empty_frame_timing();
+                    timing.m_nFrameIndex = i;
+                    timing
+                })
+                .collect::<Vec<_>>()
+        };
+        let mut stats = FrameStats::new(10);
+        stats.add_timings(&timings(&[100, 101, 102]));
+        stats.add_timings(&timings(&[101, 102, 103]));
+        assert_eq!(stats.snapshot().frames, 4);
+        // The compositor restarted.
+        stats.add_timings(&timings(&[1, 2]));
+        assert_eq!(stats.snapshot().frames, 6);
+        assert_eq!(stats.last_frame_index, Some(2));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{errors::EVRDebugError, sys, Context, Interface, InterfaceLock, TrackedDeviceIndex};
+
+use derive_more::{From, Into};
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::pin::Pin;
+
+pub struct DebugManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVRDebug>,
+    _lock: InterfaceLock<'c>,
+}
+
+type Result<T> = std::result::Result<T, EVRDebugError>;
+
+/// Handle to a profiler event started with [`DebugManager::begin_vr_profiler_event()`].
+#[derive(From, Into, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[repr(transparent)]
+pub struct VrProfilerEventHandle(pub sys::VrProfilerEventHandle_t);
+
+fn to_cstring(s: &str) -> Result<CString> {
+    CString::new(s)
+        .map_err(|_| EVRDebugError::new(sys::EVRDebugError::VRDebugError_BadParameter).unwrap_err())
+}
+
+impl<'c> DebugManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::Debug) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+        }
+    }
+
+    /// Emits an instantaneous event to the SteamVR profiler.
+    ///
+    /// Wraps c++ `EmitVrProfilerEvent`.
+    pub fn emit_vr_profiler_event(&mut self, message: &str) -> Result<()> {
+        let message = to_cstring(message)?;
+        let err = unsafe { self.inner.as_mut().EmitVrProfilerEvent(message.as_ptr()) };
+        EVRDebugError::new(err)
+    }
+
+    /// Starts a profiler event with a duration. Finish it with
+    /// [`Self::finish_vr_profiler_event()`].
+    ///
+    /// Wraps c++ `BeginVrProfilerEvent`.
+    pub fn begin_vr_profiler_event(&mut self) -> Result<VrProfilerEventHandle> {
+        let mut handle: sys::VrProfilerEventHandle_t = 0;
+        let err = unsafe { self.inner.as_mut().BeginVrProfilerEvent(&mut handle) };
+        EVRDebugError::new(err)?;
+        Ok(VrProfilerEventHandle(handle))
+    }
+
+    /// Wraps c++ `FinishVrProfilerEvent`.
+    pub fn finish_vr_profiler_event(
+        &mut self,
+        handle: VrProfilerEventHandle,
+        message: &str,
+    ) -> Result<()> {
+        let message = to_cstring(message)?;
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .FinishVrProfilerEvent(handle.0, message.as_ptr())
+        };
+        EVRDebugError::new(err)
+    }
+
+    /// Sends `request` to the driver of the given device and returns its response.
+    ///
+    /// The request is sent exactly once, so the buffer is sized for the largest
+    /// response OpenVR allows.
+    ///
+    /// Wraps c++ `DriverDebugRequest`.
+    pub fn driver_debug_request(
+        &mut self,
+        index: TrackedDeviceIndex,
+        request: &str,
+    ) -> Result<String> {
+        let request = to_cstring(request)?;
+        let mut buf = vec![0 as c_char; sys::k_unMaxDriverDebugResponseSize as usize];
+        let len = unsafe {
+            self.inner.as_mut().DriverDebugRequest(
+                index.0,
+                request.as_ptr(),
+                buf.as_mut_ptr(),
+                buf.len() as u32,
+            )
+        };
+        if len == 0 {
+            return Ok(String::new());
+        }
+        let response = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        Ok(response.to_string_lossy().into_owned())
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{sys, Context, Interface, InterfaceLock};
+
+use derive_more::{From, Into};
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::pin::Pin;
+
+pub struct DriverManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVRDriverManager>,
+    _lock: InterfaceLock<'c>,
+}
+
+/// Index of a driver, in `0..DriverManager::get_driver_count()`.
+#[derive(From, Into, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[repr(transparent)]
+pub struct DriverId(pub sys::DriverId_t);
+
+/// Property container handle of a driver.
+#[derive(From, Into, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[repr(transparent)]
+pub struct DriverHandle(pub sys::DriverHandle_t);
+
+impl<'c> DriverManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::DriverManager) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+        }
+    }
+
+    /// Wraps c++ `GetDriverCount`.
+    pub fn get_driver_count(&mut self) -> u32 {
+        self.inner.GetDriverCount()
+    }
+
+    /// Returns the name of the driver, or `None` if `driver` is out of range.
+    ///
+    /// Wraps c++ `GetDriverName`.
+    pub fn get_driver_name(&mut self, driver: DriverId) -> Option<String> {
+        let len = unsafe {
+            self.inner
+                .as_mut()
+                .GetDriverName(driver.0, std::ptr::null_mut(), 0)
+        };
+        if len == 0 {
+            return None;
+        }
+        let mut buf = vec![0 as c_char; len as usize];
+        unsafe {
+            self.inner
+                .as_mut()
+                .GetDriverName(driver.0, buf.as_mut_ptr(), len)
+        };
+        let name = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        Some(name.to_string_lossy().into_owned())
+    }
+
+    /// Returns the property container handle of the driver called `name`, or `None`
+    /// if there is no such driver.
+    ///
+    /// Wraps c++ `GetDriverHandle`.
+    pub fn get_driver_handle(&mut self, name: &str) -> Option<DriverHandle> {
+        let name = CString::new(name).ok()?;
+        let handle = unsafe { self.inner.as_mut().GetDriverHandle(name.as_ptr()) };
+        if handle == 0 {
+            None
+        } else {
+            Some(DriverHandle(handle))
+        }
+    }
+
+    /// Returns true if the driver is enabled.
+    ///
+    /// Wraps c++ `IsEnabled`.
+    pub fn is_enabled(&mut self, driver: DriverId) -> bool {
+        self.inner.IsEnabled(driver.0)
+    }
+
+    /// Iterates over the ids and names of all drivers.
+    pub fn drivers(&mut self) -> impl Iterator<Item = (DriverId, String)> + '_ {
+        let count = self.get_driver_count();
+        (0..count).filter_map(move |id| {
+            let id = DriverId(id);
+            self.get_driver_name(id).map(|name| (id, name))
+        })
+    }
+}
@@ -0,0 +1,188 @@
+//! Drives an [`egui`] UI from the input OpenVR sends to an overlay.
+//!
+//! [`EguiOverlay`] turns the laser pointer's mouse, scroll and keyboard events into
+//! egui input and runs the UI. Drawing the resulting [`egui::FullOutput`] is up to the
+//! caller, or to [`renderer::EguiRenderer`] with the `egui-wgpu` feature.
+//!
+//! ```no_run
+//! # use ovr_overlay::overlay::{OverlayHandle, OverlayManager};
+//! # use ovr_overlay::egui_overlay::EguiOverlay;
+//! # fn example(mngr: &mut OverlayManager, overlay: OverlayHandle) {
+//! let mut ui = EguiOverlay::new(mngr, overlay, 512, 512).unwrap();
+//! loop {
+//!     ui.poll_events(mngr);
+//!     let output = ui.run(|ctx| {
+//!         egui::CentralPanel::default().show(ctx, |ui| ui.label("Hello, VR"));
+//!     });
+//!     // ...draw `output` and submit it to the overlay...
+//! }
+//! # }
+//! ```
+
+use crate::errors::EVROverlayError;
+use crate::overlay::{OverlayHandle, OverlayManager};
+use crate::sys;
+use crate::system::VREvent;
+
+use byteorder::{ByteOrder, LittleEndian};
+use std::time::Instant;
+
+#[cfg(feature = "egui-wgpu")]
+pub mod renderer;
+
+pub struct EguiOverlay {
+    ctx: egui::Context,
+    overlay: OverlayHandle,
+    width: u32,
+    height: u32,
+    pixels_per_point: f32,
+    events: Vec<egui::Event>,
+    start: Instant,
+}
+
+impl EguiOverlay {
+    /// Sets up `overlay` to receive mouse and scroll events for a `width` by `height`
+    /// pixel UI.
+    pub fn new(
+        mngr: &mut OverlayManager,
+        overlay: OverlayHandle,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, EVROverlayError> {
+        mngr.set_input_method(
+            overlay,
+            sys::VROverlayInputMethod::VROverlayInputMethod_Mouse,
+        )?;
+        mngr.set_mouse_scale(overlay, width as f32, height as f32)?;
+        mngr.set_flag(
+            overlay,
+            sys::VROverlayFlags::VROverlayFlags_SendVRSmoothScrollEvents,
+            true,
+        )?;
+        Ok(Self {
+            ctx: egui::Context::default(),
+            overlay,
+            width,
+            height,
+            pixels_per_point: 1.0,
+            events: Vec::new(),
+            start: Instant::now(),
+        })
+    }
+
+    pub fn context(&self) -> &egui::Context {
+        &self.ctx
+    }
+
+    pub fn overlay(&self) -> OverlayHandle {
+        self.overlay
+    }
+
+    /// Size of the UI in pixels.
+    pub fn size(&self) -> [u32; 2] {
+        [self.width, self.height]
+    }
+
+    pub fn pixels_per_point(&self) -> f32 {
+        self.pixels_per_point
+    }
+
+    /// Scales the UI up, e.g. so text stays readable on a high resolution overlay.
+    pub fn set_pixels_per_point(&mut self, pixels_per_point: f32) {
+        self.pixels_per_point = pixels_per_point;
+    }
+
+    /// Handles every pending event of the overlay.
+    pub fn poll_events(&mut self, mngr: &mut OverlayManager) {
+        while let Some(event) = mngr.poll_next_overlay_event(self.overlay) {
+            self.handle_event(&event);
+        }
+    }
+
+    /// Queues `event` as egui input if it is one the UI cares about, returning whether
+    /// it was.
+    pub fn handle_event(&mut self, event: &VREvent) -> bool {
+        use sys::EVREventType::*;
+        let data = &event.data;
+        let event = match event.event_type {
+            VREvent_MouseMove => egui::Event::PointerMoved(self.mouse_pos(data)),
+            VREvent_MouseButtonDown | VREvent_MouseButtonUp => {
+                let Some(button) = mouse_button(LittleEndian::read_u32(&data[8..12])) else {
+                    return false;
+                };
+                egui::Event::PointerButton {
+                    pos: self.mouse_pos(data),
+                    button,
+                    pressed: event.event_type == VREvent_MouseButtonDown,
+                    modifiers: egui::Modifiers::NONE,
+                }
+            }
+            VREvent_ScrollSmooth | VREvent_ScrollDiscrete => {
+                // OpenVR reports scroll in notches; egui wants points.
+                const POINTS_PER_NOTCH: f32 = 50.0;
+                egui::Event::Scroll(egui::vec2(
+                    LittleEndian::read_f32(&data[0..4]) * POINTS_PER_NOTCH,
+                    LittleEndian::read_f32(&data[4..8]) * POINTS_PER_NOTCH,
+                ))
+            }
+            VREvent_OverlayFocusChanged | VREvent_FocusLeave => egui::Event::PointerGone,
+            VREvent_KeyboardCharInput => {
+                let input = &data[0..8];
+                let len = input.iter().position(|&b| b == 0).unwrap_or(input.len());
+                let text = String::from_utf8_lossy(&input[..len]);
+                match &*text {
+                    "\n" => key_press(egui::Key::Enter),
+                    "\x08" => key_press(egui::Key::Backspace),
+                    _ => egui::Event::Text(text.into_owned()),
+                }
+            }
+            _ => return false,
+        };
+        self.events.push(event);
+        true
+    }
+
+    /// Runs one frame of the UI with the input received since the last one.
+    pub fn run(&mut self, run_ui: impl FnOnce(&egui::Context)) -> egui::FullOutput {
+        let size = egui::vec2(self.width as f32, self.height as f32) / self.pixels_per_point;
+        let mut input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, size)),
+            time: Some(self.start.elapsed().as_secs_f64()),
+            events: std::mem::take(&mut self.events),
+            ..Default::default()
+        };
+        input
+            .viewports
+            .entry(egui::ViewportId::ROOT)
+            .or_default()
+            .native_pixels_per_point = Some(self.pixels_per_point);
+        self.ctx.run(input, run_ui)
+    }
+
+    /// OpenVR's mouse origin is the bottom left corner, egui's the top left.
+    fn mouse_pos(&self, data: &[u8]) -> egui::Pos2 {
+        let x = LittleEndian::read_f32(&data[0..4]);
+        let y = self.height as f32 - LittleEndian::read_f32(&data[4..8]);
+        egui::pos2(x, y) / self.pixels_per_point
+    }
+}
+
+fn mouse_button(button: u32) -> Option<egui::PointerButton> {
+    use sys::EVRMouseButton::*;
+    Some(match button {
+        b if b == VRMouseButton_Left as u32 => egui::PointerButton::Primary,
+        b if b == VRMouseButton_Right as u32 => egui::PointerButton::Secondary,
+        b if b == VRMouseButton_Middle as u32 => egui::PointerButton::Middle,
+        _ => return None,
+    })
+}
+
+fn key_press(key: egui::Key) -> egui::Event {
+    egui::Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: egui::Modifiers::NONE,
+    }
+}
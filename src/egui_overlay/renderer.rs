@@ -0,0 +1,115 @@
+//! Draws an [`EguiOverlay`] with `egui-wgpu` and submits it to the overlay.
+
+use super::EguiOverlay;
+use crate::overlay::OverlayManager;
+use crate::wgpu_interop::WgpuTextureError;
+
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8Unorm;
+
+pub struct EguiRenderer {
+    renderer: egui_wgpu::Renderer,
+    /// Alternated between, so a frame is never drawn into the texture the compositor
+    /// is reading.
+    targets: [wgpu::Texture; 2],
+    back: usize,
+    /// Copy destination used to move a target into the layout OpenVR reads from.
+    scratch: wgpu::Texture,
+}
+
+impl EguiRenderer {
+    pub fn new(device: &wgpu::Device, ui: &EguiOverlay) -> Self {
+        let [width, height] = ui.size();
+        let target = |usage| {
+            device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("egui overlay"),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: FORMAT,
+                usage,
+                view_formats: &[],
+            })
+        };
+        let usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_SRC;
+        Self {
+            renderer: egui_wgpu::Renderer::new(device, FORMAT, None, 1),
+            targets: [target(usage), target(usage)],
+            back: 0,
+            scratch: target(wgpu::TextureUsages::COPY_DST),
+        }
+    }
+
+    /// Draws `output` of `ui` and sets it as the contents of the overlay.
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        mngr: &mut OverlayManager,
+        ui: &EguiOverlay,
+        output: egui::FullOutput,
+    ) -> Result<(), WgpuTextureError> {
+        let screen = egui_wgpu::ScreenDescriptor {
+            size_in_pixels: ui.size(),
+            pixels_per_point: ui.pixels_per_point(),
+        };
+        for (id, delta) in &output.textures_delta.set {
+            self.renderer.update_texture(device, queue, *id, delta);
+        }
+        let jobs = ui
+            .context()
+            .tessellate(output.shapes, ui.pixels_per_point());
+
+        let target = &self.targets[self.back];
+        let view = target.create_view(&Default::default());
+        let mut encoder = device.create_command_encoder(&Default::default());
+        let buffers = self
+            .renderer
+            .update_buffers(device, queue, &mut encoder, &jobs, &screen);
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("egui overlay"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            self.renderer.render(&mut pass, &jobs, &screen);
+        }
+        // OpenVR expects Vulkan images in TRANSFER_SRC_OPTIMAL layout. wgpu has no way to
+        // request a layout, but leaves the source of a copy in that one.
+        encoder.copy_texture_to_texture(
+            target.as_image_copy(),
+            self.scratch.as_image_copy(),
+            wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit(buffers.into_iter().chain([encoder.finish()]));
+
+        for id in &output.textures_delta.free {
+            self.renderer.free_texture(id);
+        }
+
+        // Safety: the texture was created on `device`, and isn't drawn into again until
+        // the other one has been submitted.
+        unsafe { mngr.set_image_wgpu(ui.overlay(), device, target)? };
+        self.back = 1 - self.back;
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+//! Crate-level versions of the sys enums most applications use, with conversions.
+//!
+//! The generated sys enums carry OpenVR's C++ names and can change representation when
+//! the bindings are regenerated. These are stable, can be stored with the `serde`
+//! feature, and convert to and from the sys enums when calling the managers.
+
+use crate::sys;
+
+/// A sys enum value without a crate-level counterpart, like
+/// `TrackedControllerRole_Invalid`. Holds the raw value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("no matching variant for raw enum value {0}")]
+pub struct InvalidEnumValue(pub u32);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Eye {
+    Left,
+    Right,
+}
+impl From<Eye> for sys::EVREye {
+    fn from(eye: Eye) -> Self {
+        match eye {
+            Eye::Left => sys::EVREye::Eye_Left,
+            Eye::Right => sys::EVREye::Eye_Right,
+        }
+    }
+}
+impl From<sys::EVREye> for Eye {
+    fn from(eye: sys::EVREye) -> Self {
+        match eye {
+            sys::EVREye::Eye_Left => Eye::Left,
+            sys::EVREye::Eye_Right => Eye::Right,
+        }
+    }
+}
+
+/// The tracking universe poses are relative to, see
+/// [`TrackingUniverseOrigin`](crate::pose::TrackingUniverseOrigin).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Universe {
+    /// Relative to the seated zero pose.
+    Seated,
+    /// Relative to the center of the play area, on the floor.
+    Standing,
+    /// Relative to the tracking system's own origin, without calibration.
+    RawAndUncalibrated,
+}
+impl From<Universe> for sys::ETrackingUniverseOrigin {
+    fn from(universe: Universe) -> Self {
+        use sys::ETrackingUniverseOrigin::*;
+        match universe {
+            Universe::Seated => TrackingUniverseSeated,
+            Universe::Standing => TrackingUniverseStanding,
+            Universe::RawAndUncalibrated => TrackingUniverseRawAndUncalibrated,
+        }
+    }
+}
+impl From<sys::ETrackingUniverseOrigin> for Universe {
+    fn from(origin: sys::ETrackingUniverseOrigin) -> Self {
+        use sys::ETrackingUniverseOrigin::*;
+        match origin {
+            TrackingUniverseSeated => Universe::Seated,
+            TrackingUniverseStanding => Universe::Standing,
+            TrackingUniverseRawAndUncalibrated => Universe::RawAndUncalibrated,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControllerRole {
+    LeftHand,
+    RightHand,
+    /// Controllers that don't want to be assigned a hand.
+    OptOut,
+    Treadmill,
+    Stylus,
+}
+impl From<ControllerRole> for sys::ETrackedControllerRole {
+    fn from(role: ControllerRole) -> Self {
+        use sys::ETrackedControllerRole::*;
+        match role {
+            ControllerRole::LeftHand => TrackedControllerRole_LeftHand,
+            ControllerRole::RightHand => TrackedControllerRole_RightHand,
+            ControllerRole::OptOut => TrackedControllerRole_OptOut,
+            ControllerRole::Treadmill => TrackedControllerRole_Treadmill,
+            ControllerRole::Stylus => TrackedControllerRole_Stylus,
+        }
+    }
+}
+/// Fails for `TrackedControllerRole_Invalid`, which devices without a role have.
+impl TryFrom<sys::ETrackedControllerRole> for ControllerRole {
+    type Error = InvalidEnumValue;
+
+    fn try_from(role: sys::ETrackedControllerRole) -> Result<Self, Self::Error> {
+        use sys::ETrackedControllerRole::*;
+        Ok(match role {
+            TrackedControllerRole_LeftHand => ControllerRole::LeftHand,
+            TrackedControllerRole_RightHand => ControllerRole::RightHand,
+            TrackedControllerRole_OptOut => ControllerRole::OptOut,
+            TrackedControllerRole_Treadmill => ControllerRole::Treadmill,
+            TrackedControllerRole_Stylus => ControllerRole::Stylus,
+            role => return Err(InvalidEnumValue(role as u32)),
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeviceClass {
+    Hmd,
+    Controller,
+    /// Trackers and other devices that are tracked, but not held.
+    GenericTracker,
+    /// Base stations and cameras.
+    TrackingReference,
+    /// Accessories that redirect the display, without being tracked.
+    DisplayRedirect,
+}
+impl From<DeviceClass> for sys::ETrackedDeviceClass {
+    fn from(class: DeviceClass) -> Self {
+        use sys::ETrackedDeviceClass::*;
+        match class {
+            DeviceClass::Hmd => TrackedDeviceClass_HMD,
+            DeviceClass::Controller => TrackedDeviceClass_Controller,
+            DeviceClass::GenericTracker => TrackedDeviceClass_GenericTracker,
+            DeviceClass::TrackingReference => TrackedDeviceClass_TrackingReference,
+            DeviceClass::DisplayRedirect => TrackedDeviceClass_DisplayRedirect,
+        }
+    }
+}
+/// Fails for `TrackedDeviceClass_Invalid`, the class of device indices without a
+/// device.
+impl TryFrom<sys::ETrackedDeviceClass> for DeviceClass {
+    type Error = InvalidEnumValue;
+
+    fn try_from(class: sys::ETrackedDeviceClass) -> Result<Self, Self::Error> {
+        use sys::ETrackedDeviceClass::*;
+        Ok(match class {
+            TrackedDeviceClass_HMD => DeviceClass::Hmd,
+            TrackedDeviceClass_Controller => DeviceClass::Controller,
+            TrackedDeviceClass_GenericTracker => DeviceClass::GenericTracker,
+            TrackedDeviceClass_TrackingReference => DeviceClass::TrackingReference,
+            TrackedDeviceClass_DisplayRedirect => DeviceClass::DisplayRedirect,
+            class => return Err(InvalidEnumValue(class as u32)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for role in [
+            ControllerRole::LeftHand,
+            ControllerRole::RightHand,
+            ControllerRole::OptOut,
+            ControllerRole::Treadmill,
+            ControllerRole::Stylus,
+        ] {
+            assert_eq!(
+                ControllerRole::try_from(sys::ETrackedControllerRole::from(role)),
+                Ok(role)
+            );
+        }
+        assert!(ControllerRole::try_from(
+            sys::ETrackedControllerRole::TrackedControllerRole_Invalid
+        )
+        .is_err());
+        assert_eq!(
+            DeviceClass::try_from(sys::ETrackedDeviceClass::from(DeviceClass::GenericTracker)),
+            Ok(DeviceClass::GenericTracker)
+        );
+        assert_eq!(
+            DeviceClass::try_from(sys::ETrackedDeviceClass::TrackedDeviceClass_Invalid),
+            Err(InvalidEnumValue(0))
+        );
+        assert_eq!(
+            Universe::from(sys::ETrackingUniverseOrigin::from(Universe::Standing)),
+            Universe::Standing
+        );
+        assert_eq!(Eye::from(sys::EVREye::from(Eye::Right)), Eye::Right);
+    }
+}
@@ -1,46 +1,171 @@
 use crate::sys;
 
-use derive_more::{From, Into};
 use std::ffi::CStr;
 use std::fmt::Display;
 
+/// Where in this crate an error was returned from OpenVR. Only recorded with the
+/// `context-errors` feature, and ignored when comparing errors.
+#[derive(Clone, Copy)]
+struct Location(#[cfg(feature = "context-errors")] &'static std::panic::Location<'static>);
+impl Location {
+    #[cfg(feature = "context-errors")]
+    #[track_caller]
+    fn caller() -> Self {
+        Self(std::panic::Location::caller())
+    }
+
+    #[cfg(not(feature = "context-errors"))]
+    fn caller() -> Self {
+        Self()
+    }
+
+    #[cfg(feature = "context-errors")]
+    fn get(&self) -> Option<&'static std::panic::Location<'static>> {
+        Some(self.0)
+    }
+
+    #[cfg(not(feature = "context-errors"))]
+    fn get(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+}
+impl PartialEq for Location {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+impl Eq for Location {}
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.get() {
+            Some(location) => write!(f, " (at {location})"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Implements `Debug` (showing the variant name, from `description()` unless another
+/// method is given), `std::error::Error`, conversion back into the sys enum and
+/// `location()` for an error wrapper with a `description()` and a `Display` impl.
+macro_rules! impl_error {
+    ($feature:literal, $ty:ident) => {
+        #[cfg(feature = $feature)]
+        impl_error!($ty);
+    };
+    ($ty:ident) => {
+        impl_error!($ty, description);
+    };
+    ($ty:ident, $name:ident) => {
+        impl $ty {
+            /// The source location in this crate that returned the error, which
+            /// identifies the OpenVR call that failed. Always `None` unless the
+            /// `context-errors` feature is enabled.
+            pub fn location(&self) -> Option<&'static std::panic::Location<'static>> {
+                self.1.get()
+            }
+        }
+
+        impl From<$ty> for sys::$ty {
+            fn from(err: $ty) -> Self {
+                err.0
+            }
+        }
+
+        impl std::fmt::Debug for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($ty))
+                    .field(&format_args!("{}", self.$name()))
+                    .finish()
+            }
+        }
+
+        impl std::error::Error for $ty {}
+    };
+}
+
+impl_error!(EVRInitError, symbol);
+impl_error!(EVROverlayError);
+impl_error!("ovr_system", ETrackedPropertyError);
+impl_error!("ovr_input", EVRInputError);
+impl_error!("ovr_compositor", EVRCompositorError);
+impl_error!("ovr_applications", EVRApplicationError);
+impl_error!("ovr_iobuffer", EIOBufferError);
+impl_error!("ovr_notifications", EVRNotificationError);
+impl_error!("ovr_render_models", EVRRenderModelError);
+impl_error!("ovr_screenshots", EVRScreenshotError);
+impl_error!("ovr_tracked_camera", EVRTrackedCameraError);
+impl_error!("ovr_debug", EVRDebugError);
+impl_error!("ovr_settings", EVRSettingsError);
+
 #[derive(Clone, PartialEq, Eq)]
-pub struct EVRInitError(sys::EVRInitError);
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVRInitError(sys::EVRInitError, Location);
 impl EVRInitError {
+    #[track_caller]
     pub fn new(err: sys::EVRInitError) -> Result<(), Self> {
         if err == sys::EVRInitError::VRInitError_None {
             Ok(())
         } else {
-            Err(Self(err))
+            Err(Self(err, Location::caller()))
         }
     }
 
+    /// A human readable description of the error, suitable for end-user logs.
     pub fn description(&self) -> &'static str {
+        let desc: &'static CStr =
+            unsafe { CStr::from_ptr(sys::VR_GetVRInitErrorAsEnglishDescription(self.0.clone())) };
+        desc.to_str().unwrap_or("Unknown error")
+    }
+
+    /// The name of the error variant, e.g. `VRInitError_Init_HmdNotFound`.
+    pub fn symbol(&self) -> &'static str {
         let desc: &'static CStr =
             unsafe { CStr::from_ptr(sys::VR_GetVRInitErrorAsSymbol(self.0.clone())) };
-        desc.to_str().unwrap()
+        desc.to_str().unwrap_or("VRInitError_Unknown")
     }
 
     pub fn inner(&self) -> sys::EVRInitError {
         self.0.clone()
     }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
+
+    /// Returns true if this error means that SteamVR or the HMD isn't running yet,
+    /// so initializing again later may succeed.
+    pub fn is_not_yet_available(&self) -> bool {
+        use sys::EVRInitError::*;
+        matches!(
+            self.0,
+            VRInitError_Init_NoServerForBackgroundApp
+                | VRInitError_Init_HmdNotFound
+                | VRInitError_Init_HmdNotFoundPresenceFailed
+                | VRInitError_IPC_ServerInitFailed
+                | VRInitError_IPC_ConnectFailed
+        )
+    }
 }
 impl Display for EVRInitError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let num = self.0.clone() as u8;
+        let num = self.code();
+        let symbol = self.symbol();
         let desc = self.description();
-        write!(f, "EVRInitError({num}): {desc}")
+        write!(f, "EVRInitError({num}): {symbol}: {desc}{}", self.1)
     }
 }
 
 #[derive(Clone, PartialEq, Eq)]
-pub struct EVROverlayError(sys::EVROverlayError);
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVROverlayError(sys::EVROverlayError, Location);
 impl EVROverlayError {
+    #[track_caller]
     pub fn new(err: sys::EVROverlayError) -> Result<(), Self> {
         if err == sys::EVROverlayError::VROverlayError_None {
             Ok(())
         } else {
-            Err(Self(err))
+            Err(Self(err, Location::caller()))
         }
     }
 
@@ -78,27 +203,33 @@ impl EVROverlayError {
     pub fn inner(&self) -> sys::EVROverlayError {
         self.0.clone()
     }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
 }
 impl Display for EVROverlayError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let num = self.0.clone() as u8;
+        let num = self.code();
         let desc = self.description();
-        write!(f, "EVROverlayError({num}): {desc}")
+        write!(f, "EVROverlayError({num}): {desc}{}", self.1)
     }
 }
 
 #[cfg(feature = "ovr_system")]
-#[derive(Into, Clone, PartialEq, Eq)]
-#[repr(transparent)]
-pub struct ETrackedPropertyError(sys::ETrackedPropertyError);
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct ETrackedPropertyError(sys::ETrackedPropertyError, Location);
 
 #[cfg(feature = "ovr_system")]
 impl ETrackedPropertyError {
+    #[track_caller]
     pub fn new(err: sys::ETrackedPropertyError) -> Result<(), Self> {
         if err == sys::ETrackedPropertyError::TrackedProp_Success {
             Ok(())
         } else {
-            Err(Self(err))
+            Err(Self(err, Location::caller()))
         }
     }
 
@@ -127,28 +258,34 @@ impl ETrackedPropertyError {
     pub fn inner(&self) -> sys::ETrackedPropertyError {
         self.0.clone()
     }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
 }
 
 #[cfg(feature = "ovr_system")]
 impl Display for ETrackedPropertyError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let num = self.0.clone() as u8;
+        let num = self.code();
         let desc = self.description();
-        write!(f, "ETrackedPropertyError({num}): {desc}")
+        write!(f, "ETrackedPropertyError({num}): {desc}{}", self.1)
     }
 }
 #[cfg(feature = "ovr_input")]
-#[derive(From, Into, Clone, PartialEq, Eq)]
-#[repr(transparent)]
-pub struct EVRInputError(sys::EVRInputError);
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVRInputError(sys::EVRInputError, Location);
 
 #[cfg(feature = "ovr_input")]
 impl EVRInputError {
+    #[track_caller]
     pub fn new(err: sys::EVRInputError) -> Result<(), Self> {
         if err == sys::EVRInputError::VRInputError_None {
             Ok(())
         } else {
-            Err(Self(err))
+            Err(Self(err, Location::caller()))
         }
     }
 
@@ -182,29 +319,43 @@ impl EVRInputError {
     pub fn inner(&self) -> sys::EVRInputError {
         self.0.clone()
     }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
+}
+
+#[cfg(feature = "ovr_input")]
+impl From<sys::EVRInputError> for EVRInputError {
+    #[track_caller]
+    fn from(err: sys::EVRInputError) -> Self {
+        Self(err, Location::caller())
+    }
 }
 
 #[cfg(feature = "ovr_input")]
 impl Display for EVRInputError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let num = self.0.clone() as u8;
+        let num = self.code();
         let desc = self.description();
-        write!(f, "EVRInputError({num}): {desc}")
+        write!(f, "EVRInputError({num}): {desc}{}", self.1)
     }
 }
 
 #[cfg(feature = "ovr_compositor")]
-#[derive(Into, Clone, PartialEq, Eq)]
-#[repr(transparent)]
-pub struct EVRCompositorError(sys::EVRCompositorError);
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVRCompositorError(sys::EVRCompositorError, Location);
 
 #[cfg(feature = "ovr_compositor")]
 impl EVRCompositorError {
+    #[track_caller]
     pub fn new(err: sys::EVRCompositorError) -> Result<(), Self> {
         if err == sys::EVRCompositorError::VRCompositorError_None {
             Ok(())
         } else {
-            Err(Self(err))
+            Err(Self(err, Location::caller()))
         }
     }
 
@@ -230,20 +381,35 @@ impl EVRCompositorError {
     pub fn inner(&self) -> sys::EVRCompositorError {
         self.0.clone()
     }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
+}
+
+#[cfg(feature = "ovr_compositor")]
+impl Display for EVRCompositorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let num = self.code();
+        let desc = self.description();
+        write!(f, "EVRCompositorError({num}): {desc}{}", self.1)
+    }
 }
 
 #[cfg(feature = "ovr_applications")]
-#[derive(Into, Clone, PartialEq, Eq)]
-#[repr(transparent)]
-pub struct EVRApplicationError(sys::EVRApplicationError);
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVRApplicationError(sys::EVRApplicationError, Location);
 
 #[cfg(feature = "ovr_applications")]
 impl EVRApplicationError {
+    #[track_caller]
     pub fn new(err: sys::EVRApplicationError) -> Result<(), Self> {
         if err == sys::EVRApplicationError::VRApplicationError_None {
             Ok(())
         } else {
-            Err(Self(err))
+            Err(Self(err, Location::caller()))
         }
     }
 
@@ -278,19 +444,461 @@ impl EVRApplicationError {
     pub fn inner(&self) -> sys::EVRApplicationError {
         self.0.clone()
     }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
 }
 
 #[cfg(feature = "ovr_applications")]
 impl Display for EVRApplicationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let num = self.0.clone() as u8;
+        let num = self.code();
+        let desc = self.description();
+        write!(f, "EVRApplicationError({num}): {desc}{}", self.1)
+    }
+}
+
+#[cfg(feature = "ovr_iobuffer")]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EIOBufferError(sys::EIOBufferError, Location);
+
+#[cfg(feature = "ovr_iobuffer")]
+impl EIOBufferError {
+    #[track_caller]
+    pub fn new(err: sys::EIOBufferError) -> Result<(), Self> {
+        if err == sys::EIOBufferError::IOBuffer_Success {
+            Ok(())
+        } else {
+            Err(Self(err, Location::caller()))
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        use sys::EIOBufferError::*;
+        match self.0 {
+            IOBuffer_Success => "Success",
+            IOBuffer_OperationFailed => "OperationFailed",
+            IOBuffer_InvalidHandle => "InvalidHandle",
+            IOBuffer_InvalidArgument => "InvalidArgument",
+            IOBuffer_PathExists => "PathExists",
+            IOBuffer_PathDoesNotExist => "PathDoesNotExist",
+            IOBuffer_Permission => "Permission",
+        }
+    }
+
+    pub fn inner(&self) -> sys::EIOBufferError {
+        self.0.clone()
+    }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
+}
+
+#[cfg(feature = "ovr_iobuffer")]
+impl Display for EIOBufferError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let num = self.code();
+        let desc = self.description();
+        write!(f, "EIOBufferError({num}): {desc}{}", self.1)
+    }
+}
+
+#[cfg(feature = "ovr_notifications")]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVRNotificationError(sys::EVRNotificationError, Location);
+
+#[cfg(feature = "ovr_notifications")]
+impl EVRNotificationError {
+    #[track_caller]
+    pub fn new(err: sys::EVRNotificationError) -> Result<(), Self> {
+        if err == sys::EVRNotificationError::VRNotificationError_OK {
+            Ok(())
+        } else {
+            Err(Self(err, Location::caller()))
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        use sys::EVRNotificationError::*;
+        match self.0 {
+            VRNotificationError_OK => "OK",
+            VRNotificationError_InvalidNotificationId => "InvalidNotificationId",
+            VRNotificationError_NotificationQueueFull => "NotificationQueueFull",
+            VRNotificationError_InvalidOverlayHandle => "InvalidOverlayHandle",
+            VRNotificationError_SystemWithUserValueAlreadyExists => {
+                "SystemWithUserValueAlreadyExists"
+            }
+            VRNotificationError_ServiceUnavailable => "ServiceUnavailable",
+        }
+    }
+
+    pub fn inner(&self) -> sys::EVRNotificationError {
+        self.0.clone()
+    }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
+}
+
+#[cfg(feature = "ovr_notifications")]
+impl Display for EVRNotificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let num = self.code();
+        let desc = self.description();
+        write!(f, "EVRNotificationError({num}): {desc}{}", self.1)
+    }
+}
+
+#[cfg(feature = "ovr_render_models")]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVRRenderModelError(sys::EVRRenderModelError, Location);
+
+#[cfg(feature = "ovr_render_models")]
+impl EVRRenderModelError {
+    #[track_caller]
+    pub fn new(err: sys::EVRRenderModelError) -> Result<(), Self> {
+        if err == sys::EVRRenderModelError::VRRenderModelError_None {
+            Ok(())
+        } else {
+            Err(Self(err, Location::caller()))
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        use sys::EVRRenderModelError::*;
+        match self.0 {
+            VRRenderModelError_None => "None",
+            VRRenderModelError_Loading => "Loading",
+            VRRenderModelError_NotSupported => "NotSupported",
+            VRRenderModelError_InvalidArg => "InvalidArg",
+            VRRenderModelError_InvalidModel => "InvalidModel",
+            VRRenderModelError_NoShapes => "NoShapes",
+            VRRenderModelError_MultipleShapes => "MultipleShapes",
+            VRRenderModelError_TooManyVertices => "TooManyVertices",
+            VRRenderModelError_MultipleTextures => "MultipleTextures",
+            VRRenderModelError_BufferTooSmall => "BufferTooSmall",
+            VRRenderModelError_NotEnoughNormals => "NotEnoughNormals",
+            VRRenderModelError_NotEnoughTexCoords => "NotEnoughTexCoords",
+            VRRenderModelError_InvalidTexture => "InvalidTexture",
+        }
+    }
+
+    pub fn inner(&self) -> sys::EVRRenderModelError {
+        self.0.clone()
+    }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
+}
+
+#[cfg(feature = "ovr_render_models")]
+impl Display for EVRRenderModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let num = self.code();
+        let desc = self.description();
+        write!(f, "EVRRenderModelError({num}): {desc}{}", self.1)
+    }
+}
+
+#[cfg(feature = "ovr_screenshots")]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVRScreenshotError(sys::EVRScreenshotError, Location);
+
+#[cfg(feature = "ovr_screenshots")]
+impl EVRScreenshotError {
+    #[track_caller]
+    pub fn new(err: sys::EVRScreenshotError) -> Result<(), Self> {
+        if err == sys::EVRScreenshotError::VRScreenshotError_None {
+            Ok(())
+        } else {
+            Err(Self(err, Location::caller()))
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        use sys::EVRScreenshotError::*;
+        match self.0 {
+            VRScreenshotError_None => "None",
+            VRScreenshotError_RequestFailed => "RequestFailed",
+            VRScreenshotError_IncompatibleVersion => "IncompatibleVersion",
+            VRScreenshotError_NotFound => "NotFound",
+            VRScreenshotError_BufferTooSmall => "BufferTooSmall",
+            VRScreenshotError_ScreenshotAlreadyInProgress => "ScreenshotAlreadyInProgress",
+        }
+    }
+
+    pub fn inner(&self) -> sys::EVRScreenshotError {
+        self.0.clone()
+    }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
+}
+
+#[cfg(feature = "ovr_screenshots")]
+impl Display for EVRScreenshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let num = self.code();
+        let desc = self.description();
+        write!(f, "EVRScreenshotError({num}): {desc}{}", self.1)
+    }
+}
+
+#[cfg(feature = "ovr_tracked_camera")]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVRTrackedCameraError(sys::EVRTrackedCameraError, Location);
+
+#[cfg(feature = "ovr_tracked_camera")]
+impl EVRTrackedCameraError {
+    #[track_caller]
+    pub fn new(err: sys::EVRTrackedCameraError) -> Result<(), Self> {
+        if err == sys::EVRTrackedCameraError::VRTrackedCameraError_None {
+            Ok(())
+        } else {
+            Err(Self(err, Location::caller()))
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        use sys::EVRTrackedCameraError::*;
+        match self.0 {
+            VRTrackedCameraError_None => "None",
+            VRTrackedCameraError_OperationFailed => "OperationFailed",
+            VRTrackedCameraError_InvalidHandle => "InvalidHandle",
+            VRTrackedCameraError_InvalidFrameHeaderVersion => "InvalidFrameHeaderVersion",
+            VRTrackedCameraError_OutOfHandles => "OutOfHandles",
+            VRTrackedCameraError_IPCFailure => "IPCFailure",
+            VRTrackedCameraError_NotSupportedForThisDevice => "NotSupportedForThisDevice",
+            VRTrackedCameraError_SharedMemoryFailure => "SharedMemoryFailure",
+            VRTrackedCameraError_FrameBufferingFailure => "FrameBufferingFailure",
+            VRTrackedCameraError_StreamSetupFailure => "StreamSetupFailure",
+            VRTrackedCameraError_InvalidGLTextureId => "InvalidGLTextureId",
+            VRTrackedCameraError_InvalidSharedTextureHandle => "InvalidSharedTextureHandle",
+            VRTrackedCameraError_FailedToGetGLTextureId => "FailedToGetGLTextureId",
+            VRTrackedCameraError_SharedTextureFailure => "SharedTextureFailure",
+            VRTrackedCameraError_NoFrameAvailable => "NoFrameAvailable",
+            VRTrackedCameraError_InvalidArgument => "InvalidArgument",
+            VRTrackedCameraError_InvalidFrameBufferSize => "InvalidFrameBufferSize",
+        }
+    }
+
+    pub fn inner(&self) -> sys::EVRTrackedCameraError {
+        self.0.clone()
+    }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
+}
+
+#[cfg(feature = "ovr_tracked_camera")]
+impl Display for EVRTrackedCameraError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let num = self.code();
+        let desc = self.description();
+        write!(f, "EVRTrackedCameraError({num}): {desc}{}", self.1)
+    }
+}
+
+#[cfg(feature = "ovr_debug")]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVRDebugError(sys::EVRDebugError, Location);
+
+#[cfg(feature = "ovr_debug")]
+impl EVRDebugError {
+    #[track_caller]
+    pub fn new(err: sys::EVRDebugError) -> Result<(), Self> {
+        if err == sys::EVRDebugError::VRDebugError_Success {
+            Ok(())
+        } else {
+            Err(Self(err, Location::caller()))
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        use sys::EVRDebugError::*;
+        match self.0 {
+            VRDebugError_Success => "Success",
+            VRDebugError_BadParameter => "BadParameter",
+        }
+    }
+
+    pub fn inner(&self) -> sys::EVRDebugError {
+        self.0.clone()
+    }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
+}
+
+#[cfg(feature = "ovr_debug")]
+impl Display for EVRDebugError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let num = self.code();
         let desc = self.description();
-        write!(f, "EVRApplicationError({num}): {desc}")
+        write!(f, "EVRDebugError({num}): {desc}{}", self.1)
     }
 }
 
-#[derive(From)]
+#[cfg(feature = "ovr_settings")]
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(not(feature = "context-errors"), repr(transparent))]
+pub struct EVRSettingsError(sys::EVRSettingsError, Location);
+
+#[cfg(feature = "ovr_settings")]
+impl EVRSettingsError {
+    #[track_caller]
+    pub fn new(err: sys::EVRSettingsError) -> Result<(), Self> {
+        if err == sys::EVRSettingsError::VRSettingsError_None {
+            Ok(())
+        } else {
+            Err(Self(err, Location::caller()))
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        use sys::EVRSettingsError::*;
+        match self.0 {
+            VRSettingsError_None => "None",
+            VRSettingsError_IPCFailed => "IPCFailed",
+            VRSettingsError_WriteFailed => "WriteFailed",
+            VRSettingsError_ReadFailed => "ReadFailed",
+            VRSettingsError_JsonParseFailed => "JsonParseFailed",
+            VRSettingsError_UnsetSettingHasNoDefault => "UnsetSettingHasNoDefault",
+        }
+    }
+
+    pub fn inner(&self) -> sys::EVRSettingsError {
+        self.0.clone()
+    }
+
+    /// The raw value of the error code, as defined by OpenVR.
+    pub fn code(&self) -> u32 {
+        self.0.clone() as u32
+    }
+}
+
+#[cfg(feature = "ovr_settings")]
+impl Display for EVRSettingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let num = self.code();
+        let desc = self.description();
+        write!(f, "EVRSettingsError({num}): {desc}{}", self.1)
+    }
+}
+
+// Errors other than the OpenVR error codes above derive `thiserror::Error`, here and in
+// the rest of the crate.
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum InitError {
+    #[error("OpenVR is already initialized")]
     AlreadyInitialized,
-    Sys(EVRInitError),
+    /// Gave up waiting for SteamVR to become available.
+    #[error("timed out waiting for SteamVR")]
+    TimedOut,
+    #[error("{0}")]
+    Sys(#[from] EVRInitError),
+}
+
+/// Any error returned by this crate, for applications that use several interfaces
+/// and want a single error type.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    #[error("{0}")]
+    Init(#[from] InitError),
+    #[error("{0}")]
+    Overlay(#[from] EVROverlayError),
+    #[cfg(feature = "ovr_system")]
+    #[error("{0}")]
+    TrackedProperty(#[from] ETrackedPropertyError),
+    #[cfg(feature = "ovr_input")]
+    #[error("{0}")]
+    Input(#[from] EVRInputError),
+    #[cfg(feature = "ovr_compositor")]
+    #[error("{0}")]
+    Compositor(#[from] EVRCompositorError),
+    #[cfg(feature = "ovr_applications")]
+    #[error("{0}")]
+    Application(#[from] EVRApplicationError),
+    #[cfg(feature = "ovr_settings")]
+    #[error("{0}")]
+    Settings(#[from] EVRSettingsError),
+    #[cfg(feature = "ovr_settings")]
+    #[error("{0}")]
+    TrackerRole(#[from] crate::settings::trackers::TrackerRoleError),
+    #[cfg(feature = "ovr_iobuffer")]
+    #[error("{0}")]
+    IoBuffer(#[from] EIOBufferError),
+    #[cfg(feature = "ovr_notifications")]
+    #[error("{0}")]
+    Notification(#[from] EVRNotificationError),
+    #[cfg(feature = "ovr_render_models")]
+    #[error("{0}")]
+    RenderModel(#[from] EVRRenderModelError),
+    #[cfg(feature = "ovr_screenshots")]
+    #[error("{0}")]
+    Screenshot(#[from] EVRScreenshotError),
+    #[cfg(feature = "ovr_screenshots")]
+    #[error("{0}")]
+    ScreenshotPath(#[from] crate::screenshots::ScreenshotPathError),
+    #[cfg(feature = "ovr_tracked_camera")]
+    #[error("{0}")]
+    TrackedCamera(#[from] EVRTrackedCameraError),
+    #[cfg(feature = "ovr_debug")]
+    #[error("{0}")]
+    Debug(#[from] EVRDebugError),
+}
+impl From<EVRInitError> for Error {
+    fn from(err: EVRInitError) -> Self {
+        Self::Init(err.into())
+    }
+}
+
+/// A `Result` defaulting to this crate's [`Error`].
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "context-errors")]
+    fn location_is_recorded() {
+        let line = line!() + 1;
+        let err = EVROverlayError::new(sys::EVROverlayError::VROverlayError_InvalidHandle);
+        let err = err.unwrap_err();
+        let location = err.location().unwrap();
+        assert_eq!(location.file(), file!());
+        assert_eq!(location.line(), line);
+        assert!(err.to_string().ends_with(&format!("(at {location})")));
+    }
+
+    #[test]
+    fn location_is_ignored_by_eq() {
+        let a = EVROverlayError::new(sys::EVROverlayError::VROverlayError_InvalidHandle);
+        let b = EVROverlayError::new(sys::EVROverlayError::VROverlayError_InvalidHandle);
+        assert_eq!(a.unwrap_err(), b.unwrap_err());
+    }
 }
@@ -0,0 +1,91 @@
+//! Support for HMDs running in extended mode, where the HMD shows up as a
+//! monitor of the desktop.
+
+use crate::{sys, Context, Interface, InterfaceLock};
+
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+pub struct ExtendedDisplayManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVRExtendedDisplay>,
+    _lock: InterfaceLock<'c>,
+}
+
+/// Position and size of the window to draw the HMD's contents into, in desktop coordinates.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Region of the window that an eye is displayed in, in pixels.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Viewport {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Identifies the DXGI output that the HMD is connected to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct DxgiOutputInfo {
+    pub adapter_index: i32,
+    pub adapter_output_index: i32,
+}
+
+impl<'c> ExtendedDisplayManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::ExtendedDisplay) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+        }
+    }
+
+    /// Wraps c++ `GetWindowBounds`.
+    pub fn get_window_bounds(&mut self) -> WindowBounds {
+        let mut bounds = WindowBounds::default();
+        unsafe {
+            self.inner.as_mut().GetWindowBounds(
+                &mut bounds.x,
+                &mut bounds.y,
+                &mut bounds.width,
+                &mut bounds.height,
+            )
+        };
+        bounds
+    }
+
+    /// Wraps c++ `GetEyeOutputViewport`.
+    pub fn get_eye_output_viewport(&mut self, eye: sys::EVREye) -> Viewport {
+        let mut viewport = Viewport::default();
+        unsafe {
+            self.inner.as_mut().GetEyeOutputViewport(
+                eye,
+                &mut viewport.x,
+                &mut viewport.y,
+                &mut viewport.width,
+                &mut viewport.height,
+            )
+        };
+        viewport
+    }
+
+    /// Only meaningful on Windows.
+    ///
+    /// Wraps c++ `GetDXGIOutputInfo`.
+    pub fn get_dxgi_output_info(&mut self) -> DxgiOutputInfo {
+        let mut info = DxgiOutputInfo::default();
+        unsafe {
+            self.inner
+                .as_mut()
+                .GetDXGIOutputInfo(&mut info.adapter_index, &mut info.adapter_output_index)
+        };
+        info
+    }
+}
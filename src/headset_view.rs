@@ -0,0 +1,121 @@
+//! Control over the "VR View" desktop window that mirrors the headset.
+
+use crate::{sys, Context, Interface, InterfaceLock};
+
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+pub struct HeadsetViewManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVRHeadsetView>,
+    _lock: InterfaceLock<'c>,
+}
+
+/// Which eyes the headset view shows.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum HeadsetViewMode {
+    Left,
+    Right,
+    Both,
+}
+impl From<HeadsetViewMode> for sys::HeadsetViewMode_t {
+    fn from(mode: HeadsetViewMode) -> Self {
+        use sys::HeadsetViewMode_t::*;
+        match mode {
+            HeadsetViewMode::Left => HeadsetViewMode_Left,
+            HeadsetViewMode::Right => HeadsetViewMode_Right,
+            HeadsetViewMode::Both => HeadsetViewMode_Both,
+        }
+    }
+}
+impl From<sys::HeadsetViewMode_t> for HeadsetViewMode {
+    fn from(mode: sys::HeadsetViewMode_t) -> Self {
+        use sys::HeadsetViewMode_t::*;
+        match mode {
+            HeadsetViewMode_Left => Self::Left,
+            HeadsetViewMode_Right => Self::Right,
+            HeadsetViewMode_Both => Self::Both,
+        }
+    }
+}
+
+impl<'c> HeadsetViewManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::HeadsetView) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+        }
+    }
+
+    /// Sets the size of the headset view window, in pixels.
+    ///
+    /// Wraps c++ `SetHeadsetViewSize`.
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        unsafe { self.inner.as_mut().SetHeadsetViewSize(width, height) }
+    }
+
+    /// Returns the size of the headset view window as `(width, height)`, in pixels.
+    ///
+    /// Wraps c++ `GetHeadsetViewSize`.
+    pub fn size(&mut self) -> (u32, u32) {
+        let mut width = 0;
+        let mut height = 0;
+        unsafe {
+            self.inner
+                .as_mut()
+                .GetHeadsetViewSize(&mut width, &mut height)
+        };
+        (width, height)
+    }
+
+    /// Returns the aspect ratio of the headset view, expressed as width / height.
+    ///
+    /// Wraps c++ `GetHeadsetViewAspectRatio`.
+    pub fn aspect_ratio(&mut self) -> f32 {
+        unsafe { self.inner.as_mut().GetHeadsetViewAspectRatio() }
+    }
+
+    /// Wraps c++ `SetHeadsetViewMode`.
+    pub fn set_mode(&mut self, mode: HeadsetViewMode) {
+        unsafe { self.inner.as_mut().SetHeadsetViewMode(mode.into()) }
+    }
+
+    /// Wraps c++ `GetHeadsetViewMode`.
+    pub fn mode(&mut self) -> HeadsetViewMode {
+        unsafe { self.inner.as_mut().GetHeadsetViewMode() }.into()
+    }
+
+    /// Wraps c++ `SetHeadsetViewCropped`.
+    pub fn set_cropped(&mut self, cropped: bool) {
+        unsafe { self.inner.as_mut().SetHeadsetViewCropped(cropped) }
+    }
+
+    /// Wraps c++ `GetHeadsetViewCropped`.
+    pub fn cropped(&mut self) -> bool {
+        unsafe { self.inner.as_mut().GetHeadsetViewCropped() }
+    }
+
+    /// Sets the range over which the two eyes are blended together in
+    /// [`HeadsetViewMode::Both`], as fractions of the view width.
+    ///
+    /// Wraps c++ `SetHeadsetViewBlendRange`.
+    pub fn set_blend_range(&mut self, start: f32, end: f32) {
+        unsafe { self.inner.as_mut().SetHeadsetViewBlendRange(start, end) }
+    }
+
+    /// Returns the blend range as `(start, end)`.
+    ///
+    /// Wraps c++ `GetHeadsetViewBlendRange`.
+    pub fn blend_range(&mut self) -> (f32, f32) {
+        let mut start = 0.0;
+        let mut end = 0.0;
+        unsafe {
+            self.inner
+                .as_mut()
+                .GetHeadsetViewBlendRange(&mut start, &mut end)
+        };
+        (start, end)
+    }
+}
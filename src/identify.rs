@@ -0,0 +1,161 @@
+//! Helps users tell their devices apart, like "which tracker is Tracker 3", by
+//! buzzing the device and pointing at it with an overlay.
+//!
+//! ```no_run
+//! # use ovr_overlay::identify::Identifier;
+//! # use ovr_overlay::overlay::{OverlayHandle, OverlayManager};
+//! # use ovr_overlay::pose::Matrix3x4;
+//! # use ovr_overlay::system::SystemManager;
+//! # use ovr_overlay::TrackedDeviceIndex;
+//! # fn example(system: &mut SystemManager, mngr: &mut OverlayManager, arrow: OverlayHandle, tracker: TrackedDeviceIndex) {
+//! let mut identifier =
+//!     Identifier::new().with_arrow(arrow, Matrix3x4::from_translation([0., 0.15, 0.]));
+//! identifier.identify_device(tracker);
+//! loop {
+//!     identifier.update(system, mngr).unwrap();
+//! }
+//! # }
+//! ```
+
+use crate::errors::EVROverlayError;
+use crate::overlay::OverlayHandle;
+use crate::pose::Matrix3x4;
+use crate::traits::{Overlay, System};
+use crate::TrackedDeviceIndex;
+
+use std::time::{Duration, Instant};
+
+struct Identification {
+    device: TrackedDeviceIndex,
+    started: Instant,
+    pulses_sent: u32,
+}
+
+/// Identifies devices with a haptic pattern and, optionally, an overlay shown above
+/// them while the pattern plays.
+pub struct Identifier {
+    arrow: Option<(OverlayHandle, Matrix3x4)>,
+    pulses: u32,
+    interval: Duration,
+    pulse_length: Duration,
+    active: Vec<Identification>,
+    arrow_device: Option<TrackedDeviceIndex>,
+}
+
+impl Default for Identifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Identifier {
+    /// Defaults to three pulses a third of a second apart, without an arrow.
+    pub fn new() -> Self {
+        Self {
+            arrow: None,
+            pulses: 3,
+            interval: Duration::from_millis(330),
+            pulse_length: Duration::from_micros(3999),
+            active: Vec::new(),
+            arrow_device: None,
+        }
+    }
+
+    /// Shows `overlay` at `offset` relative to the device being identified, e.g. an
+    /// arrow image pointing down. The overlay is hidden otherwise.
+    pub fn with_arrow(mut self, overlay: OverlayHandle, offset: Matrix3x4) -> Self {
+        self.arrow = Some((overlay, offset));
+        self
+    }
+
+    /// `pulses` pulses of `pulse_length` (at most 3999µs), every `interval`.
+    pub fn with_pattern(mut self, pulses: u32, interval: Duration, pulse_length: Duration) -> Self {
+        self.pulses = pulses;
+        self.interval = interval;
+        self.pulse_length = pulse_length;
+        self
+    }
+
+    /// Starts identifying `device`, restarting the pattern if it already is. The arrow
+    /// moves to the device identified last.
+    pub fn identify_device(&mut self, device: TrackedDeviceIndex) {
+        self.active.retain(|active| active.device != device);
+        self.active.push(Identification {
+            device,
+            started: Instant::now(),
+            pulses_sent: 0,
+        });
+    }
+
+    pub fn is_identifying(&self, device: TrackedDeviceIndex) -> bool {
+        self.active.iter().any(|active| active.device == device)
+    }
+
+    /// Sends the pulses that are due and moves or hides the arrow. Call this every
+    /// frame while identifying.
+    pub fn update(
+        &mut self,
+        system: &mut (impl System + ?Sized),
+        mngr: &mut (impl Overlay + ?Sized),
+    ) -> Result<(), EVROverlayError> {
+        let now = Instant::now();
+        for active in &mut self.active {
+            let due = pulses_due(
+                now.saturating_duration_since(active.started),
+                self.interval,
+                self.pulses,
+            );
+            while active.pulses_sent < due {
+                system.trigger_haptic_pulse(active.device, 0, self.pulse_length);
+                active.pulses_sent += 1;
+            }
+        }
+        let duration = self.interval * self.pulses;
+        self.active
+            .retain(|active| now.saturating_duration_since(active.started) < duration);
+
+        let Some((overlay, offset)) = &self.arrow else {
+            return Ok(());
+        };
+        let device = self.active.last().map(|active| active.device);
+        if device == self.arrow_device {
+            return Ok(());
+        }
+        match device {
+            Some(device) => {
+                mngr.set_transform_tracked_device_relative(*overlay, device, offset)?;
+                mngr.set_visibility(*overlay, true)?;
+            }
+            None => mngr.set_visibility(*overlay, false)?,
+        }
+        self.arrow_device = device;
+        Ok(())
+    }
+}
+
+/// How many of `pulses` pulses, the first one immediately, have been sent after
+/// `elapsed`.
+fn pulses_due(elapsed: Duration, interval: Duration, pulses: u32) -> u32 {
+    if interval.is_zero() {
+        return pulses;
+    }
+    let due = elapsed.as_nanos() / interval.as_nanos() + 1;
+    due.min(pulses as u128) as u32
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_pulses_due() {
+        let interval = Duration::from_millis(300);
+        let due = |ms| pulses_due(Duration::from_millis(ms), interval, 3);
+        assert_eq!(due(0), 1);
+        assert_eq!(due(299), 1);
+        assert_eq!(due(300), 2);
+        assert_eq!(due(650), 3);
+        assert_eq!(due(5000), 3);
+        assert_eq!(pulses_due(Duration::ZERO, Duration::ZERO, 2), 2);
+    }
+}
@@ -1,52 +1,216 @@
-use crate::{errors::EVRInputError, pose, sys, Context};
+use crate::{errors::EVRInputError, pose, sys, Context, Interface, InterfaceLock};
 
+use bytemuck::{Pod, TransparentWrapper, Zeroable};
 use derive_more::{From, Into};
 use enumset::{EnumSet, EnumSetType};
 use std::ffi::{CStr, CString};
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
+use std::os::raw::c_char;
 use std::path::Path;
 use std::pin::Pin;
 use std::time::Duration;
 
+pub mod bindings;
+pub mod chord;
+pub mod gestures;
+pub mod priority;
+pub mod skeleton;
+
 pub struct InputManager<'c> {
     ctx: PhantomData<&'c Context>,
     inner: Pin<&'c mut sys::IVRInput>,
+    _lock: InterfaceLock<'c>,
+    /// The handles of the left hand, right hand and head, once looked up.
+    well_known: [Option<InputValueHandle>; 3],
 }
 
-#[derive(From, Into, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(From, Into, Debug, PartialEq, Eq, Clone, Copy, Pod, Zeroable)]
 #[repr(transparent)]
 pub struct ActionSetHandle(pub sys::VRActionSetHandle_t);
 
-#[derive(From, Into, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(From, Into, Debug, PartialEq, Eq, Clone, Copy, Pod, Zeroable)]
 #[repr(transparent)]
 pub struct ActionHandle(sys::VRActionHandle_t);
 
-#[derive(From, Into, Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(From, Into, Debug, PartialEq, Eq, Clone, Copy, Pod, Zeroable)]
 #[repr(transparent)]
 pub struct InputValueHandle(pub sys::VRInputValueHandle_t);
-
-#[derive(From, Into /*, Debug, PartialEq, Eq, Clone, Copy*/)]
+#[derive(From, Into, TransparentWrapper)]
 #[repr(transparent)]
 // TODO: do we want to do something else to forward fields to the sys struct?
 pub struct ActiveActionSet(pub sys::VRActiveActionSet_t);
 
-#[derive(From, Into /*, Debug, PartialEq, Eq, Clone, Copy*/)]
+#[derive(From, Into, TransparentWrapper)]
 #[repr(transparent)]
 pub struct DigitalActionData(pub sys::InputDigitalActionData_t);
 
-#[derive(From, Into /*, Debug, PartialEq, Eq, Clone, Copy*/)]
+#[derive(From, Into, TransparentWrapper)]
 #[repr(transparent)]
 pub struct AnalogActionData(pub sys::InputAnalogActionData_t);
 
-#[derive(From, Into /*, Debug, PartialEq, Eq, Clone, Copy*/)]
+#[derive(From, Into, TransparentWrapper)]
 #[repr(transparent)]
 pub struct PoseActionData(pub sys::InputPoseActionData_t);
 
-#[derive(From, Into /*, Debug, PartialEq, Eq, Clone, Copy*/)]
+#[derive(From, Into, TransparentWrapper)]
 #[repr(transparent)]
 pub struct OriginInfo(pub sys::InputOriginInfo_t);
 
+// The sys structs are plain data, but autocxx doesn't derive `Clone` for them.
+macro_rules! impl_clone {
+    ($($ty:ty),*) => {$(
+        impl Clone for $ty {
+            fn clone(&self) -> Self {
+                // Safety: the struct has no destructor or pointers it owns.
+                Self(unsafe { std::ptr::read(&self.0) })
+            }
+        }
+    )*};
+}
+impl_clone!(
+    ActiveActionSet,
+    DigitalActionData,
+    AnalogActionData,
+    PoseActionData,
+    OriginInfo
+);
+
+impl ActiveActionSet {
+    /// Activates `set` for all devices, without a secondary set, at priority 0.
+    pub fn new(set: ActionSetHandle) -> Self {
+        // Safety: all fields are plain integers.
+        let mut inner: sys::VRActiveActionSet_t = unsafe { std::mem::zeroed() };
+        inner.ulActionSet = set.0;
+        Self(inner)
+    }
+
+    /// Only take input from the given input source, like `/user/hand/left`.
+    pub fn restricted_to(mut self, device: InputValueHandle) -> Self {
+        self.0.ulRestrictedToDevice = device.0;
+        self
+    }
+
+    /// Also use the actions of `set`, but only for the restricted device.
+    pub fn with_secondary(mut self, set: ActionSetHandle) -> Self {
+        self.0.ulSecondaryActionSet = set.0;
+        self
+    }
+
+    /// See [`input::priority`](crate::input::priority) for what the values mean.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.0.nPriority = priority;
+        self
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.0.nPriority
+    }
+
+    pub fn set_priority(&mut self, priority: i32) {
+        self.0.nPriority = priority;
+    }
+}
+
+impl std::fmt::Debug for ActiveActionSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ActiveActionSet")
+            .field("action_set", &ActionSetHandle(self.0.ulActionSet))
+            .field(
+                "restricted_to_device",
+                &InputValueHandle(self.0.ulRestrictedToDevice),
+            )
+            .field(
+                "secondary_action_set",
+                &ActionSetHandle(self.0.ulSecondaryActionSet),
+            )
+            .field("priority", &self.0.nPriority)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for DigitalActionData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DigitalActionData")
+            .field("active", &self.0.bActive)
+            .field("active_origin", &InputValueHandle(self.0.activeOrigin))
+            .field("state", &self.0.bState)
+            .field("changed", &self.0.bChanged)
+            .field("update_time", &self.0.fUpdateTime)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for AnalogActionData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnalogActionData")
+            .field("active", &self.0.bActive)
+            .field("active_origin", &InputValueHandle(self.0.activeOrigin))
+            .field("value", &[self.0.x, self.0.y, self.0.z])
+            .field("delta", &[self.0.deltaX, self.0.deltaY, self.0.deltaZ])
+            .field("update_time", &self.0.fUpdateTime)
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for PoseActionData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pose = &self.0.pose;
+        f.debug_struct("PoseActionData")
+            .field("active", &self.0.bActive)
+            .field("active_origin", &InputValueHandle(self.0.activeOrigin))
+            .field("pose_is_valid", &pose.bPoseIsValid)
+            .field("device_is_connected", &pose.bDeviceIsConnected)
+            .field("tracking_result", &(pose.eTrackingResult.clone() as i32))
+            .field("velocity", &pose.vVelocity.v)
+            .field("angular_velocity", &pose.vAngularVelocity.v)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OriginInfo {
+    /// The input source of the device the origin belongs to, like `/user/hand/left`.
+    pub fn device_path(&self) -> InputValueHandle {
+        InputValueHandle(self.0.devicePath)
+    }
+
+    /// The device the origin belongs to, or `None` if it isn't a tracked device.
+    pub fn device_index(&self) -> Option<crate::TrackedDeviceIndex> {
+        crate::TrackedDeviceIndex::new(self.0.trackedDeviceIndex).ok()
+    }
+
+    /// The render model component of the origin, like `trigger`, or an empty string.
+    pub fn render_model_component_name(&self) -> String {
+        crate::strings::string_from(&self.0.rchRenderModelComponentName)
+    }
+}
+
+impl std::fmt::Debug for OriginInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OriginInfo")
+            .field("device_path", &self.device_path())
+            .field(
+                "tracked_device_index",
+                &crate::TrackedDeviceIndex(self.0.trackedDeviceIndex),
+            )
+            .field(
+                "render_model_component_name",
+                &self.render_model_component_name(),
+            )
+            .finish()
+    }
+}
+
+/// Finger positions of a hand, each between 0 and 1.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct SkeletalSummaryData {
+    /// How curled each finger is, from the thumb to the pinky. 0 is straight.
+    pub finger_curl: [f32; 5],
+    /// How far apart each pair of neighbouring fingers is, from thumb-index to
+    /// ring-pinky.
+    pub finger_splay: [f32; 4],
+}
+
 type Result<T> = std::result::Result<T, EVRInputError>;
 
 pub trait ToSeconds {
@@ -82,11 +246,13 @@ pub enum InputString {
 }
 
 impl<'c> InputManager<'c> {
-    pub(super) fn new(_ctx: &'c Context) -> Self {
-        let inner = unsafe { Pin::new_unchecked(sys::VRInput().as_mut::<'c>().unwrap()) };
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::Input) };
         Self {
             ctx: Default::default(),
             inner,
+            _lock: lock,
+            well_known: [None; 3],
         }
     }
 
@@ -164,6 +330,31 @@ impl<'c> InputManager<'c> {
         self.get_input_source_handle_raw(&name)
     }
 
+    /// The input source of the left hand, `/user/hand/left`. It is only looked up the
+    /// first time, and cached while the manager is alive.
+    pub fn left_hand(&mut self) -> Result<InputValueHandle> {
+        self.well_known_source(0, crate::paths::USER_HAND_LEFT)
+    }
+
+    /// The input source of the right hand, `/user/hand/right`, see [`Self::left_hand()`].
+    pub fn right_hand(&mut self) -> Result<InputValueHandle> {
+        self.well_known_source(1, crate::paths::USER_HAND_RIGHT)
+    }
+
+    /// The input source of the head, `/user/head`, see [`Self::left_hand()`].
+    pub fn head(&mut self) -> Result<InputValueHandle> {
+        self.well_known_source(2, crate::paths::USER_HEAD)
+    }
+
+    fn well_known_source(&mut self, index: usize, path: &str) -> Result<InputValueHandle> {
+        if let Some(handle) = self.well_known[index] {
+            return Ok(handle);
+        }
+        let handle = self.get_input_source_handle(path)?;
+        self.well_known[index] = Some(handle);
+        Ok(handle)
+    }
+
     pub fn get_input_source_handle_raw(&mut self, name: &CStr) -> Result<InputValueHandle> {
         let mut handle: sys::VRInputValueHandle_t = 0;
 
@@ -182,9 +373,7 @@ impl<'c> InputManager<'c> {
     pub fn update_actions(&mut self, sets: &mut [ActiveActionSet]) -> Result<()> {
         let err = unsafe {
             self.inner.as_mut().UpdateActionState(
-                // this should be fine because of repr(transparent)
-                // TODO: have bytemuck say it's fine or something?
-                sets.as_mut_ptr() as *mut sys::VRActiveActionSet_t,
+                ActiveActionSet::peel_slice_mut(sets).as_mut_ptr(),
                 std::mem::size_of::<sys::VRActiveActionSet_t>() as u32,
                 sets.len() as u32,
             )
@@ -252,6 +441,26 @@ impl<'c> InputManager<'c> {
         Ok(PoseActionData(unsafe { data.assume_init() }))
     }
 
+    /// Reads how curled and splayed the fingers of a skeletal action's hand are.
+    pub fn get_skeletal_summary_data(
+        &mut self,
+        action: ActionHandle,
+        summary_type: sys::EVRSummaryType,
+    ) -> Result<SkeletalSummaryData> {
+        let mut data: MaybeUninit<sys::VRSkeletalSummaryData_t> = MaybeUninit::uninit();
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .GetSkeletalSummaryData(action.0, summary_type, data.as_mut_ptr())
+        };
+        EVRInputError::new(err)?;
+        let data = unsafe { data.assume_init() };
+        Ok(SkeletalSummaryData {
+            finger_curl: data.flFingerCurl,
+            finger_splay: data.flFingerSplay,
+        })
+    }
+
     // ---- Action Origins ----
 
     pub fn get_action_origins(
@@ -277,25 +486,32 @@ impl<'c> InputManager<'c> {
         origin: InputValueHandle,
         bits: EnumSet<InputString>,
     ) -> Result<String> {
-        let mut name: [::std::os::raw::c_char; 128usize] = unsafe { ::std::mem::zeroed() };
+        let name = self.get_origin_localized_name_raw(origin, bits)?;
+        Ok(name.to_string_lossy().into_owned())
+    }
+
+    /// Like [`Self::get_origin_localized_name()`], but returns the name exactly as
+    /// OpenVR reported it, without replacing invalid UTF-8.
+    pub fn get_origin_localized_name_raw(
+        &mut self,
+        origin: InputValueHandle,
+        bits: EnumSet<InputString>,
+    ) -> Result<CString> {
+        let mut name: [c_char; 128usize] = unsafe { ::std::mem::zeroed() };
 
         let err = unsafe {
             self.inner.as_mut().GetOriginLocalizedName(
                 origin.0,
-                name.as_mut_ptr() as *mut i8,
+                name.as_mut_ptr() as *mut c_char,
                 128,
                 bits.as_repr() as i32,
             )
         };
 
         EVRInputError::new(err)?;
-        let trimmed_str = name
-            .iter()
-            .map(|&c| c as u8)
-            .take_while(|&x| x != 0)
-            .collect();
-
-        Ok(String::from_utf8(trimmed_str).expect("Could not parse string from name array"))
+        // Guarantee termination even if OpenVR filled the whole buffer.
+        name[name.len() - 1] = 0;
+        Ok(unsafe { CStr::from_ptr(name.as_ptr()) }.to_owned())
     }
 
     pub fn get_origin_tracked_device_info(
@@ -332,7 +548,7 @@ impl<'c> InputManager<'c> {
     ) -> Result<()> {
         let err = unsafe {
             self.inner.as_mut().ShowBindingsForActionSet(
-                sets.as_mut_ptr() as *mut sys::VRActiveActionSet_t,
+                ActiveActionSet::peel_slice_mut(sets).as_mut_ptr(),
                 std::mem::size_of::<sys::VRActiveActionSet_t>() as u32,
                 sets.len() as u32,
                 origin.0,
@@ -0,0 +1,129 @@
+//! Reports how a set of actions is bound, so applications can warn users about
+//! actions their controllers have no binding for.
+//!
+//! ```no_run
+//! # use ovr_overlay::input::{ActionSetHandle, InputManager};
+//! # fn example(input: &mut InputManager, set: ActionSetHandle) {
+//! let actions = ["/actions/main/in/toggle", "/actions/main/in/grab"];
+//! let report = input.binding_report(set, &actions).unwrap();
+//! for action in report.unbound() {
+//!     eprintln!("{} is not bound for your controllers", action.name);
+//! }
+//! # }
+//! ```
+
+use super::{ActionHandle, ActionSetHandle, InputManager, InputString, InputValueHandle};
+use crate::errors::EVRInputError;
+use crate::strings::string_from;
+use crate::{sys, TrackedDeviceIndex};
+
+use enumset::EnumSet;
+
+/// One input an action is bound to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    /// Like `/user/hand/right`.
+    pub device_path: String,
+    /// Like `/input/trigger`.
+    pub input_path: String,
+    /// Like `button` or `trigger`.
+    pub mode: String,
+    /// Like `click` or `value`.
+    pub slot: String,
+    pub input_source_type: String,
+}
+
+/// A device input that currently drives an action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundOrigin {
+    pub origin: InputValueHandle,
+    /// Like "Right Hand Index Controller Trigger", in the user's language.
+    pub localized_name: String,
+    pub device: Option<TrackedDeviceIndex>,
+    pub render_model_component: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionBindingStatus {
+    pub name: String,
+    pub action: ActionHandle,
+    pub bindings: Vec<Binding>,
+    pub origins: Vec<BoundOrigin>,
+}
+
+impl ActionBindingStatus {
+    pub fn is_bound(&self) -> bool {
+        !self.bindings.is_empty()
+    }
+}
+
+/// Returned by [`InputManager::binding_report()`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BindingReport {
+    pub actions: Vec<ActionBindingStatus>,
+}
+
+impl BindingReport {
+    /// The actions without any binding for the current controllers.
+    pub fn unbound(&self) -> impl Iterator<Item = &ActionBindingStatus> {
+        self.actions.iter().filter(|action| !action.is_bound())
+    }
+}
+
+impl InputManager<'_> {
+    /// Collects the bindings and origins of each of `actions`, which belong to
+    /// `action_set`.
+    ///
+    /// Origins are only reported for action sets that have been updated with
+    /// [`update_actions()`](Self::update_actions).
+    pub fn binding_report(
+        &mut self,
+        action_set: ActionSetHandle,
+        actions: &[&str],
+    ) -> Result<BindingReport, EVRInputError> {
+        let mut report = BindingReport::default();
+        for &name in actions {
+            let action = self.get_action_handle(name)?;
+            let bindings = match self.get_action_binding_info(action) {
+                Ok(bindings) => bindings.iter().map(Binding::from).collect(),
+                Err(err) if err.inner() == sys::EVRInputError::VRInputError_NoData => Vec::new(),
+                Err(err) => return Err(err),
+            };
+
+            let mut origins = Vec::new();
+            for origin in self.get_action_origins(action_set, action)? {
+                if origin == sys::k_ulInvalidInputValueHandle {
+                    continue;
+                }
+                let origin = InputValueHandle(origin);
+                let info = self.get_origin_tracked_device_info(origin)?;
+                origins.push(BoundOrigin {
+                    origin,
+                    localized_name: self.get_origin_localized_name(origin, EnumSet::all())?,
+                    device: info.device_index(),
+                    render_model_component: info.render_model_component_name(),
+                });
+            }
+
+            report.actions.push(ActionBindingStatus {
+                name: name.to_owned(),
+                action,
+                bindings,
+                origins,
+            });
+        }
+        Ok(report)
+    }
+}
+
+impl From<&sys::InputBindingInfo_t> for Binding {
+    fn from(info: &sys::InputBindingInfo_t) -> Self {
+        Self {
+            device_path: string_from(&info.rchDevicePathName),
+            input_path: string_from(&info.rchInputPathName),
+            mode: string_from(&info.rchModeName),
+            slot: string_from(&info.rchSlotName),
+            input_source_type: string_from(&info.rchInputSourceType),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Treats a combination of digital actions, like holding both grips for a second, as a
+//! single derived action.
+//!
+//! ```no_run
+//! # use ovr_overlay::input::{ActionHandle, InputManager};
+//! # use ovr_overlay::input::chord::Chord;
+//! # use std::time::Duration;
+//! # fn example(input: &mut InputManager, left_grip: ActionHandle, right_grip: ActionHandle) {
+//! let mut toggle = Chord::new(vec![left_grip, right_grip], Duration::from_secs(1));
+//! loop {
+//!     let state = toggle.update(input).unwrap();
+//!     if state.pressed && state.changed {
+//!         // Toggle the overlay...
+//!     }
+//! }
+//! # }
+//! ```
+
+use super::{ActionHandle, InputValueHandle};
+use crate::errors::EVRInputError;
+use crate::traits::Input;
+
+use std::time::{Duration, Instant};
+
+/// The state of a [`Chord`], with the same meaning as for a digital action.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ChordState {
+    /// Whether the chord is currently held.
+    pub pressed: bool,
+    /// Whether `pressed` changed in this update.
+    pub changed: bool,
+}
+
+pub struct Chord {
+    actions: Vec<ActionHandle>,
+    hold: Duration,
+    window: Option<Duration>,
+    pressed_at: Vec<Option<Instant>>,
+    states: Vec<bool>,
+    pressed: bool,
+}
+
+impl Chord {
+    /// A chord pressed once all `actions` have been held for `hold`.
+    pub fn new(actions: Vec<ActionHandle>, hold: Duration) -> Self {
+        let len = actions.len();
+        Self {
+            actions,
+            hold,
+            window: None,
+            pressed_at: vec![None; len],
+            states: vec![false; len],
+            pressed: false,
+        }
+    }
+
+    /// Only count the chord if all actions were pressed within `window` of each other,
+    /// so holding one button for a while and then pressing the other doesn't trigger
+    /// it. Unlimited by default.
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    /// Reads the actions' current state and updates the chord. Call this every frame,
+    /// after updating the actions.
+    pub fn update(
+        &mut self,
+        input: &mut (impl Input + ?Sized),
+    ) -> Result<ChordState, EVRInputError> {
+        for (state, &action) in self.states.iter_mut().zip(&self.actions) {
+            let data = input.get_digital_action_data(action, InputValueHandle(0))?;
+            *state = data.0.bActive && data.0.bState;
+        }
+        let states = std::mem::take(&mut self.states);
+        let state = self.update_states_at(&states, Instant::now());
+        self.states = states;
+        Ok(state)
+    }
+
+    /// Updates the chord with the state of each action, in the order they were given
+    /// in, as of `now`.
+    pub fn update_states_at(&mut self, states: &[bool], now: Instant) -> ChordState {
+        for (pressed_at, &state) in self.pressed_at.iter_mut().zip(states) {
+            match (state, *pressed_at) {
+                (true, None) => *pressed_at = Some(now),
+                (false, _) => *pressed_at = None,
+                (true, Some(_)) => {}
+            }
+        }
+
+        let times: Option<Vec<Instant>> = self.pressed_at.iter().copied().collect();
+        let pressed = match times.as_deref() {
+            Some([first, rest @ ..]) => {
+                let (min, max) = rest
+                    .iter()
+                    .fold((*first, *first), |(min, max), &t| (min.min(t), max.max(t)));
+                let in_window = !self.window.is_some_and(|window| max - min > window);
+                in_window && now.saturating_duration_since(max) >= self.hold
+            }
+            _ => false,
+        };
+
+        let changed = pressed != self.pressed;
+        self.pressed = pressed;
+        ChordState { pressed, changed }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chord() -> Chord {
+        Chord::new(
+            vec![ActionHandle(1), ActionHandle(2)],
+            Duration::from_millis(500),
+        )
+        .with_window(Duration::from_millis(200))
+    }
+
+    #[test]
+    fn test_hold() {
+        let mut chord = chord();
+        let t0 = Instant::now();
+        let ms = |ms| t0 + Duration::from_millis(ms);
+        assert_eq!(
+            chord.update_states_at(&[true, false], ms(0)),
+            ChordState::default()
+        );
+        let state = chord.update_states_at(&[true, true], ms(100));
+        assert!(!state.pressed);
+        let state = chord.update_states_at(&[true, true], ms(600));
+        assert!(state.pressed && state.changed);
+        let state = chord.update_states_at(&[true, true], ms(700));
+        assert!(state.pressed && !state.changed);
+        let state = chord.update_states_at(&[false, true], ms(800));
+        assert!(!state.pressed && state.changed);
+    }
+
+    #[test]
+    fn test_window() {
+        let mut chord = chord();
+        let t0 = Instant::now();
+        let ms = |ms| t0 + Duration::from_millis(ms);
+        chord.update_states_at(&[true, false], ms(0));
+        chord.update_states_at(&[true, true], ms(300));
+        assert!(!chord.update_states_at(&[true, true], ms(2000)).pressed);
+
+        // Pressing the first button again starts a new attempt.
+        chord.update_states_at(&[false, true], ms(2100));
+        chord.update_states_at(&[true, true], ms(2200));
+        assert!(chord.update_states_at(&[true, true], ms(2700)).pressed);
+    }
+}
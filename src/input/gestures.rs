@@ -0,0 +1,434 @@
+//! Recognizes common gestures from action data: double clicks, long presses,
+//! thumbstick flicks, pinches and hand poses.
+//!
+//! Each detector tracks one action, and is fed its data every frame.
+//!
+//! ```no_run
+//! # use ovr_overlay::input::{ActionHandle, InputManager, InputValueHandle};
+//! # use ovr_overlay::input::gestures::{ButtonGestures, Gesture};
+//! # fn example(input: &mut InputManager, action: ActionHandle) {
+//! let mut button = ButtonGestures::default();
+//! loop {
+//!     let data = input
+//!         .get_digital_action_data(action, InputValueHandle(0))
+//!         .unwrap();
+//!     if let Some(Gesture::DoubleClick) = button.update(&data) {
+//!         // ...
+//!     }
+//! }
+//! # }
+//! ```
+
+use super::{AnalogActionData, DigitalActionData, SkeletalSummaryData};
+
+use std::time::{Duration, Instant};
+
+/// A gesture recognized by one of the detectors.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Gesture {
+    DoubleClick,
+    /// The button has been held for the long press duration. Reported once per press.
+    LongPress,
+    Flick(FlickDirection),
+    PinchStarted,
+    PinchEnded,
+    /// The hand changed from one pose to another, `None` being no recognized pose.
+    HandPoseChanged {
+        from: Option<HandPose>,
+        to: Option<HandPose>,
+    },
+}
+
+/// A pose of the fingers recognized by [`HandPoseGestures`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum HandPose {
+    /// All fingers straight.
+    Open,
+    /// All fingers but the thumb curled.
+    Fist,
+    /// The index finger straight, the middle, ring and pinky fingers curled.
+    Point,
+    /// The thumb and index finger curled together, the middle finger not.
+    Pinch,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FlickDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Detects double clicks and long presses of a digital action.
+#[derive(Debug, Clone)]
+pub struct ButtonGestures {
+    /// The longest time between two clicks for them to be a double click.
+    pub double_click_window: Duration,
+    /// How long the button must be held for a long press.
+    pub long_press: Duration,
+    pressed_at: Option<Instant>,
+    long_press_reported: bool,
+    last_click: Option<Instant>,
+}
+impl Default for ButtonGestures {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(300), Duration::from_millis(800))
+    }
+}
+impl ButtonGestures {
+    pub fn new(double_click_window: Duration, long_press: Duration) -> Self {
+        Self {
+            double_click_window,
+            long_press,
+            pressed_at: None,
+            long_press_reported: false,
+            last_click: None,
+        }
+    }
+
+    pub fn update(&mut self, data: &DigitalActionData) -> Option<Gesture> {
+        if !data.0.bActive {
+            self.reset();
+            return None;
+        }
+        self.update_state(data.0.bState, Instant::now())
+    }
+
+    /// Forgets the current press and the last click, so the action becoming inactive
+    /// while held isn't taken as a release.
+    pub fn reset(&mut self) {
+        self.pressed_at = None;
+        self.long_press_reported = false;
+        self.last_click = None;
+    }
+
+    /// Like [`Self::update()`], with the button state and time given directly.
+    pub fn update_state(&mut self, pressed: bool, now: Instant) -> Option<Gesture> {
+        match (self.pressed_at, pressed) {
+            (None, true) => {
+                self.pressed_at = Some(now);
+                self.long_press_reported = false;
+                None
+            }
+            (Some(at), true) => {
+                if !self.long_press_reported && now - at >= self.long_press {
+                    self.long_press_reported = true;
+                    // A long press doesn't count as the first click of a double click.
+                    self.last_click = None;
+                    return Some(Gesture::LongPress);
+                }
+                None
+            }
+            (Some(_), false) => {
+                self.pressed_at = None;
+                if self.long_press_reported {
+                    return None;
+                }
+                match self.last_click.take() {
+                    Some(last) if now - last <= self.double_click_window => {
+                        Some(Gesture::DoubleClick)
+                    }
+                    _ => {
+                        self.last_click = Some(now);
+                        None
+                    }
+                }
+            }
+            (None, false) => None,
+        }
+    }
+}
+
+/// Detects quick pushes of a thumbstick or trackpad towards an edge.
+///
+/// A push is a flick if it goes from the `reset` radius to the `threshold` within
+/// `max_rise_time`. Slower pushes are ignored until the stick returns to the center.
+#[derive(Debug, Clone)]
+pub struct FlickGestures {
+    /// How far the stick must be pushed, between 0 and 1, for a flick.
+    pub threshold: f32,
+    /// How close to the center the stick must return before the next flick.
+    pub reset: f32,
+    /// The longest a push may take from leaving the center to reaching the threshold.
+    pub max_rise_time: Duration,
+    armed: bool,
+    left_center: Option<Instant>,
+}
+impl Default for FlickGestures {
+    fn default() -> Self {
+        Self::new(0.8, 0.3, Duration::from_millis(150))
+    }
+}
+impl FlickGestures {
+    pub fn new(threshold: f32, reset: f32, max_rise_time: Duration) -> Self {
+        Self {
+            threshold,
+            reset,
+            max_rise_time,
+            armed: true,
+            left_center: None,
+        }
+    }
+
+    pub fn update(&mut self, data: &AnalogActionData) -> Option<Gesture> {
+        if !data.0.bActive {
+            self.armed = true;
+            self.left_center = None;
+            return None;
+        }
+        self.update_position(data.0.x, data.0.y, Instant::now())
+    }
+
+    /// Like [`Self::update()`], with the stick position and time given directly.
+    pub fn update_position(&mut self, x: f32, y: f32, now: Instant) -> Option<Gesture> {
+        let magnitude = x.hypot(y);
+        if magnitude <= self.reset {
+            self.armed = true;
+            self.left_center = None;
+            return None;
+        }
+        let left_center = *self.left_center.get_or_insert(now);
+        if !self.armed || magnitude < self.threshold {
+            return None;
+        }
+        self.armed = false;
+        if now - left_center > self.max_rise_time {
+            return None;
+        }
+        let direction = if x.abs() > y.abs() {
+            if x > 0. {
+                FlickDirection::Right
+            } else {
+                FlickDirection::Left
+            }
+        } else if y > 0. {
+            FlickDirection::Up
+        } else {
+            FlickDirection::Down
+        };
+        Some(Gesture::Flick(direction))
+    }
+}
+
+/// Detects the thumb and index finger being curled together.
+#[derive(Debug, Clone)]
+pub struct PinchGestures {
+    /// How curled both fingers must be, between 0 and 1, to start a pinch.
+    pub start: f32,
+    /// How far either finger must uncurl to end the pinch.
+    pub end: f32,
+    pinching: bool,
+}
+impl Default for PinchGestures {
+    fn default() -> Self {
+        Self::new(0.8, 0.6)
+    }
+}
+impl PinchGestures {
+    pub fn new(start: f32, end: f32) -> Self {
+        Self {
+            start,
+            end,
+            pinching: false,
+        }
+    }
+
+    pub fn is_pinching(&self) -> bool {
+        self.pinching
+    }
+
+    pub fn update(&mut self, data: &SkeletalSummaryData) -> Option<Gesture> {
+        let [thumb, index, ..] = data.finger_curl;
+        let curl = thumb.min(index);
+        if !self.pinching && curl >= self.start {
+            self.pinching = true;
+            Some(Gesture::PinchStarted)
+        } else if self.pinching && curl < self.end {
+            self.pinching = false;
+            Some(Gesture::PinchEnded)
+        } else {
+            None
+        }
+    }
+}
+
+/// Recognizes [`HandPose`]s from the finger curl of one hand.
+///
+/// A recognized pose is kept until the fingers move `hysteresis` past the thresholds,
+/// so fingers resting near a threshold don't make the pose flicker.
+#[derive(Debug, Clone)]
+pub struct HandPoseGestures {
+    /// How curled a finger must be, between 0 and 1, to count as curled.
+    pub curled: f32,
+    /// How straight a finger must be to count as straight.
+    pub straight: f32,
+    pub hysteresis: f32,
+    pose: Option<HandPose>,
+}
+impl Default for HandPoseGestures {
+    fn default() -> Self {
+        Self::new(0.75, 0.25, 0.1)
+    }
+}
+impl HandPoseGestures {
+    pub fn new(curled: f32, straight: f32, hysteresis: f32) -> Self {
+        Self {
+            curled,
+            straight,
+            hysteresis,
+            pose: None,
+        }
+    }
+
+    pub fn pose(&self) -> Option<HandPose> {
+        self.pose
+    }
+
+    pub fn update(&mut self, data: &SkeletalSummaryData) -> Option<Gesture> {
+        self.update_curl(data.finger_curl)
+    }
+
+    /// Like [`Self::update()`], with the finger curl given directly.
+    pub fn update_curl(&mut self, curl: [f32; 5]) -> Option<Gesture> {
+        if self
+            .pose
+            .is_some_and(|pose| self.matches(pose, curl, self.hysteresis))
+        {
+            return None;
+        }
+        let to = [
+            HandPose::Fist,
+            HandPose::Point,
+            HandPose::Pinch,
+            HandPose::Open,
+        ]
+        .into_iter()
+        .find(|&pose| self.matches(pose, curl, 0.));
+        let from = std::mem::replace(&mut self.pose, to);
+        (from != to).then_some(Gesture::HandPoseChanged { from, to })
+    }
+
+    /// Whether `curl` is in `pose`, with the thresholds relaxed by `margin`.
+    fn matches(&self, pose: HandPose, curl: [f32; 5], margin: f32) -> bool {
+        let curled = |c: f32| c >= self.curled - margin;
+        let straight = |c: f32| c <= self.straight + margin;
+        let relaxed = |c: f32| c < self.curled + margin;
+        let [thumb, index, middle, ring, pinky] = curl;
+        match pose {
+            HandPose::Open => curl.into_iter().all(straight),
+            HandPose::Fist => [index, middle, ring, pinky].into_iter().all(curled),
+            HandPose::Point => straight(index) && [middle, ring, pinky].into_iter().all(curled),
+            HandPose::Pinch => curled(thumb) && curled(index) && relaxed(middle),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn button_gestures() {
+        let mut button = ButtonGestures::default();
+        let t0 = Instant::now();
+        let ms = |ms| t0 + Duration::from_millis(ms);
+        assert_eq!(button.update_state(true, ms(0)), None);
+        assert_eq!(button.update_state(false, ms(50)), None);
+        assert_eq!(button.update_state(true, ms(100)), None);
+        assert_eq!(
+            button.update_state(false, ms(150)),
+            Some(Gesture::DoubleClick)
+        );
+
+        assert_eq!(button.update_state(true, ms(1000)), None);
+        assert_eq!(
+            button.update_state(true, ms(1900)),
+            Some(Gesture::LongPress)
+        );
+        assert_eq!(button.update_state(true, ms(2000)), None);
+        assert_eq!(button.update_state(false, ms(2100)), None);
+
+        // Losing the action while held isn't a click.
+        assert_eq!(button.update_state(true, ms(3000)), None);
+        button.reset();
+        assert_eq!(button.update_state(true, ms(3100)), None);
+        assert_eq!(button.update_state(false, ms(3150)), None);
+    }
+
+    #[test]
+    fn flick_needs_reset() {
+        let mut flick = FlickGestures::default();
+        let t0 = Instant::now();
+        let ms = |ms| t0 + Duration::from_millis(ms);
+        assert_eq!(
+            flick.update_position(0., 0.9, ms(0)),
+            Some(Gesture::Flick(FlickDirection::Up))
+        );
+        assert_eq!(flick.update_position(0.9, 0., ms(10)), None);
+        assert_eq!(flick.update_position(0., 0., ms(20)), None);
+        assert_eq!(
+            flick.update_position(-0.9, 0., ms(30)),
+            Some(Gesture::Flick(FlickDirection::Left))
+        );
+    }
+
+    #[test]
+    fn slow_push_is_not_a_flick() {
+        let mut flick = FlickGestures::default();
+        let t0 = Instant::now();
+        let ms = |ms| t0 + Duration::from_millis(ms);
+        assert_eq!(flick.update_position(0.5, 0., ms(0)), None);
+        assert_eq!(flick.update_position(0.9, 0., ms(500)), None);
+        assert_eq!(flick.update_position(0.5, 0., ms(600)), None);
+        assert_eq!(flick.update_position(0.9, 0., ms(650)), None);
+        assert_eq!(flick.update_position(0., 0., ms(700)), None);
+        assert_eq!(flick.update_position(0.5, 0., ms(800)), None);
+        assert_eq!(
+            flick.update_position(0.9, 0., ms(850)),
+            Some(Gesture::Flick(FlickDirection::Right))
+        );
+    }
+
+    #[test]
+    fn hand_pose_hysteresis() {
+        let mut hand = HandPoseGestures::default();
+        assert_eq!(
+            hand.update_curl([0.9, 0.9, 0.9, 0.9, 0.9]),
+            Some(Gesture::HandPoseChanged {
+                from: None,
+                to: Some(HandPose::Fist),
+            })
+        );
+        // Just below the threshold, still within the hysteresis.
+        assert_eq!(hand.update_curl([0.9, 0.7, 0.9, 0.9, 0.9]), None);
+        assert_eq!(
+            hand.update_curl([0.9, 0.1, 0.9, 0.9, 0.9]),
+            Some(Gesture::HandPoseChanged {
+                from: Some(HandPose::Fist),
+                to: Some(HandPose::Point),
+            })
+        );
+        assert_eq!(
+            hand.update_curl([0.9, 0.9, 0.3, 0.2, 0.2]),
+            Some(Gesture::HandPoseChanged {
+                from: Some(HandPose::Point),
+                to: Some(HandPose::Pinch),
+            })
+        );
+        assert_eq!(
+            hand.update_curl([0.1; 5]).unwrap(),
+            Gesture::HandPoseChanged {
+                from: Some(HandPose::Pinch),
+                to: Some(HandPose::Open),
+            }
+        );
+        assert_eq!(
+            hand.update_curl([0.5; 5]).unwrap(),
+            Gesture::HandPoseChanged {
+                from: Some(HandPose::Open),
+                to: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Action set priorities, which decide who gets an input when several active action
+//! sets bind it.
+//!
+//! Within one application, only the sets with the highest priority that bind an input
+//! receive it. Overlay applications can additionally use priorities from
+//! [`OVERLAY_GLOBAL_MIN`] to [`OVERLAY_GLOBAL_MAX`], which also take the input away
+//! from the scene application and other overlays with lower priorities. Priorities
+//! from [`RESERVED_MIN`] are rejected with `InvalidPriority`.
+//!
+//! ```no_run
+//! # use ovr_overlay::input::{ActionSetHandle, ActiveActionSet, InputManager};
+//! # use ovr_overlay::input::priority::ExclusiveActionSets;
+//! # fn example(input: &mut InputManager, main: ActionSetHandle, grabbed: bool) {
+//! let mut sets = ExclusiveActionSets::new(vec![ActiveActionSet::new(main)]);
+//! loop {
+//!     // Keep the game from reacting to the buttons while a panel is held.
+//!     if grabbed {
+//!         sets.claim();
+//!     } else {
+//!         sets.release();
+//!     }
+//!     sets.update(input).unwrap();
+//! }
+//! # }
+//! ```
+
+use super::ActiveActionSet;
+use crate::errors::EVRInputError;
+use crate::traits::Input;
+
+/// The lowest priority that blocks the input of the scene application.
+pub const OVERLAY_GLOBAL_MIN: i32 = 0x0100_0000;
+/// The highest priority applications may use.
+pub const OVERLAY_GLOBAL_MAX: i32 = 0x01FF_FFFF;
+/// Priorities from here on are reserved for SteamVR.
+pub const RESERVED_MIN: i32 = 0x0200_0000;
+
+/// Whether `priority` takes input away from other applications.
+pub fn is_global(priority: i32) -> bool {
+    (OVERLAY_GLOBAL_MIN..=OVERLAY_GLOBAL_MAX).contains(&priority)
+}
+
+/// The active action sets of an overlay, which can temporarily claim their inputs
+/// from all other applications.
+pub struct ExclusiveActionSets {
+    sets: Vec<ActiveActionSet>,
+    /// The priorities the sets were given, restored when released.
+    priorities: Vec<i32>,
+    claimed: bool,
+}
+
+impl ExclusiveActionSets {
+    /// The sets start out released, with the priorities they have.
+    pub fn new(sets: Vec<ActiveActionSet>) -> Self {
+        let priorities = sets.iter().map(ActiveActionSet::priority).collect();
+        Self {
+            sets,
+            priorities,
+            claimed: false,
+        }
+    }
+
+    /// Moves the sets into the global priority range, keeping their order relative to
+    /// each other. Only works for overlay applications.
+    pub fn claim(&mut self) {
+        if self.claimed {
+            return;
+        }
+        for (set, &priority) in self.sets.iter_mut().zip(&self.priorities) {
+            set.set_priority(claimed_priority(priority));
+        }
+        self.claimed = true;
+    }
+
+    /// Restores the sets' priorities, giving the inputs back to other applications.
+    pub fn release(&mut self) {
+        if !self.claimed {
+            return;
+        }
+        for (set, &priority) in self.sets.iter_mut().zip(&self.priorities) {
+            set.set_priority(priority);
+        }
+        self.claimed = false;
+    }
+
+    pub fn is_claimed(&self) -> bool {
+        self.claimed
+    }
+
+    pub fn sets(&self) -> &[ActiveActionSet] {
+        &self.sets
+    }
+
+    /// Updates the action state with the sets, at their current priorities. Call this
+    /// instead of [`InputManager::update_actions()`](super::InputManager::update_actions).
+    pub fn update(&mut self, input: &mut (impl Input + ?Sized)) -> Result<(), EVRInputError> {
+        input.update_actions(&mut self.sets)
+    }
+}
+
+/// `priority` shifted into the global range, saturating at its end.
+fn claimed_priority(priority: i32) -> i32 {
+    if is_global(priority) {
+        return priority;
+    }
+    OVERLAY_GLOBAL_MIN
+        .saturating_add(priority.max(0))
+        .min(OVERLAY_GLOBAL_MAX)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input::ActionSetHandle;
+
+    #[test]
+    fn test_claim_release() {
+        let mut sets = ExclusiveActionSets::new(vec![
+            ActiveActionSet::new(ActionSetHandle(1)),
+            ActiveActionSet::new(ActionSetHandle(2)).with_priority(10),
+        ]);
+        let priorities = |sets: &ExclusiveActionSets| {
+            sets.sets().iter().map(|s| s.priority()).collect::<Vec<_>>()
+        };
+
+        sets.claim();
+        sets.claim();
+        assert!(sets.is_claimed());
+        assert_eq!(
+            priorities(&sets),
+            [OVERLAY_GLOBAL_MIN, OVERLAY_GLOBAL_MIN + 10]
+        );
+        assert!(sets.sets().iter().all(|s| is_global(s.priority())));
+
+        sets.release();
+        assert_eq!(priorities(&sets), [0, 10]);
+    }
+
+    #[test]
+    fn test_claimed_priority() {
+        assert_eq!(claimed_priority(-5), OVERLAY_GLOBAL_MIN);
+        assert_eq!(
+            claimed_priority(OVERLAY_GLOBAL_MIN + 3),
+            OVERLAY_GLOBAL_MIN + 3
+        );
+        assert_eq!(claimed_priority(i32::MAX), OVERLAY_GLOBAL_MAX);
+        assert!(!is_global(RESERVED_MIN));
+    }
+}
@@ -0,0 +1,125 @@
+//! Names for the bones of SteamVR's hand skeleton, for indexing the bone transform
+//! arrays of skeletal actions.
+
+use crate::enums::InvalidEnumValue;
+
+/// How many bones a hand skeleton has.
+pub const BONE_COUNT: usize = 31;
+
+/// A bone of the hand skeleton, with SteamVR's bone index as its discriminant.
+///
+/// Fingers count up from the bone closest to the wrist, and their last bone is the tip.
+/// The thumb has no metacarpal, so it has one bone less.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u32)]
+pub enum HandSkeletonBone {
+    Root = 0,
+    Wrist,
+    Thumb0,
+    Thumb1,
+    Thumb2,
+    Thumb3,
+    IndexFinger0,
+    IndexFinger1,
+    IndexFinger2,
+    IndexFinger3,
+    IndexFinger4,
+    MiddleFinger0,
+    MiddleFinger1,
+    MiddleFinger2,
+    MiddleFinger3,
+    MiddleFinger4,
+    RingFinger0,
+    RingFinger1,
+    RingFinger2,
+    RingFinger3,
+    RingFinger4,
+    PinkyFinger0,
+    PinkyFinger1,
+    PinkyFinger2,
+    PinkyFinger3,
+    PinkyFinger4,
+    /// Auxiliary bones, for attaching things to the fingers, positioned relative to
+    /// the wrist.
+    AuxThumb,
+    AuxIndexFinger,
+    AuxMiddleFinger,
+    AuxRingFinger,
+    AuxPinkyFinger,
+}
+
+impl HandSkeletonBone {
+    /// Every bone, in index order.
+    #[rustfmt::skip]
+    pub const ALL: [Self; BONE_COUNT] = {
+        use HandSkeletonBone::*;
+        [
+            Root, Wrist,
+            Thumb0, Thumb1, Thumb2, Thumb3,
+            IndexFinger0, IndexFinger1, IndexFinger2, IndexFinger3, IndexFinger4,
+            MiddleFinger0, MiddleFinger1, MiddleFinger2, MiddleFinger3, MiddleFinger4,
+            RingFinger0, RingFinger1, RingFinger2, RingFinger3, RingFinger4,
+            PinkyFinger0, PinkyFinger1, PinkyFinger2, PinkyFinger3, PinkyFinger4,
+            AuxThumb, AuxIndexFinger, AuxMiddleFinger, AuxRingFinger, AuxPinkyFinger,
+        ]
+    };
+
+    /// The index of the bone in bone transform arrays.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+impl From<HandSkeletonBone> for u32 {
+    fn from(bone: HandSkeletonBone) -> Self {
+        bone as u32
+    }
+}
+
+impl From<HandSkeletonBone> for usize {
+    fn from(bone: HandSkeletonBone) -> Self {
+        bone.index()
+    }
+}
+
+impl TryFrom<u32> for HandSkeletonBone {
+    type Error = InvalidEnumValue;
+
+    fn try_from(index: u32) -> Result<Self, Self::Error> {
+        Self::ALL
+            .get(index as usize)
+            .copied()
+            .ok_or(InvalidEnumValue(index))
+    }
+}
+
+impl TryFrom<usize> for HandSkeletonBone {
+    type Error = InvalidEnumValue;
+
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        Self::ALL
+            .get(index)
+            .copied()
+            .ok_or(InvalidEnumValue(index.try_into().unwrap_or(u32::MAX)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_indices() {
+        for (i, bone) in HandSkeletonBone::ALL.into_iter().enumerate() {
+            assert_eq!(bone.index(), i);
+            assert_eq!(HandSkeletonBone::try_from(i), Ok(bone));
+        }
+        assert_eq!(HandSkeletonBone::IndexFinger0.index(), 6);
+        assert_eq!(HandSkeletonBone::AuxPinkyFinger.index(), BONE_COUNT - 1);
+        assert_eq!(
+            HandSkeletonBone::try_from(BONE_COUNT as u32),
+            Err(InvalidEnumValue(31))
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Checks for which OpenVR interfaces the installed runtime supports.
+
+use crate::sys;
+
+use std::ffi::{c_void, CStr};
+
+/// An OpenVR interface that this crate wraps, one per manager.
+// The discriminants double as indices into `Context`'s interfaces, so this must stay
+// a fieldless enum without explicit discriminants.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Interface {
+    Overlay,
+    #[cfg(feature = "ovr_overlay_view")]
+    OverlayView,
+    #[cfg(feature = "ovr_applications")]
+    Applications,
+    #[cfg(feature = "ovr_chaperone_setup")]
+    ChaperoneSetup,
+    #[cfg(feature = "ovr_compositor")]
+    Compositor,
+    #[cfg(feature = "ovr_debug")]
+    Debug,
+    #[cfg(feature = "ovr_driver_manager")]
+    DriverManager,
+    #[cfg(feature = "ovr_extended_display")]
+    ExtendedDisplay,
+    #[cfg(feature = "ovr_headset_view")]
+    HeadsetView,
+    #[cfg(feature = "ovr_input")]
+    Input,
+    #[cfg(feature = "ovr_iobuffer")]
+    IoBuffer,
+    #[cfg(feature = "ovr_notifications")]
+    Notifications,
+    #[cfg(feature = "ovr_render_models")]
+    RenderModels,
+    #[cfg(feature = "ovr_resources")]
+    Resources,
+    #[cfg(feature = "ovr_screenshots")]
+    Screenshots,
+    #[cfg(feature = "ovr_settings")]
+    Settings,
+    #[cfg(feature = "ovr_system")]
+    System,
+    #[cfg(feature = "ovr_tracked_camera")]
+    TrackedCamera,
+}
+impl Interface {
+    /// All interfaces enabled by this crate's features.
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Overlay,
+            #[cfg(feature = "ovr_overlay_view")]
+            Self::OverlayView,
+            #[cfg(feature = "ovr_applications")]
+            Self::Applications,
+            #[cfg(feature = "ovr_chaperone_setup")]
+            Self::ChaperoneSetup,
+            #[cfg(feature = "ovr_compositor")]
+            Self::Compositor,
+            #[cfg(feature = "ovr_debug")]
+            Self::Debug,
+            #[cfg(feature = "ovr_driver_manager")]
+            Self::DriverManager,
+            #[cfg(feature = "ovr_extended_display")]
+            Self::ExtendedDisplay,
+            #[cfg(feature = "ovr_headset_view")]
+            Self::HeadsetView,
+            #[cfg(feature = "ovr_input")]
+            Self::Input,
+            #[cfg(feature = "ovr_iobuffer")]
+            Self::IoBuffer,
+            #[cfg(feature = "ovr_notifications")]
+            Self::Notifications,
+            #[cfg(feature = "ovr_render_models")]
+            Self::RenderModels,
+            #[cfg(feature = "ovr_resources")]
+            Self::Resources,
+            #[cfg(feature = "ovr_screenshots")]
+            Self::Screenshots,
+            #[cfg(feature = "ovr_settings")]
+            Self::Settings,
+            #[cfg(feature = "ovr_system")]
+            Self::System,
+            #[cfg(feature = "ovr_tracked_camera")]
+            Self::TrackedCamera,
+        ]
+    }
+
+    /// The interface version string that this crate was built against, such as
+    /// `IVRSystem_022`.
+    pub fn version(self) -> &'static CStr {
+        let version: &'static [u8] = match self {
+            Self::Overlay => &sys::IVROverlay_Version[..],
+            #[cfg(feature = "ovr_overlay_view")]
+            Self::OverlayView => &sys::IVROverlayView_Version[..],
+            #[cfg(feature = "ovr_applications")]
+            Self::Applications => &sys::IVRApplications_Version[..],
+            #[cfg(feature = "ovr_chaperone_setup")]
+            Self::ChaperoneSetup => &sys::IVRChaperoneSetup_Version[..],
+            #[cfg(feature = "ovr_compositor")]
+            Self::Compositor => &sys::IVRCompositor_Version[..],
+            #[cfg(feature = "ovr_debug")]
+            Self::Debug => &sys::IVRDebug_Version[..],
+            #[cfg(feature = "ovr_driver_manager")]
+            Self::DriverManager => &sys::IVRDriverManager_Version[..],
+            #[cfg(feature = "ovr_extended_display")]
+            Self::ExtendedDisplay => &sys::IVRExtendedDisplay_Version[..],
+            #[cfg(feature = "ovr_headset_view")]
+            Self::HeadsetView => &sys::IVRHeadsetView_Version[..],
+            #[cfg(feature = "ovr_input")]
+            Self::Input => &sys::IVRInput_Version[..],
+            #[cfg(feature = "ovr_iobuffer")]
+            Self::IoBuffer => &sys::IVRIOBuffer_Version[..],
+            #[cfg(feature = "ovr_notifications")]
+            Self::Notifications => &sys::IVRNotifications_Version[..],
+            #[cfg(feature = "ovr_render_models")]
+            Self::RenderModels => &sys::IVRRenderModels_Version[..],
+            #[cfg(feature = "ovr_resources")]
+            Self::Resources => &sys::IVRResources_Version[..],
+            #[cfg(feature = "ovr_screenshots")]
+            Self::Screenshots => &sys::IVRScreenshots_Version[..],
+            #[cfg(feature = "ovr_settings")]
+            Self::Settings => &sys::IVRSettings_Version[..],
+            #[cfg(feature = "ovr_system")]
+            Self::System => &sys::IVRSystem_Version[..],
+            #[cfg(feature = "ovr_tracked_camera")]
+            Self::TrackedCamera => &sys::IVRTrackedCamera_Version[..],
+        };
+        CStr::from_bytes_with_nul(version).expect("interface versions are nul terminated")
+    }
+
+    /// Looks up the interface in the current OpenVR context. Null if the runtime
+    /// doesn't support it.
+    pub(crate) fn resolve(self) -> *mut c_void {
+        match self {
+            Self::Overlay => sys::VROverlay() as *mut c_void,
+            #[cfg(feature = "ovr_overlay_view")]
+            Self::OverlayView => sys::VROverlayView() as *mut c_void,
+            #[cfg(feature = "ovr_applications")]
+            Self::Applications => sys::VRApplications() as *mut c_void,
+            #[cfg(feature = "ovr_chaperone_setup")]
+            Self::ChaperoneSetup => sys::VRChaperoneSetup() as *mut c_void,
+            #[cfg(feature = "ovr_compositor")]
+            Self::Compositor => sys::VRCompositor() as *mut c_void,
+            #[cfg(feature = "ovr_debug")]
+            Self::Debug => sys::VRDebug() as *mut c_void,
+            #[cfg(feature = "ovr_driver_manager")]
+            Self::DriverManager => sys::VRDriverManager() as *mut c_void,
+            #[cfg(feature = "ovr_extended_display")]
+            Self::ExtendedDisplay => sys::VRExtendedDisplay() as *mut c_void,
+            #[cfg(feature = "ovr_headset_view")]
+            Self::HeadsetView => sys::VRHeadsetView() as *mut c_void,
+            #[cfg(feature = "ovr_input")]
+            Self::Input => sys::VRInput() as *mut c_void,
+            #[cfg(feature = "ovr_iobuffer")]
+            Self::IoBuffer => sys::VRIOBuffer() as *mut c_void,
+            #[cfg(feature = "ovr_notifications")]
+            Self::Notifications => sys::VRNotifications() as *mut c_void,
+            #[cfg(feature = "ovr_render_models")]
+            Self::RenderModels => sys::VRRenderModels() as *mut c_void,
+            #[cfg(feature = "ovr_resources")]
+            Self::Resources => sys::VRResources() as *mut c_void,
+            #[cfg(feature = "ovr_screenshots")]
+            Self::Screenshots => sys::VRScreenshots() as *mut c_void,
+            #[cfg(feature = "ovr_settings")]
+            Self::Settings => sys::VRSettings() as *mut c_void,
+            #[cfg(feature = "ovr_system")]
+            Self::System => sys::VRSystem() as *mut c_void,
+            #[cfg(feature = "ovr_tracked_camera")]
+            Self::TrackedCamera => sys::VRTrackedCamera() as *mut c_void,
+        }
+    }
+
+    /// Returns true if the running OpenVR runtime supports this interface version.
+    pub fn is_available(self) -> bool {
+        unsafe { sys::VR_IsInterfaceVersionValid(self.version().as_ptr()) }
+    }
+}
+
+/// Which interfaces are supported by the running runtime, as returned by
+/// [`crate::Context::check_interface_versions()`].
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct InterfaceVersionReport {
+    pub available: Vec<Interface>,
+    pub unavailable: Vec<Interface>,
+}
+impl InterfaceVersionReport {
+    pub fn is_available(&self, interface: Interface) -> bool {
+        self.available.contains(&interface)
+    }
+
+    pub fn all_available(&self) -> bool {
+        self.unavailable.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discriminants_are_indices() {
+        for (i, interface) in Interface::all().into_iter().enumerate() {
+            assert_eq!(interface as usize, i);
+        }
+    }
+}
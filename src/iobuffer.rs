@@ -0,0 +1,133 @@
+//! Shared memory buffers that drivers and applications can publish and subscribe to,
+//! addressed by paths like `/user/head/eyetracking`.
+
+use crate::{errors::EIOBufferError, sys, Context, Interface, InterfaceLock};
+
+use derive_more::{From, Into};
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+pub struct IoBufferManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVRIOBuffer>,
+    _lock: InterfaceLock<'c>,
+}
+
+type Result<T> = std::result::Result<T, EIOBufferError>;
+
+/// Handle to an open buffer. Close it with [`IoBufferManager::close()`].
+#[derive(From, Into, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[repr(transparent)]
+pub struct IoBufferHandle(pub sys::IOBufferHandle_t);
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum IoBufferMode {
+    /// Subscribe to an existing buffer.
+    Read,
+    /// Publish to an existing buffer.
+    Write,
+    /// Create a new buffer.
+    Create,
+}
+impl From<IoBufferMode> for sys::EIOBufferMode {
+    fn from(mode: IoBufferMode) -> Self {
+        use sys::EIOBufferMode::*;
+        match mode {
+            IoBufferMode::Read => IOBufferMode_Read,
+            IoBufferMode::Write => IOBufferMode_Write,
+            IoBufferMode::Create => IOBufferMode_Create,
+        }
+    }
+}
+
+impl<'c> IoBufferManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::IoBuffer) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+        }
+    }
+
+    /// Opens the buffer at `path`, which holds `elements` elements of `element_size` bytes.
+    ///
+    /// Wraps c++ `Open`.
+    pub fn open(
+        &mut self,
+        path: &str,
+        mode: IoBufferMode,
+        element_size: u32,
+        elements: u32,
+    ) -> Result<IoBufferHandle> {
+        let path = CString::new(path).map_err(|_| {
+            EIOBufferError::new(sys::EIOBufferError::IOBuffer_InvalidArgument).unwrap_err()
+        })?;
+        let mut handle: sys::IOBufferHandle_t = 0;
+        let err = unsafe {
+            self.inner.as_mut().Open(
+                path.as_ptr(),
+                mode.into(),
+                element_size,
+                elements,
+                &mut handle,
+            )
+        };
+        EIOBufferError::new(err)?;
+        Ok(IoBufferHandle(handle))
+    }
+
+    /// Wraps c++ `Close`.
+    pub fn close(&mut self, buffer: IoBufferHandle) -> Result<()> {
+        let err = unsafe { self.inner.as_mut().Close(buffer.0) };
+        EIOBufferError::new(err)
+    }
+
+    /// Reads the latest element into `dst`, returning the number of bytes read.
+    ///
+    /// Wraps c++ `Read`.
+    pub fn read(&mut self, buffer: IoBufferHandle, dst: &mut [u8]) -> Result<usize> {
+        let mut read = 0u32;
+        let err = unsafe {
+            self.inner.as_mut().Read(
+                buffer.0,
+                dst.as_mut_ptr().cast(),
+                dst.len() as u32,
+                &mut read,
+            )
+        };
+        EIOBufferError::new(err)?;
+        Ok(read as usize)
+    }
+
+    /// Publishes `src` as the latest element.
+    ///
+    /// Wraps c++ `Write`.
+    pub fn write(&mut self, buffer: IoBufferHandle, src: &[u8]) -> Result<()> {
+        // OpenVR only reads from the source, despite the mut pointer.
+        let ptr = src.as_ptr() as *mut u8;
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .Write(buffer.0, ptr.cast(), src.len() as u32)
+        };
+        EIOBufferError::new(err)
+    }
+
+    /// Returns the property container of the buffer, for reading metadata that
+    /// the publisher attached to it.
+    ///
+    /// Wraps c++ `PropertyContainer`.
+    pub fn property_container(&mut self, buffer: IoBufferHandle) -> sys::PropertyContainerHandle_t {
+        unsafe { self.inner.as_mut().PropertyContainer(buffer.0) }
+    }
+
+    /// Returns true if anything has the buffer open for reading. Publishers can use
+    /// this to skip producing data nobody consumes.
+    ///
+    /// Wraps c++ `HasReaders`.
+    pub fn has_readers(&mut self, buffer: IoBufferHandle) -> bool {
+        unsafe { self.inner.as_mut().HasReaders(buffer.0) }
+    }
+}
@@ -6,6 +6,11 @@
 pub mod overlay;
 use self::overlay::OverlayManager;
 
+#[cfg(feature = "ovr_overlay_view")]
+pub mod overlay_view;
+#[cfg(feature = "ovr_overlay_view")]
+use self::overlay_view::OverlayViewManager;
+
 pub mod pose;
 
 #[cfg(feature = "ovr_chaperone_setup")]
@@ -18,16 +23,66 @@ pub mod compositor;
 #[cfg(feature = "ovr_compositor")]
 use self::compositor::CompositorManager;
 
+#[cfg(feature = "ovr_debug")]
+pub mod debug;
+#[cfg(feature = "ovr_debug")]
+use self::debug::DebugManager;
+
+#[cfg(feature = "ovr_driver_manager")]
+pub mod driver_manager;
+#[cfg(feature = "ovr_driver_manager")]
+use self::driver_manager::DriverManager;
+
+#[cfg(feature = "ovr_extended_display")]
+pub mod extended_display;
+#[cfg(feature = "ovr_extended_display")]
+use self::extended_display::ExtendedDisplayManager;
+
+#[cfg(feature = "ovr_headset_view")]
+pub mod headset_view;
+#[cfg(feature = "ovr_headset_view")]
+use self::headset_view::HeadsetViewManager;
+
 #[cfg(feature = "ovr_input")]
 pub mod input;
 #[cfg(feature = "ovr_input")]
 use self::input::InputManager;
 
+#[cfg(feature = "ovr_iobuffer")]
+pub mod iobuffer;
+#[cfg(feature = "ovr_iobuffer")]
+use self::iobuffer::IoBufferManager;
+
+#[cfg(feature = "ovr_notifications")]
+pub mod notifications;
+#[cfg(feature = "ovr_notifications")]
+use self::notifications::NotificationsManager;
+
+#[cfg(feature = "ovr_render_models")]
+pub mod render_models;
+#[cfg(feature = "ovr_render_models")]
+use self::render_models::RenderModelsManager;
+
+#[cfg(feature = "ovr_resources")]
+pub mod resources;
+#[cfg(feature = "ovr_resources")]
+use self::resources::ResourcesManager;
+
+#[cfg(feature = "ovr_screenshots")]
+pub mod screenshots;
+#[cfg(feature = "ovr_screenshots")]
+use self::screenshots::ScreenshotManager;
+
 #[cfg(feature = "ovr_system")]
 pub mod system;
 #[cfg(feature = "ovr_system")]
 use self::system::SystemManager;
 
+#[cfg(feature = "ovr_tracked_camera")]
+pub mod tracked_camera;
+#[cfg(feature = "ovr_tracked_camera")]
+use self::tracked_camera::TrackedCameraManager;
+
 #[cfg(feature = "ovr_settings")]
 pub mod settings;
 #[cfg(feature = "ovr_settings")]
@@ -38,15 +93,60 @@ pub mod applications;
 #[cfg(feature = "ovr_applications")]
 use self::applications::ApplicationsManager;
 
+#[cfg(feature = "ovr_system")]
+pub mod battery;
+#[cfg(feature = "ovr_system")]
+pub mod identify;
+#[cfg(feature = "ovr_system")]
+pub mod pose_watcher;
+#[cfg(feature = "ovr_system")]
+pub mod presence;
+#[cfg(feature = "ovr_system")]
+pub mod watchdog;
+
+#[cfg(feature = "egui")]
+pub mod egui_overlay;
+#[cfg(feature = "ash")]
+pub mod vulkan;
+#[cfg(feature = "wgpu")]
+pub mod wgpu_interop;
+
+pub mod animation;
+pub mod color;
+pub mod enums;
 pub mod errors;
+pub mod interfaces;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod paths;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(any(
+    feature = "ovr_applications",
+    feature = "ovr_input",
+    feature = "ovr_render_models",
+    feature = "ovr_system"
+))]
+mod strings;
+pub mod traits;
 
-pub use self::errors::{EVRInitError, InitError};
+#[allow(deprecated)]
+pub use self::color::ColorTint;
+pub use self::color::{Color, ParseColorError};
+pub use self::enums::{ControllerRole, DeviceClass, Eye, Universe};
+pub use self::errors::{EVRInitError, Error, InitError, Result};
+pub use self::interfaces::{Interface, InterfaceVersionReport};
 pub use ovr_overlay_sys as sys;
 
+use bytemuck::{Pod, TransparentWrapper, Zeroable};
 use derive_more::{From, Into};
 use lazy_static::lazy_static;
+use std::cell::Cell;
+use std::ffi::c_void;
 use std::fmt::Debug;
-use std::sync::Mutex;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
 
 lazy_static! {
     // Mutex instead of atomic allows for blocking on lock
@@ -55,14 +155,63 @@ lazy_static! {
 
 /// Represents an active OpenVR context.
 ///
-/// Shutting down this context is unsafe, so if this is dropped, the context will
-/// remain active, as leaking resources is better than accidentally causing unsafe
-/// behavior. To actually shut down, call [`Self::shutdown()`]..
+/// The context is `Send` and `Sync`, so it can be shared or cloned across threads,
+/// and managers can be obtained on each thread. Each manager holds a lock on its
+/// interface for as long as it is alive, so a given interface is only ever used from
+/// one thread at a time. Obtaining a second manager for the same interface while the
+/// first is still alive therefore blocks, or panics if done on the same thread, since
+/// that could never succeed.
+///
+/// Shutting down this context is unsafe while any managers are still alive, so if
+/// this is dropped, the context will remain active, as leaking resources is better
+/// than accidentally causing unsafe behavior. To actually shut down, call
+/// [`Self::shutdown()`]. Afterwards, a new context may be created with [`Self::init()`].
 #[derive(Clone)]
-pub struct Context {}
+pub struct Context {
+    // One slot per interface, indexed by `Interface`. Shared by all clones, which
+    // also lets `shutdown` tell if it has the last one.
+    interfaces: Arc<Vec<InterfaceSlot>>,
+}
+
+/// Whether applications of this type can only run with a headset.
+pub(crate) fn needs_hmd(application_type: &sys::EVRApplicationType) -> bool {
+    *application_type != sys::EVRApplicationType::VRApplication_Background
+        && *application_type != sys::EVRApplicationType::VRApplication_Utility
+}
+
+/// An interface pointer, resolved once when the context is created, and the lock
+/// that managers hold while using it.
+struct InterfaceSlot {
+    lock: Mutex<()>,
+    ptr: *mut c_void,
+}
+// SAFETY: `ptr` is only dereferenced by managers holding `lock`, and the interfaces
+// themselves may be called from any thread.
+unsafe impl Send for InterfaceSlot {}
+unsafe impl Sync for InterfaceSlot {}
+
+thread_local! {
+    // The interfaces locked by managers on this thread, as bits indexed by `Interface`.
+    static HELD: Cell<u64> = Cell::new(0);
+}
+
+/// The lock a manager holds on its interface.
+pub(crate) struct InterfaceLock<'c> {
+    bit: u64,
+    _guard: MutexGuard<'c, ()>,
+}
+
+impl Drop for InterfaceLock<'_> {
+    fn drop(&mut self) {
+        HELD.with(|held| held.set(held.get() & !self.bit));
+    }
+}
 impl Context {
-    pub fn init(application_type: sys::EVRApplicationType) -> Result<Self, InitError> {
-        if let Ok(guard) = INITIALIZED.try_lock() {
+    /// Initializes OpenVR as the given type of application. Accepts either an
+    /// [`ApplicationType`] or the raw [`sys::EVRApplicationType`].
+    pub fn init(application_type: impl Into<sys::EVRApplicationType>) -> Result<Self, InitError> {
+        let application_type = application_type.into();
+        if let Ok(mut guard) = INITIALIZED.try_lock() {
             if *guard {
                 return Err(InitError::AlreadyInitialized);
             }
@@ -72,38 +221,226 @@ impl Context {
                 err.assume_init()
             };
             EVRInitError::new(err)?;
-            Ok(Self {})
+            *guard = true;
+            let interfaces = Interface::all()
+                .into_iter()
+                .map(|interface| InterfaceSlot {
+                    lock: Mutex::new(()),
+                    ptr: interface.resolve(),
+                })
+                .collect();
+            Ok(Self {
+                interfaces: Arc::new(interfaces),
+            })
         } else {
             Err(InitError::AlreadyInitialized)
         }
     }
 
-    // TODO: Is this actually unsafe?
+    /// Like [`Self::init()`], but if SteamVR or the HMD isn't available yet, keeps
+    /// retrying every `poll_interval` until it is, or until `timeout` elapses.
+    /// A `timeout` of `None` waits forever.
+    pub fn init_when_available(
+        application_type: impl Into<sys::EVRApplicationType>,
+        poll_interval: Duration,
+        timeout: Option<Duration>,
+    ) -> Result<Self, InitError> {
+        let application_type = application_type.into();
+        let deadline = timeout.map(|t| Instant::now() + t);
+        loop {
+            if let Some(result) = Self::poll_init(application_type.clone(), poll_interval, deadline)
+            {
+                return result;
+            }
+            std::thread::sleep(poll_interval);
+        }
+    }
+
+    /// Async version of [`Self::init_when_available()`], which sleeps on the tokio timer.
+    #[cfg(feature = "async")]
+    pub async fn init_when_available_async(
+        application_type: impl Into<sys::EVRApplicationType>,
+        poll_interval: Duration,
+        timeout: Option<Duration>,
+    ) -> Result<Self, InitError> {
+        let application_type = application_type.into();
+        let deadline = timeout.map(|t| Instant::now() + t);
+        loop {
+            if let Some(result) = Self::poll_init(application_type.clone(), poll_interval, deadline)
+            {
+                return result;
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// One attempt of the retry loop of [`Self::init_when_available()`]. Returns `None`
+    /// if the caller should wait `poll_interval` and try again, or the final result
+    /// once init succeeded, failed for good, or waiting would pass `deadline`.
+    fn poll_init(
+        application_type: sys::EVRApplicationType,
+        poll_interval: Duration,
+        deadline: Option<Instant>,
+    ) -> Option<Result<Self, InitError>> {
+        if let Some(result) = Self::try_init_available(application_type) {
+            return Some(result);
+        }
+        if deadline.map_or(false, |d| Instant::now() + poll_interval > d) {
+            return Some(Err(InitError::TimedOut));
+        }
+        None
+    }
+
+    /// Attempts to init once. Returns `None` if the failure means SteamVR just
+    /// isn't available yet, and it is worth trying again later.
+    pub(crate) fn try_init_available(
+        application_type: sys::EVRApplicationType,
+    ) -> Option<Result<Self, InitError>> {
+        if needs_hmd(&application_type) && !unsafe { sys::VR_IsHmdPresent() } {
+            return None;
+        }
+        match Self::init(application_type) {
+            Err(InitError::Sys(err)) if err.is_not_yet_available() => None,
+            result => Some(result),
+        }
+    }
+
+    /// Shuts down OpenVR. This is only possible once every other clone of this
+    /// context has been dropped, which guarantees that no managers are still alive.
+    /// Otherwise, the context is handed back.
+    pub fn shutdown(self) -> Result<(), Self> {
+        if Arc::strong_count(&self.interfaces) != 1 {
+            return Err(self);
+        }
+        unsafe { self.shutdown_unchecked() };
+        Ok(())
+    }
+
+    /// Shuts down OpenVR without checking for other clones of this context.
+    ///
     /// # Safety
+    /// No managers obtained from any clone of this context may be used afterwards.
     /// see <https://docs.rs/openvr/latest/openvr/struct.Context.html#safety>
-    pub unsafe fn shutdown(&self) {
-        sys::VR_Shutdown()
+    pub unsafe fn shutdown_unchecked(&self) {
+        let mut guard = INITIALIZED.lock().unwrap_or_else(|e| e.into_inner());
+        sys::VR_Shutdown();
+        *guard = false;
+    }
+
+    /// Locks `interface` for the lifetime of a manager, and returns its pointer.
+    ///
+    /// # Panics
+    /// If a manager for `interface` is still alive on this thread, instead of
+    /// deadlocking.
+    ///
+    /// # Safety
+    /// `T` must be the sys type of `interface`.
+    pub(crate) unsafe fn acquire<T>(
+        &self,
+        interface: Interface,
+    ) -> (Pin<&mut T>, InterfaceLock<'_>) {
+        let slot = &self.interfaces[interface as usize];
+        let bit = 1 << interface as usize;
+        if HELD.with(|held| held.get() & bit != 0) {
+            panic!("a manager for {interface:?} is already alive on this thread");
+        }
+        // The lock guards no data, so poisoning is irrelevant.
+        let guard = slot.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        HELD.with(|held| held.set(held.get() | bit));
+        let lock = InterfaceLock { bit, _guard: guard };
+        let inner = (slot.ptr as *mut T)
+            .as_mut()
+            .unwrap_or_else(|| panic!("{interface:?} is not supported by the runtime"));
+        (Pin::new_unchecked(inner), lock)
+    }
+
+    /// Checks which of the interfaces enabled by this crate's features are supported
+    /// by the running runtime. Managers for unavailable interfaces must not be used,
+    /// so that features relying on them can be disabled instead.
+    pub fn check_interface_versions(&self) -> InterfaceVersionReport {
+        let (available, unavailable) = Interface::all()
+            .into_iter()
+            .partition(|interface| interface.is_available());
+        InterfaceVersionReport {
+            available,
+            unavailable,
+        }
     }
 
     pub fn overlay_mngr(&self) -> OverlayManager<'_> {
         OverlayManager::new(self)
     }
 
+    #[cfg(feature = "ovr_overlay_view")]
+    pub fn overlay_view_mngr(&self) -> OverlayViewManager<'_> {
+        OverlayViewManager::new(self)
+    }
+
     #[cfg(feature = "ovr_chaperone_setup")]
     pub fn chaperone_setup_mngr(&self) -> ChaperoneSetupManager<'_> {
         ChaperoneSetupManager::new(self)
     }
 
+    #[cfg(feature = "ovr_debug")]
+    pub fn debug_mngr(&self) -> DebugManager<'_> {
+        DebugManager::new(self)
+    }
+
+    #[cfg(feature = "ovr_driver_manager")]
+    pub fn driver_mngr(&self) -> DriverManager<'_> {
+        DriverManager::new(self)
+    }
+
+    #[cfg(feature = "ovr_extended_display")]
+    pub fn extended_display_mngr(&self) -> ExtendedDisplayManager<'_> {
+        ExtendedDisplayManager::new(self)
+    }
+
+    #[cfg(feature = "ovr_headset_view")]
+    pub fn headset_view_mngr(&self) -> HeadsetViewManager<'_> {
+        HeadsetViewManager::new(self)
+    }
+
     #[cfg(feature = "ovr_input")]
     pub fn input_mngr(&self) -> InputManager<'_> {
         InputManager::new(self)
     }
 
+    #[cfg(feature = "ovr_iobuffer")]
+    pub fn iobuffer_mngr(&self) -> IoBufferManager<'_> {
+        IoBufferManager::new(self)
+    }
+
+    #[cfg(feature = "ovr_notifications")]
+    pub fn notifications_mngr(&self) -> NotificationsManager<'_> {
+        NotificationsManager::new(self)
+    }
+
+    #[cfg(feature = "ovr_render_models")]
+    pub fn render_models_mngr(&self) -> RenderModelsManager<'_> {
+        RenderModelsManager::new(self)
+    }
+
+    #[cfg(feature = "ovr_resources")]
+    pub fn resources_mngr(&self) -> ResourcesManager<'_> {
+        ResourcesManager::new(self)
+    }
+
+    #[cfg(feature = "ovr_screenshots")]
+    pub fn screenshot_mngr(&self) -> ScreenshotManager<'_> {
+        ScreenshotManager::new(self)
+    }
+
     #[cfg(feature = "ovr_system")]
     pub fn system_mngr(&self) -> SystemManager<'_> {
         SystemManager::new(self)
     }
 
+    #[cfg(feature = "ovr_tracked_camera")]
+    pub fn tracked_camera_mngr(&self) -> TrackedCameraManager<'_> {
+        TrackedCameraManager::new(self)
+    }
+
     #[cfg(feature = "ovr_settings")]
     pub fn settings_mngr(&self) -> SettingsManager<'_> {
         SettingsManager::new(self)
@@ -120,26 +457,37 @@ impl Context {
     }
 }
 
-/// Tints each color channel by multiplying it with the given f32
-#[derive(Clone, Copy, PartialEq, Debug)]
-pub struct ColorTint {
-    pub r: f32,
-    pub g: f32,
-    pub b: f32,
-    pub a: f32,
+/// The kind of application to initialize OpenVR as, which decides how SteamVR
+/// treats the process.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ApplicationType {
+    /// A 3D application that submits frames to the compositor.
+    Scene,
+    /// An application that only draws overlays. Starts SteamVR if it isn't running.
+    Overlay,
+    /// An application that doesn't draw anything, and won't start SteamVR.
+    /// Initialization fails if SteamVR isn't already running.
+    Background,
+    /// A setup or settings tool that may run without an HMD connected.
+    Utility,
+    /// Reserved for vrmonitor.
+    VRMonitor,
 }
-impl Default for ColorTint {
-    fn default() -> Self {
-        Self {
-            r: 1.,
-            g: 1.,
-            b: 1.,
-            a: 1.,
+impl From<ApplicationType> for sys::EVRApplicationType {
+    fn from(ty: ApplicationType) -> Self {
+        use sys::EVRApplicationType::*;
+        match ty {
+            ApplicationType::Scene => VRApplication_Scene,
+            ApplicationType::Overlay => VRApplication_Overlay,
+            ApplicationType::Background => VRApplication_Background,
+            ApplicationType::Utility => VRApplication_Utility,
+            ApplicationType::VRMonitor => VRApplication_VRMonitor,
         }
     }
 }
 
-#[derive(From, Into)]
+#[derive(From, Into, TransparentWrapper)]
+#[repr(transparent)]
 pub struct TextureBounds(pub sys::VRTextureBounds_t);
 impl Clone for TextureBounds {
     fn clone(&self) -> Self {
@@ -162,7 +510,7 @@ impl Debug for TextureBounds {
     }
 }
 
-#[derive(Clone, Copy, From, Into)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, From, Into, Pod, Zeroable)]
 #[repr(transparent)]
 pub struct TrackedDeviceIndex(pub sys::TrackedDeviceIndex_t);
 impl TrackedDeviceIndex {
@@ -189,6 +537,41 @@ impl TrackedDeviceIndex {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    /// A context whose interfaces all point at `value`, without initializing OpenVR.
+    fn fake_context(value: &mut u32) -> Context {
+        let slots = Interface::all()
+            .into_iter()
+            .map(|_| InterfaceSlot {
+                lock: Mutex::new(()),
+                ptr: value as *mut u32 as *mut c_void,
+            })
+            .collect();
+        Context {
+            interfaces: Arc::new(slots),
+        }
+    }
+
+    #[test]
+    fn test_acquire_again_after_drop() {
+        let mut value = 0;
+        let ctx = fake_context(&mut value);
+        let (_, lock) = unsafe { ctx.acquire::<u32>(Interface::Overlay) };
+        drop(lock);
+        let (inner, _lock) = unsafe { ctx.acquire::<u32>(Interface::Overlay) };
+        assert_eq!(*inner, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "already alive on this thread")]
+    fn test_acquire_twice_panics() {
+        let mut value = 0;
+        let ctx = fake_context(&mut value);
+        let _first = unsafe { ctx.acquire::<u32>(Interface::Overlay) };
+        let _second = unsafe { ctx.acquire::<u32>(Interface::Overlay) };
+    }
+
     #[test]
     fn ensure_testing_optional_features() {
         macro_rules! helper {
@@ -197,6 +580,21 @@ mod tests {
             };
         }
 
-        helper!("nalgebra");
+        helper!(
+            "nalgebra",
+            "glam",
+            "manifest",
+            "serde",
+            "image",
+            "async",
+            "context-errors",
+            "wgpu",
+            "ash",
+            "egui",
+            "egui-wgpu",
+            "mock",
+            "rodio",
+            "replay"
+        );
     }
 }
@@ -0,0 +1,639 @@
+//! In-memory fakes implementing the traits of [`crate::traits`], for testing logic
+//! without a running SteamVR.
+//!
+//! The fakes keep just enough state to answer their own queries: tests set up devices,
+//! action states and events, run the code under test, and inspect the state it left.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::errors::EVROverlayError;
+use crate::overlay::OverlayHandle;
+use crate::pose::{Matrix3x4, TrackingUniverseOrigin};
+use crate::traits::Overlay;
+use crate::{sys, TrackedDeviceIndex};
+
+/// Where a [`MockOverlay`] overlay was last placed.
+#[derive(Clone)]
+pub enum MockTransform {
+    Absolute(TrackingUniverseOrigin, Matrix3x4),
+    TrackedDeviceRelative(TrackedDeviceIndex, Matrix3x4),
+}
+
+/// The state of an overlay created through [`MockOverlay`].
+#[derive(Clone)]
+pub struct MockOverlayState {
+    pub key: String,
+    pub friendly_name: String,
+    pub visible: bool,
+    pub width: f32,
+    pub alpha: f32,
+    pub transform: Option<MockTransform>,
+    #[cfg(feature = "ovr_system")]
+    events: VecDeque<crate::system::VREvent>,
+}
+
+/// Fake of [`crate::overlay::OverlayManager`].
+#[derive(Default)]
+pub struct MockOverlay {
+    next_handle: sys::VROverlayHandle_t,
+    overlays: HashMap<sys::VROverlayHandle_t, MockOverlayState>,
+}
+
+impl MockOverlay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn overlay(&self, overlay: OverlayHandle) -> Option<&MockOverlayState> {
+        self.overlays.get(&overlay.0)
+    }
+
+    pub fn overlay_mut(&mut self, overlay: OverlayHandle) -> Option<&mut MockOverlayState> {
+        self.overlays.get_mut(&overlay.0)
+    }
+
+    /// Queues an event to be returned by [`Overlay::poll_next_overlay_event()`].
+    #[cfg(feature = "ovr_system")]
+    pub fn push_overlay_event(
+        &mut self,
+        overlay: OverlayHandle,
+        event: crate::system::VREvent,
+    ) -> Result<(), EVROverlayError> {
+        self.state(overlay)?.events.push_back(event);
+        Ok(())
+    }
+
+    #[track_caller]
+    fn state(&mut self, overlay: OverlayHandle) -> Result<&mut MockOverlayState, EVROverlayError> {
+        match self.overlays.get_mut(&overlay.0) {
+            Some(state) => Ok(state),
+            None => Err(overlay_error(
+                sys::EVROverlayError::VROverlayError_UnknownOverlay,
+            )),
+        }
+    }
+}
+
+#[track_caller]
+fn overlay_error(err: sys::EVROverlayError) -> EVROverlayError {
+    EVROverlayError::new(err).unwrap_err()
+}
+
+impl Overlay for MockOverlay {
+    fn create_overlay(
+        &mut self,
+        key: &str,
+        friendly_name: &str,
+    ) -> Result<OverlayHandle, EVROverlayError> {
+        if self.overlays.values().any(|o| o.key == key) {
+            return Err(overlay_error(sys::EVROverlayError::VROverlayError_KeyInUse));
+        }
+        self.next_handle += 1;
+        self.overlays.insert(
+            self.next_handle,
+            MockOverlayState {
+                key: key.to_owned(),
+                friendly_name: friendly_name.to_owned(),
+                visible: false,
+                width: 1.0,
+                alpha: 1.0,
+                transform: None,
+                #[cfg(feature = "ovr_system")]
+                events: VecDeque::new(),
+            },
+        );
+        Ok(OverlayHandle(self.next_handle))
+    }
+
+    fn destroy_overlay(&mut self, overlay: OverlayHandle) -> Result<(), EVROverlayError> {
+        match self.overlays.remove(&overlay.0) {
+            Some(_) => Ok(()),
+            None => Err(overlay_error(
+                sys::EVROverlayError::VROverlayError_UnknownOverlay,
+            )),
+        }
+    }
+
+    fn set_visibility(
+        &mut self,
+        overlay: OverlayHandle,
+        is_visible: bool,
+    ) -> Result<(), EVROverlayError> {
+        self.state(overlay)?.visible = is_visible;
+        Ok(())
+    }
+
+    fn is_visible(&mut self, overlay: OverlayHandle) -> bool {
+        self.overlay(overlay).is_some_and(|o| o.visible)
+    }
+
+    fn set_opacity(&mut self, overlay: OverlayHandle, alpha: f32) -> Result<(), EVROverlayError> {
+        self.state(overlay)?.alpha = alpha;
+        Ok(())
+    }
+
+    fn opacity(&mut self, overlay: OverlayHandle) -> Result<f32, EVROverlayError> {
+        Ok(self.state(overlay)?.alpha)
+    }
+
+    fn set_width(
+        &mut self,
+        overlay: OverlayHandle,
+        width_in_meters: f32,
+    ) -> Result<(), EVROverlayError> {
+        self.state(overlay)?.width = width_in_meters;
+        Ok(())
+    }
+
+    fn width(&mut self, overlay: OverlayHandle) -> Result<f32, EVROverlayError> {
+        Ok(self.state(overlay)?.width)
+    }
+
+    fn set_transform_absolute(
+        &mut self,
+        overlay: OverlayHandle,
+        origin: TrackingUniverseOrigin,
+        origin_to_overlay: &Matrix3x4,
+    ) -> Result<(), EVROverlayError> {
+        self.state(overlay)?.transform = Some(MockTransform::Absolute(origin, *origin_to_overlay));
+        Ok(())
+    }
+
+    fn set_transform_tracked_device_relative(
+        &mut self,
+        overlay: OverlayHandle,
+        index: TrackedDeviceIndex,
+        device_to_overlay: &Matrix3x4,
+    ) -> Result<(), EVROverlayError> {
+        self.state(overlay)?.transform = Some(MockTransform::TrackedDeviceRelative(
+            index,
+            *device_to_overlay,
+        ));
+        Ok(())
+    }
+
+    fn get_transform_tracked_device_relative(
+        &mut self,
+        overlay: OverlayHandle,
+        device_to_overlay: &mut Matrix3x4,
+    ) -> Result<TrackedDeviceIndex, EVROverlayError> {
+        match &self.state(overlay)?.transform {
+            Some(MockTransform::TrackedDeviceRelative(index, transform)) => {
+                *device_to_overlay = *transform;
+                Ok(*index)
+            }
+            _ => Err(overlay_error(
+                sys::EVROverlayError::VROverlayError_WrongTransformType,
+            )),
+        }
+    }
+
+    #[cfg(feature = "ovr_system")]
+    fn poll_next_overlay_event(
+        &mut self,
+        overlay: OverlayHandle,
+    ) -> Option<crate::system::VREvent> {
+        self.overlays.get_mut(&overlay.0)?.events.pop_front()
+    }
+}
+
+#[cfg(feature = "ovr_system")]
+pub use self::system::{MockDevice, MockSystem};
+#[cfg(feature = "ovr_system")]
+mod system {
+    use super::*;
+    use crate::errors::ETrackedPropertyError;
+    use crate::system::{PropRequest, PropValue, VREvent};
+    use crate::traits::System;
+
+    use std::time::Duration;
+
+    /// A tracked device of [`MockSystem`].
+    #[derive(Clone)]
+    pub struct MockDevice {
+        pub class: sys::ETrackedDeviceClass,
+        pub role: sys::ETrackedControllerRole,
+        pub connected: bool,
+        pub activity_level: sys::EDeviceActivityLevel,
+        /// Reported for every tracking universe origin.
+        pub pose: Matrix3x4,
+        pub properties: Vec<(sys::ETrackedDeviceProperty, PropValue)>,
+    }
+
+    impl MockDevice {
+        pub fn new(class: sys::ETrackedDeviceClass) -> Self {
+            Self {
+                class,
+                role: sys::ETrackedControllerRole::TrackedControllerRole_Invalid,
+                connected: true,
+                activity_level: sys::EDeviceActivityLevel::k_EDeviceActivityLevel_UserInteraction,
+                pose: Matrix3x4::IDENTITY,
+                properties: Vec::new(),
+            }
+        }
+
+        pub fn with_role(mut self, role: sys::ETrackedControllerRole) -> Self {
+            self.role = role;
+            self
+        }
+
+        pub fn with_property(
+            mut self,
+            prop: sys::ETrackedDeviceProperty,
+            value: PropValue,
+        ) -> Self {
+            self.properties.push((prop, value));
+            self
+        }
+    }
+
+    /// Fake of [`crate::system::SystemManager`].
+    #[derive(Default)]
+    pub struct MockSystem {
+        devices: Vec<Option<MockDevice>>,
+        events: VecDeque<VREvent>,
+        haptic_pulses: Vec<(TrackedDeviceIndex, u32, Duration)>,
+    }
+
+    impl MockSystem {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        pub fn set_device(&mut self, index: TrackedDeviceIndex, device: MockDevice) {
+            let i = index.0 as usize;
+            if self.devices.len() <= i {
+                self.devices.resize(i + 1, None);
+            }
+            self.devices[i] = Some(device);
+        }
+
+        pub fn remove_device(&mut self, index: TrackedDeviceIndex) -> Option<MockDevice> {
+            self.devices.get_mut(index.0 as usize)?.take()
+        }
+
+        pub fn device(&self, index: TrackedDeviceIndex) -> Option<&MockDevice> {
+            self.devices.get(index.0 as usize)?.as_ref()
+        }
+
+        pub fn device_mut(&mut self, index: TrackedDeviceIndex) -> Option<&mut MockDevice> {
+            self.devices.get_mut(index.0 as usize)?.as_mut()
+        }
+
+        /// Queues an event to be returned by [`System::poll_next_event()`].
+        pub fn push_event(&mut self, event: VREvent) {
+            self.events.push_back(event);
+        }
+
+        /// The device, axis and duration of every haptic pulse triggered so far.
+        pub fn haptic_pulses(&self) -> &[(TrackedDeviceIndex, u32, Duration)] {
+            &self.haptic_pulses
+        }
+
+        fn get_property(
+            &self,
+            index: TrackedDeviceIndex,
+            request: &PropRequest,
+        ) -> Result<PropValue, ETrackedPropertyError> {
+            use sys::ETrackedPropertyError::*;
+            let Some(device) = self.device(index) else {
+                return Err(prop_error(TrackedProp_InvalidDevice));
+            };
+            let prop = match request {
+                PropRequest::Bool(prop)
+                | PropRequest::Float(prop)
+                | PropRequest::Int32(prop)
+                | PropRequest::Uint64(prop)
+                | PropRequest::String(prop) => prop,
+            };
+            let Some((_, value)) = device.properties.iter().find(|(p, _)| p == prop) else {
+                return Err(prop_error(TrackedProp_UnknownProperty));
+            };
+            match (request, value) {
+                (PropRequest::Bool(_), PropValue::Bool(_))
+                | (PropRequest::Float(_), PropValue::Float(_))
+                | (PropRequest::Int32(_), PropValue::Int32(_))
+                | (PropRequest::Uint64(_), PropValue::Uint64(_))
+                | (PropRequest::String(_), PropValue::String(_)) => Ok(value.clone()),
+                _ => Err(prop_error(TrackedProp_WrongDataType)),
+            }
+        }
+    }
+
+    #[track_caller]
+    fn prop_error(err: sys::ETrackedPropertyError) -> ETrackedPropertyError {
+        ETrackedPropertyError::new(err).unwrap_err()
+    }
+
+    impl System for MockSystem {
+        fn poll_next_event(&mut self) -> Option<VREvent> {
+            self.events.pop_front()
+        }
+
+        fn get_tracked_device_class(
+            &mut self,
+            index: TrackedDeviceIndex,
+        ) -> sys::ETrackedDeviceClass {
+            match self.device(index) {
+                Some(device) => device.class.clone(),
+                None => sys::ETrackedDeviceClass::TrackedDeviceClass_Invalid,
+            }
+        }
+
+        fn is_tracked_device_connected(&mut self, index: TrackedDeviceIndex) -> bool {
+            self.device(index).is_some_and(|d| d.connected)
+        }
+
+        fn get_tracked_device_activity_level(
+            &mut self,
+            index: TrackedDeviceIndex,
+        ) -> sys::EDeviceActivityLevel {
+            match self.device(index) {
+                Some(device) => device.activity_level.clone(),
+                None => sys::EDeviceActivityLevel::k_EDeviceActivityLevel_Unknown,
+            }
+        }
+
+        fn get_controller_role_for_tracked_device_index(
+            &mut self,
+            index: TrackedDeviceIndex,
+        ) -> sys::ETrackedControllerRole {
+            match self.device(index) {
+                Some(device) => device.role.clone(),
+                None => sys::ETrackedControllerRole::TrackedControllerRole_Invalid,
+            }
+        }
+
+        fn get_tracked_device_index_for_controller_role(
+            &mut self,
+            role: sys::ETrackedControllerRole,
+        ) -> Option<TrackedDeviceIndex> {
+            self.devices
+                .iter()
+                .position(|d| d.as_ref().is_some_and(|d| d.connected && d.role == role))
+                .map(|i| TrackedDeviceIndex(i as u32))
+        }
+
+        fn get_device_to_absolute_tracking_pose_into(
+            &mut self,
+            _origin: TrackingUniverseOrigin,
+            _predicted_seconds_to_photons_from_now: f32,
+            poses: &mut [sys::TrackedDevicePose_t],
+        ) {
+            for (i, pose) in poses.iter_mut().enumerate() {
+                *pose = unsafe { std::mem::zeroed() };
+                let Some(Some(device)) = self.devices.get(i) else {
+                    continue;
+                };
+                pose.mDeviceToAbsoluteTracking = device.pose.into();
+                pose.bPoseIsValid = device.connected;
+                pose.bDeviceIsConnected = device.connected;
+                pose.eTrackingResult = sys::ETrackingResult::TrackingResult_Running_OK;
+            }
+        }
+
+        fn hmd_pose(&mut self, _origin: TrackingUniverseOrigin) -> Option<Matrix3x4> {
+            match self.devices.get(TrackedDeviceIndex::HMD.0 as usize) {
+                Some(Some(device)) if device.connected => Some(device.pose),
+                _ => None,
+            }
+        }
+
+        fn get_properties(
+            &mut self,
+            index: TrackedDeviceIndex,
+            requests: &[PropRequest],
+        ) -> Vec<Result<PropValue, ETrackedPropertyError>> {
+            requests
+                .iter()
+                .map(|request| self.get_property(index, request))
+                .collect()
+        }
+
+        fn read_any(
+            &mut self,
+            index: TrackedDeviceIndex,
+            request: &PropRequest,
+        ) -> Result<PropValue, ETrackedPropertyError> {
+            self.get_property(index, request)
+        }
+
+        fn trigger_haptic_pulse(
+            &mut self,
+            index: TrackedDeviceIndex,
+            axis_id: u32,
+            duration: Duration,
+        ) {
+            self.haptic_pulses.push((index, axis_id, duration));
+        }
+    }
+}
+
+#[cfg(feature = "ovr_input")]
+pub use self::input::MockInput;
+#[cfg(feature = "ovr_input")]
+mod input {
+    use super::*;
+    use crate::errors::EVRInputError;
+    use crate::input::{
+        ActionHandle, ActionSetHandle, ActiveActionSet, AnalogActionData, DigitalActionData,
+        InputValueHandle, PoseActionData,
+    };
+    use crate::traits::Input;
+
+    /// A value as set by the test, and as seen after the last two `update_actions` calls.
+    #[derive(Default, Clone, Copy)]
+    struct Latched<T> {
+        pending: T,
+        current: T,
+        previous: T,
+    }
+
+    impl<T: Copy> Latched<T> {
+        fn update(&mut self) {
+            self.previous = self.current;
+            self.current = self.pending;
+        }
+    }
+
+    /// Fake of [`crate::input::InputManager`].
+    ///
+    /// Like OpenVR, handles are handed out for any name. States set with
+    /// [`set_digital()`](Self::set_digital), [`set_analog()`](Self::set_analog) and
+    /// [`set_pose()`](Self::set_pose) become visible on the next
+    /// [`Input::update_actions()`].
+    #[derive(Default)]
+    pub struct MockInput {
+        handles: HashMap<String, u64>,
+        digital: HashMap<sys::VRActionHandle_t, Latched<bool>>,
+        analog: HashMap<sys::VRActionHandle_t, Latched<[f32; 3]>>,
+        poses: HashMap<sys::VRActionHandle_t, Latched<Option<Matrix3x4>>>,
+        update_time: f32,
+    }
+
+    impl MockInput {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        fn handle(&mut self, name: &str) -> u64 {
+            let next = self.handles.len() as u64 + 1;
+            *self.handles.entry(name.to_owned()).or_insert(next)
+        }
+
+        pub fn set_digital(&mut self, action: &str, state: bool) {
+            let handle = self.handle(action);
+            self.digital.entry(handle).or_default().pending = state;
+        }
+
+        pub fn set_analog(&mut self, action: &str, value: [f32; 3]) {
+            let handle = self.handle(action);
+            self.analog.entry(handle).or_default().pending = value;
+        }
+
+        /// Sets the pose of a pose action, reported for every tracking universe origin.
+        /// `None` makes the pose invalid.
+        pub fn set_pose(&mut self, action: &str, pose: Option<Matrix3x4>) {
+            let handle = self.handle(action);
+            self.poses.entry(handle).or_default().pending = pose;
+        }
+
+        #[track_caller]
+        fn check_handle(&self, handle: u64) -> Result<(), EVRInputError> {
+            if self.handles.values().any(|&h| h == handle) {
+                Ok(())
+            } else {
+                EVRInputError::new(sys::EVRInputError::VRInputError_InvalidHandle)
+            }
+        }
+    }
+
+    impl Input for MockInput {
+        fn get_action_set_handle(&mut self, name: &str) -> Result<ActionSetHandle, EVRInputError> {
+            Ok(ActionSetHandle(self.handle(name)))
+        }
+
+        fn get_action_handle(&mut self, name: &str) -> Result<ActionHandle, EVRInputError> {
+            Ok(ActionHandle::from(self.handle(name)))
+        }
+
+        fn update_actions(&mut self, sets: &mut [ActiveActionSet]) -> Result<(), EVRInputError> {
+            if sets.is_empty() {
+                return EVRInputError::new(sys::EVRInputError::VRInputError_NoActiveActionSet);
+            }
+            self.digital.values_mut().for_each(Latched::update);
+            self.analog.values_mut().for_each(Latched::update);
+            self.poses.values_mut().for_each(Latched::update);
+            self.update_time += 1.0 / 90.0;
+            Ok(())
+        }
+
+        fn get_digital_action_data(
+            &mut self,
+            action: ActionHandle,
+            _restrict: InputValueHandle,
+        ) -> Result<DigitalActionData, EVRInputError> {
+            let handle = sys::VRActionHandle_t::from(action);
+            self.check_handle(handle)?;
+            let mut data: sys::InputDigitalActionData_t = unsafe { std::mem::zeroed() };
+            if let Some(state) = self.digital.get(&handle) {
+                data.bActive = true;
+                data.bState = state.current;
+                data.bChanged = state.current != state.previous;
+                data.fUpdateTime = -self.update_time;
+            }
+            Ok(DigitalActionData(data))
+        }
+
+        fn get_analog_action_data(
+            &mut self,
+            action: ActionHandle,
+            _restrict: InputValueHandle,
+        ) -> Result<AnalogActionData, EVRInputError> {
+            let handle = sys::VRActionHandle_t::from(action);
+            self.check_handle(handle)?;
+            let mut data: sys::InputAnalogActionData_t = unsafe { std::mem::zeroed() };
+            if let Some(state) = self.analog.get(&handle) {
+                let [x, y, z] = state.current;
+                let [px, py, pz] = state.previous;
+                data.bActive = true;
+                (data.x, data.y, data.z) = (x, y, z);
+                (data.deltaX, data.deltaY, data.deltaZ) = (x - px, y - py, z - pz);
+                data.fUpdateTime = -self.update_time;
+            }
+            Ok(AnalogActionData(data))
+        }
+
+        fn get_pose_action_data_relative_to_now(
+            &mut self,
+            action: ActionHandle,
+            _universe: TrackingUniverseOrigin,
+            _seconds_from_now: f32,
+            _restrict: InputValueHandle,
+        ) -> Result<PoseActionData, EVRInputError> {
+            let handle = sys::VRActionHandle_t::from(action);
+            self.check_handle(handle)?;
+            let mut data: sys::InputPoseActionData_t = unsafe { std::mem::zeroed() };
+            if let Some(state) = self.poses.get(&handle) {
+                data.bActive = true;
+                if let Some(pose) = state.current {
+                    data.pose.mDeviceToAbsoluteTracking = pose.into();
+                    data.pose.bPoseIsValid = true;
+                    data.pose.bDeviceIsConnected = true;
+                    data.pose.eTrackingResult = sys::ETrackingResult::TrackingResult_Running_OK;
+                }
+            }
+            Ok(PoseActionData(data))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_overlay_state() {
+        let mut mngr = MockOverlay::new();
+        let overlay = mngr.create_overlay("test.key", "Test").unwrap();
+        assert!(mngr.create_overlay("test.key", "Test").is_err());
+        mngr.set_visibility(overlay, true).unwrap();
+        mngr.set_width(overlay, 0.5).unwrap();
+        assert!(mngr.is_visible(overlay));
+        assert_eq!(mngr.width(overlay).unwrap(), 0.5);
+        mngr.destroy_overlay(overlay).unwrap();
+        assert!(!mngr.is_visible(overlay));
+        assert!(mngr.opacity(overlay).is_err());
+    }
+
+    #[cfg(feature = "ovr_input")]
+    #[test]
+    fn test_input_changed() {
+        use crate::input::InputValueHandle;
+        use crate::traits::Input;
+
+        let mut mngr = MockInput::new();
+        let set = mngr.get_action_set_handle("/actions/main").unwrap();
+        let click = mngr.get_action_handle("/actions/main/in/click").unwrap();
+        let mut sets = [crate::input::ActiveActionSet(unsafe { std::mem::zeroed() })];
+        sets[0].0.ulActionSet = set.0;
+
+        mngr.set_digital("/actions/main/in/click", true);
+        let data = mngr
+            .get_digital_action_data(click, InputValueHandle(0))
+            .unwrap();
+        assert!(!data.0.bState);
+
+        mngr.update_actions(&mut sets).unwrap();
+        let data = mngr
+            .get_digital_action_data(click, InputValueHandle(0))
+            .unwrap();
+        assert!(data.0.bState && data.0.bChanged);
+
+        mngr.update_actions(&mut sets).unwrap();
+        let data = mngr
+            .get_digital_action_data(click, InputValueHandle(0))
+            .unwrap();
+        assert!(data.0.bState && !data.0.bChanged);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::overlay::OverlayHandle;
+use crate::{errors::EVRNotificationError, sys, Context, Interface, InterfaceLock};
+
+use derive_more::{From, Into};
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+pub mod notifier;
+
+pub use sys::EVRNotificationStyle as NotificationStyle;
+pub use sys::EVRNotificationType as NotificationType;
+
+pub struct NotificationsManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVRNotifications>,
+    _lock: InterfaceLock<'c>,
+}
+
+type Result<T> = std::result::Result<T, EVRNotificationError>;
+
+#[derive(From, Into, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[repr(transparent)]
+pub struct NotificationId(pub sys::VRNotificationId);
+
+/// An RGBA8 image used as the icon of a notification.
+#[derive(Debug, Clone, Copy)]
+pub struct NotificationBitmap<'a> {
+    data: &'a [u8],
+    width: u32,
+    height: u32,
+}
+impl<'a> NotificationBitmap<'a> {
+    const BYTES_PER_PIXEL: usize = 4;
+
+    /// Wraps tightly packed RGBA8 pixel data. Returns `None` if `data` is not
+    /// exactly `width * height * 4` bytes long.
+    pub fn from_rgba(data: &'a [u8], width: u32, height: u32) -> Option<Self> {
+        let len = (width as usize)
+            .checked_mul(height as usize)?
+            .checked_mul(Self::BYTES_PER_PIXEL)?;
+        if data.len() != len || width > i32::MAX as u32 || height > i32::MAX as u32 {
+            return None;
+        }
+        Some(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn as_sys(&self) -> sys::NotificationBitmap_t {
+        sys::NotificationBitmap_t {
+            // OpenVR only reads from the image data, despite the mut pointer.
+            m_pImageData: self.data.as_ptr() as *mut std::ffi::c_void as *mut _,
+            m_nWidth: self.width as i32,
+            m_nHeight: self.height as i32,
+            m_nBytesPerPixel: Self::BYTES_PER_PIXEL as i32,
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl<'a> From<&'a image::RgbaImage> for NotificationBitmap<'a> {
+    fn from(img: &'a image::RgbaImage) -> Self {
+        Self {
+            data: img.as_raw(),
+            width: img.width(),
+            height: img.height(),
+        }
+    }
+}
+
+impl<'c> NotificationsManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::Notifications) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+        }
+    }
+
+    /// Shows a notification attached to `overlay`, optionally with an icon. Fails with
+    /// `InvalidNotificationId` if `text` contains a nul byte, as there is no error for
+    /// invalid text.
+    ///
+    /// Wraps c++ `CreateNotification`.
+    pub fn create_notification(
+        &mut self,
+        overlay: OverlayHandle,
+        user_value: u64,
+        ty: NotificationType,
+        text: &str,
+        style: NotificationStyle,
+        icon: Option<&NotificationBitmap>,
+    ) -> Result<NotificationId> {
+        let text = CString::new(text).map_err(|_| {
+            EVRNotificationError::new(
+                sys::EVRNotificationError::VRNotificationError_InvalidNotificationId,
+            )
+            .unwrap_err()
+        })?;
+        let icon = icon.map(|icon| icon.as_sys());
+        let icon_ptr = icon
+            .as_ref()
+            .map(|icon| icon as *const sys::NotificationBitmap_t)
+            .unwrap_or(std::ptr::null());
+        let mut id: sys::VRNotificationId = 0;
+        let err = unsafe {
+            self.inner.as_mut().CreateNotification(
+                overlay.0,
+                user_value,
+                ty,
+                text.as_ptr(),
+                style,
+                icon_ptr,
+                &mut id,
+            )
+        };
+        EVRNotificationError::new(err)?;
+        Ok(NotificationId(id))
+    }
+
+    /// Like [`Self::create_notification()`], but with an icon from the `image` crate,
+    /// converted to RGBA8 if needed.
+    #[cfg(feature = "image")]
+    pub fn create_notification_with_image(
+        &mut self,
+        overlay: OverlayHandle,
+        user_value: u64,
+        ty: NotificationType,
+        text: &str,
+        style: NotificationStyle,
+        icon: &image::DynamicImage,
+    ) -> Result<NotificationId> {
+        let icon = crate::overlay::rgba8(icon);
+        let icon = NotificationBitmap::from(&*icon);
+        self.create_notification(overlay, user_value, ty, text, style, Some(&icon))
+    }
+
+    /// Wraps c++ `RemoveNotification`.
+    pub fn remove_notification(&mut self, id: NotificationId) -> Result<()> {
+        let err = unsafe { self.inner.as_mut().RemoveNotification(id.0) };
+        EVRNotificationError::new(err)
+    }
+}
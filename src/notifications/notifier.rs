@@ -0,0 +1,263 @@
+//! Queues notifications so they are shown one at a time, skips duplicates, and removes
+//! them again once they expire, so applications can't spam the dashboard or leak
+//! notification ids.
+//!
+//! ```no_run
+//! # use ovr_overlay::notifications::notifier::Notifier;
+//! # use ovr_overlay::notifications::NotificationsManager;
+//! # use ovr_overlay::overlay::OverlayHandle;
+//! # fn example(mngr: &mut NotificationsManager, overlay: OverlayHandle) {
+//! let mut notifier = Notifier::new(overlay);
+//! notifier.notify("Tracker 3 is low on battery");
+//! // Doesn't show again while the first one is queued or shown.
+//! notifier.notify("Tracker 3 is low on battery");
+//! loop {
+//!     notifier.update(mngr).unwrap();
+//! }
+//! # }
+//! ```
+
+use super::{NotificationId, NotificationStyle, NotificationType, NotificationsManager};
+use crate::errors::EVRNotificationError;
+use crate::overlay::OverlayHandle;
+use crate::sys;
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+struct Pending {
+    text: String,
+    ty: NotificationType,
+    style: NotificationStyle,
+}
+
+struct Shown {
+    id: NotificationId,
+    text: String,
+    expires: Instant,
+}
+
+/// Shows queued notifications on an overlay, at most one every `min_interval`.
+pub struct Notifier {
+    overlay: OverlayHandle,
+    min_interval: Duration,
+    lifetime: Duration,
+    max_queued: usize,
+    queue: VecDeque<Pending>,
+    shown: Vec<Shown>,
+    last_shown: Option<Instant>,
+}
+
+impl Notifier {
+    /// Defaults to one notification every 2 seconds, each removed after 5 seconds, and
+    /// at most 10 queued.
+    pub fn new(overlay: OverlayHandle) -> Self {
+        Self {
+            overlay,
+            min_interval: Duration::from_secs(2),
+            lifetime: Duration::from_secs(5),
+            max_queued: 10,
+            queue: VecDeque::new(),
+            shown: Vec::new(),
+            last_shown: None,
+        }
+    }
+
+    /// The shortest time between two notifications being shown.
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// How long notifications stay before they are removed.
+    pub fn with_lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    /// How many notifications can wait to be shown. Further ones are dropped.
+    pub fn with_max_queued(mut self, max_queued: usize) -> Self {
+        self.max_queued = max_queued;
+        self
+    }
+
+    /// Queues a transient notification without a style. See [`Self::notify_with()`].
+    pub fn notify(&mut self, text: impl Into<String>) -> bool {
+        self.notify_with(
+            text,
+            NotificationType::EVRNotificationType_Transient,
+            NotificationStyle::EVRNotificationStyle_None,
+        )
+    }
+
+    /// Queues a notification. Returns `false` if it was dropped, because one with the
+    /// same text is already queued or shown, because the queue is full, or because the
+    /// text contains a nul byte and can't be shown.
+    pub fn notify_with(
+        &mut self,
+        text: impl Into<String>,
+        ty: NotificationType,
+        style: NotificationStyle,
+    ) -> bool {
+        let text = text.into();
+        if text.contains('\0') {
+            return false;
+        }
+        let duplicate = self.queue.iter().any(|pending| pending.text == text)
+            || self.shown.iter().any(|shown| shown.text == text);
+        if duplicate || self.queue.len() >= self.max_queued {
+            return false;
+        }
+        self.queue.push_back(Pending { text, ty, style });
+        true
+    }
+
+    /// How many notifications are waiting to be shown.
+    pub fn queued(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Removes expired notifications and shows the next queued one if it is due. Call
+    /// this regularly, like every frame.
+    ///
+    /// Notifications that fail to be removed are tried again on the next call.
+    pub fn update(&mut self, mngr: &mut NotificationsManager) -> Result<(), EVRNotificationError> {
+        let now = Instant::now();
+        let expired = self.take_expired(now);
+        let removed = self.remove(mngr, expired);
+        if let Some(pending) = self.pop_due(now) {
+            let id = mngr.create_notification(
+                self.overlay,
+                0,
+                pending.ty,
+                &pending.text,
+                pending.style,
+                None,
+            )?;
+            self.shown.push(Shown {
+                id,
+                text: pending.text,
+                expires: now + self.lifetime,
+            });
+        }
+        removed
+    }
+
+    /// Removes all shown notifications and empties the queue, like before exiting.
+    ///
+    /// Notifications that fail to be removed are kept, so this can be called again.
+    pub fn clear(&mut self, mngr: &mut NotificationsManager) -> Result<(), EVRNotificationError> {
+        self.queue.clear();
+        let shown = std::mem::take(&mut self.shown);
+        self.remove(mngr, shown)
+    }
+
+    /// Removes every notification in `shown`, putting back the ones that fail to be
+    /// removed. Returns the first error.
+    fn remove(
+        &mut self,
+        mngr: &mut NotificationsManager,
+        shown: Vec<Shown>,
+    ) -> Result<(), EVRNotificationError> {
+        let mut result = Ok(());
+        for shown in shown {
+            let Err(err) = mngr.remove_notification(shown.id) else {
+                continue;
+            };
+            // Already gone, like after SteamVR restarted.
+            if err.inner() == sys::EVRNotificationError::VRNotificationError_InvalidNotificationId {
+                continue;
+            }
+            self.shown.push(shown);
+            if result.is_ok() {
+                result = Err(err);
+            }
+        }
+        result
+    }
+
+    fn take_expired(&mut self, now: Instant) -> Vec<Shown> {
+        let (expired, shown): (Vec<_>, Vec<_>) = std::mem::take(&mut self.shown)
+            .into_iter()
+            .partition(|shown| shown.expires <= now);
+        self.shown = shown;
+        expired
+    }
+
+    fn pop_due(&mut self, now: Instant) -> Option<Pending> {
+        let due = !self
+            .last_shown
+            .is_some_and(|last| now.saturating_duration_since(last) < self.min_interval);
+        if !due {
+            return None;
+        }
+        let pending = self.queue.pop_front()?;
+        self.last_shown = Some(now);
+        Some(pending)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn show(notifier: &mut Notifier, now: Instant, id: u32) -> Option<String> {
+        let pending = notifier.pop_due(now)?;
+        notifier.shown.push(Shown {
+            id: NotificationId(id),
+            text: pending.text.clone(),
+            expires: now + notifier.lifetime,
+        });
+        Some(pending.text)
+    }
+
+    fn expired_ids(notifier: &mut Notifier, now: Instant) -> Vec<NotificationId> {
+        notifier
+            .take_expired(now)
+            .iter()
+            .map(|shown| shown.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_rate_limit_and_expiry() {
+        let mut notifier = Notifier::new(OverlayHandle(1))
+            .with_min_interval(Duration::from_secs(1))
+            .with_lifetime(Duration::from_secs(3));
+        let t0 = Instant::now();
+        let secs = |s| t0 + Duration::from_secs(s);
+        assert!(notifier.notify("a"));
+        assert!(notifier.notify("b"));
+
+        assert_eq!(show(&mut notifier, secs(0), 1).as_deref(), Some("a"));
+        assert_eq!(show(&mut notifier, secs(0), 2), None);
+        assert_eq!(show(&mut notifier, secs(1), 2).as_deref(), Some("b"));
+        assert_eq!(show(&mut notifier, secs(2), 3), None);
+
+        assert!(expired_ids(&mut notifier, secs(2)).is_empty());
+        assert_eq!(expired_ids(&mut notifier, secs(3)), [NotificationId(1)]);
+        assert_eq!(expired_ids(&mut notifier, secs(4)), [NotificationId(2)]);
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut notifier = Notifier::new(OverlayHandle(1)).with_max_queued(2);
+        let now = Instant::now();
+        assert!(notifier.notify("a"));
+        assert!(!notifier.notify("a"));
+        assert!(!notifier.notify("nul\0"));
+        show(&mut notifier, now, 1);
+        // Still shown.
+        assert!(!notifier.notify("a"));
+
+        assert!(notifier.notify("b"));
+        assert!(notifier.notify("c"));
+        assert!(!notifier.notify("d"));
+        assert_eq!(notifier.queued(), 2);
+
+        let later = now + notifier.lifetime;
+        notifier.take_expired(later);
+        assert_eq!(show(&mut notifier, later, 2).as_deref(), Some("b"));
+        assert!(notifier.notify("a"));
+    }
+}
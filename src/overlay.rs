@@ -1,24 +1,44 @@
 pub use crate::errors::EVROverlayError;
 use crate::pose::Matrix3x4;
+use crate::pose::Ray;
 use crate::pose::TrackingUniverseOrigin;
 use crate::TextureBounds;
-use crate::{sys, ColorTint, Context, TrackedDeviceIndex};
+use crate::{sys, Color, Context, Interface, InterfaceLock, TrackedDeviceIndex};
 
 use derive_more::From;
+use std::ffi::CString;
 use std::marker::PhantomData;
 use std::pin::Pin;
 use sys::VRVulkanTextureData_t;
 
+pub mod batch;
+#[cfg(feature = "ovr_system")]
+pub mod dashboard;
+pub mod double_buffer;
+#[cfg(feature = "ovr_system")]
+pub mod follow;
+#[cfg(feature = "ovr_system")]
+pub mod layout;
+#[cfg(feature = "ovr_input")]
+pub mod pointer;
+pub mod stacking;
+#[cfg(feature = "ovr_system")]
+pub mod state;
+#[cfg(all(feature = "ovr_input", feature = "ovr_system"))]
+pub mod watch;
+
 pub struct OverlayManager<'c> {
     ctx: PhantomData<&'c Context>,
     inner: Pin<&'c mut sys::IVROverlay>,
+    _lock: InterfaceLock<'c>,
 }
 impl<'c> OverlayManager<'c> {
-    pub(super) fn new(_ctx: &'c Context) -> Self {
-        let inner = unsafe { Pin::new_unchecked(sys::VROverlay().as_mut::<'c>().unwrap()) };
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::Overlay) };
         Self {
             ctx: Default::default(),
             inner,
+            _lock: lock,
         }
     }
 
@@ -40,6 +60,33 @@ impl<'c> OverlayManager<'c> {
         Ok(OverlayHandle(handle))
     }
 
+    /// Creates an overlay shown as a tab in the dashboard, returning it and the
+    /// thumbnail overlay used as the tab's icon.
+    pub fn create_dashboard_overlay(
+        &mut self,
+        key: &str,
+        friendly_name: &str,
+    ) -> Result<(OverlayHandle, OverlayHandle), EVROverlayError> {
+        let (Ok(key), Ok(friendly_name)) = (CString::new(key), CString::new(friendly_name)) else {
+            return Err(EVROverlayError::new(
+                sys::EVROverlayError::VROverlayError_InvalidParameter,
+            )
+            .unwrap_err());
+        };
+        let mut main = sys::VROverlayHandle_t::default();
+        let mut thumbnail = sys::VROverlayHandle_t::default();
+        let err = unsafe {
+            self.inner.as_mut().CreateDashboardOverlay(
+                key.as_ptr(),
+                friendly_name.as_ptr(),
+                &mut main,
+                &mut thumbnail,
+            )
+        };
+        EVROverlayError::new(err)?;
+        Ok((OverlayHandle(main), OverlayHandle(thumbnail)))
+    }
+
     pub fn destroy_overlay(&mut self, overlay: OverlayHandle) -> Result<(), EVROverlayError> {
         let err = unsafe { self.inner.as_mut().DestroyOverlay(overlay.0) };
         EVROverlayError::new(err)
@@ -163,8 +210,8 @@ impl<'c> OverlayManager<'c> {
         EVROverlayError::new(err)
     }
 
-    pub fn tint(&mut self, overlay: OverlayHandle) -> Result<ColorTint, EVROverlayError> {
-        let mut tint = ColorTint::default();
+    pub fn tint(&mut self, overlay: OverlayHandle) -> Result<Color, EVROverlayError> {
+        let mut tint = Color::default();
         unsafe {
             let err = self.inner.as_mut().GetOverlayColor(
                 overlay.0,
@@ -182,8 +229,9 @@ impl<'c> OverlayManager<'c> {
     pub fn set_tint(
         &mut self,
         overlay: OverlayHandle,
-        tint: ColorTint,
+        tint: impl Into<Color>,
     ) -> Result<(), EVROverlayError> {
+        let tint = tint.into();
         unsafe {
             let err = self
                 .inner
@@ -270,6 +318,27 @@ impl<'c> OverlayManager<'c> {
         EVROverlayError::new(err)
     }
 
+    /// Sets the contents of `overlay` to `image`, uploaded from the CPU. Images that
+    /// aren't already RGBA8 are converted first.
+    ///
+    /// This is meant for small, rarely changing images such as icons; use a GPU
+    /// texture for anything updated every frame.
+    #[cfg(feature = "image")]
+    pub fn set_overlay_image(
+        &mut self,
+        overlay: OverlayHandle,
+        image: &image::DynamicImage,
+    ) -> Result<(), EVROverlayError> {
+        let image = rgba8(image);
+        self.set_raw_data(
+            overlay,
+            image.as_raw(),
+            image.width() as usize,
+            image.height() as usize,
+            4,
+        )
+    }
+
     /// Get aspect ratio, with aspect expressed as width / height.
     pub fn texel_aspect(&mut self, overlay: OverlayHandle) -> Result<f32, EVROverlayError> {
         let mut aspect = 0.0;
@@ -427,6 +496,103 @@ impl<'c> OverlayManager<'c> {
         EVROverlayError::new(err)
     }
 
+    /// Sets whether the laser pointer can interact with `overlay`, generating mouse
+    /// events for it.
+    pub fn set_input_method(
+        &mut self,
+        overlay: OverlayHandle,
+        method: sys::VROverlayInputMethod,
+    ) -> Result<(), EVROverlayError> {
+        let err = unsafe { self.inner.as_mut().SetOverlayInputMethod(overlay.0, method) };
+        EVROverlayError::new(err)
+    }
+
+    /// Sets the size of the coordinate space that mouse events for `overlay` are
+    /// reported in. Usually the texture size in pixels.
+    pub fn set_mouse_scale(
+        &mut self,
+        overlay: OverlayHandle,
+        width: f32,
+        height: f32,
+    ) -> Result<(), EVROverlayError> {
+        let scale = sys::HmdVector2_t { v: [width, height] };
+        let err = unsafe { self.inner.as_mut().SetOverlayMouseScale(overlay.0, &scale) };
+        EVROverlayError::new(err)
+    }
+
+    pub fn mouse_scale(&mut self, overlay: OverlayHandle) -> Result<[f32; 2], EVROverlayError> {
+        let mut scale = sys::HmdVector2_t { v: [0.; 2] };
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .GetOverlayMouseScale(overlay.0, &mut scale)
+        };
+        EVROverlayError::new(err)?;
+        Ok(scale.v)
+    }
+
+    pub fn set_flag(
+        &mut self,
+        overlay: OverlayHandle,
+        flag: sys::VROverlayFlags,
+        enabled: bool,
+    ) -> Result<(), EVROverlayError> {
+        let err = unsafe { self.inner.as_mut().SetOverlayFlag(overlay.0, flag, enabled) };
+        EVROverlayError::new(err)
+    }
+
+    /// Returns the next event sent to `overlay`, such as mouse input, if there is one.
+    #[cfg(feature = "ovr_system")]
+    pub fn poll_next_overlay_event(
+        &mut self,
+        overlay: OverlayHandle,
+    ) -> Option<crate::system::VREvent> {
+        let mut event = std::mem::MaybeUninit::uninit();
+        let res = unsafe {
+            self.inner.as_mut().PollNextOverlayEvent(
+                overlay.0,
+                event.as_mut_ptr(),
+                std::mem::size_of::<sys::VREvent_t>() as u32,
+            )
+        };
+        if !res {
+            return None;
+        }
+        let event = unsafe { event.assume_init() };
+        Some(crate::system::VREvent::parse(event))
+    }
+
+    /// Where `ray` hits `overlay`, if it does. `origin` is the tracking universe `ray` is in.
+    pub fn compute_overlay_intersection(
+        &mut self,
+        overlay: OverlayHandle,
+        origin: TrackingUniverseOrigin,
+        ray: &Ray,
+    ) -> Option<OverlayIntersection> {
+        let params = sys::VROverlayIntersectionParams_t {
+            vSource: sys::HmdVector3_t { v: ray.origin },
+            vDirection: sys::HmdVector3_t { v: ray.direction },
+            eOrigin: origin,
+        };
+        let mut results: sys::VROverlayIntersectionResults_t = unsafe { std::mem::zeroed() };
+        let hit = unsafe {
+            self.inner
+                .as_mut()
+                .ComputeOverlayIntersection(overlay.0, &params, &mut results)
+        };
+        hit.then(|| OverlayIntersection {
+            point: results.vPoint.v,
+            normal: results.vNormal.v,
+            uv: results.vUVs.v,
+            distance: results.fDistance,
+        })
+    }
+
+    /// Whether the overlay is the one the user's laser pointer or mouse is over.
+    pub fn is_hover_target_overlay(&mut self, overlay: OverlayHandle) -> bool {
+        unsafe { self.inner.as_mut().IsHoverTargetOverlay(overlay.0) }
+    }
+
     pub fn is_dashboard_visible(&mut self) -> bool {
         unsafe { self.inner.as_mut().IsDashboardVisible() }
     }
@@ -436,8 +602,25 @@ impl<'c> OverlayManager<'c> {
         EVROverlayError::new(err)
     }
 }
-unsafe impl Send for OverlayManager<'_> {}
-unsafe impl Sync for OverlayManager<'_> {}
 
 #[derive(From, Debug, PartialEq, Eq, Clone, Copy)]
 pub struct OverlayHandle(pub sys::VROverlayHandle_t);
+
+/// Where a ray hits an overlay, see [`OverlayManager::compute_overlay_intersection()`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OverlayIntersection {
+    pub point: [f32; 3],
+    pub normal: [f32; 3],
+    /// Texture coordinates of the hit, with the origin at the bottom left.
+    pub uv: [f32; 2],
+    pub distance: f32,
+}
+
+/// Borrows `image` if it is already RGBA8, and converts it otherwise.
+#[cfg(feature = "image")]
+pub(crate) fn rgba8(image: &image::DynamicImage) -> std::borrow::Cow<'_, image::RgbaImage> {
+    match image.as_rgba8() {
+        Some(image) => std::borrow::Cow::Borrowed(image),
+        None => std::borrow::Cow::Owned(image.to_rgba8()),
+    }
+}
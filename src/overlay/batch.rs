@@ -0,0 +1,135 @@
+//! Per-frame updates of many overlays in one call.
+//!
+//! ```no_run
+//! # use ovr_overlay::overlay::{OverlayHandle, OverlayManager};
+//! # use ovr_overlay::overlay::batch::{OverlayUpdate, TextureSource};
+//! # fn example(mngr: &mut OverlayManager, a: OverlayHandle, b: OverlayHandle) {
+//! let updates = [
+//!     (a, OverlayUpdate::texture(TextureSource::OpenGl(1))),
+//!     (b, OverlayUpdate { alpha: Some(0.5), ..Default::default() }),
+//! ];
+//! if let Err(errors) = mngr.submit_batch(&updates) {
+//!     for (overlay, err) in errors {
+//!         eprintln!("failed to update {overlay:?}: {err}");
+//!     }
+//! }
+//! # }
+//! ```
+
+use super::{OverlayHandle, OverlayManager};
+use crate::errors::EVROverlayError;
+use crate::pose::{Matrix3x4, TrackingUniverseOrigin};
+use crate::sys;
+
+use std::ffi::CStr;
+
+/// Where an [`OverlayUpdate`] gets the overlay's new contents from.
+#[derive(Default)]
+pub enum TextureSource<'a> {
+    /// Keep the current contents.
+    #[default]
+    Keep,
+    /// An OpenGL texture name, as with [`OverlayManager::set_image_opengl()`].
+    OpenGl(u32),
+    /// As with [`OverlayManager::set_image_vulkan()`].
+    Vulkan(&'a sys::VRVulkanTextureData_t),
+    /// Pixels uploaded from the CPU, as with [`OverlayManager::set_raw_data()`].
+    Raw {
+        data: &'a [u8],
+        width: usize,
+        height: usize,
+        bytes_per_pixel: usize,
+    },
+    /// An image file, as with [`OverlayManager::set_image()`].
+    File(&'a CStr),
+}
+
+/// The changes to one overlay in [`OverlayManager::submit_batch()`]. Fields left as
+/// `None` are not changed.
+#[derive(Default)]
+pub struct OverlayUpdate<'a> {
+    pub texture: TextureSource<'a>,
+    pub alpha: Option<f32>,
+    pub transform: Option<(TrackingUniverseOrigin, Matrix3x4)>,
+    pub visible: Option<bool>,
+}
+
+impl<'a> OverlayUpdate<'a> {
+    /// An update of only the contents.
+    pub fn texture(texture: TextureSource<'a>) -> Self {
+        Self {
+            texture,
+            ..Default::default()
+        }
+    }
+}
+
+impl OverlayManager<'_> {
+    /// Applies `updates` in order, setting the texture, opacity, transform and
+    /// visibility of each overlay.
+    ///
+    /// An error stops the remaining changes to that overlay, but not the updates of the
+    /// other overlays. All errors are returned together, with the overlay they happened
+    /// for.
+    pub fn submit_batch(
+        &mut self,
+        updates: &[(OverlayHandle, OverlayUpdate)],
+    ) -> Result<(), Vec<(OverlayHandle, EVROverlayError)>> {
+        let errors: Vec<_> = updates
+            .iter()
+            .filter_map(|(overlay, update)| {
+                self.apply_update(*overlay, update)
+                    .err()
+                    .map(|err| (*overlay, err))
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn apply_update(
+        &mut self,
+        overlay: OverlayHandle,
+        update: &OverlayUpdate,
+    ) -> Result<(), EVROverlayError> {
+        match update.texture {
+            TextureSource::Keep => {}
+            TextureSource::OpenGl(texture) => self.set_image_opengl(overlay, texture)?,
+            TextureSource::Vulkan(texture) => {
+                // OpenVR only reads the texture data, despite the mutable pointer.
+                let texture = texture as *const sys::VRVulkanTextureData_t;
+                let err = unsafe {
+                    self.inner.as_mut().SetOverlayTexture(
+                        overlay.0,
+                        &sys::Texture_t {
+                            handle: texture as *mut _,
+                            eType: sys::ETextureType::TextureType_Vulkan,
+                            eColorSpace: sys::EColorSpace::ColorSpace_Auto,
+                        },
+                    )
+                };
+                EVROverlayError::new(err)?;
+            }
+            TextureSource::Raw {
+                data,
+                width,
+                height,
+                bytes_per_pixel,
+            } => self.set_raw_data(overlay, data, width, height, bytes_per_pixel)?,
+            TextureSource::File(path) => self.set_image(overlay, path)?,
+        }
+        if let Some(alpha) = update.alpha {
+            self.set_opacity(overlay, alpha)?;
+        }
+        if let Some((origin, transform)) = &update.transform {
+            self.set_transform_absolute(overlay, origin.clone(), transform)?;
+        }
+        if let Some(visible) = update.visible {
+            self.set_visibility(overlay, visible)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+//! A tab in the SteamVR dashboard, tracking whether it is the one being shown so
+//! applications can skip rendering while it isn't.
+//!
+//! ```no_run
+//! # use ovr_overlay::overlay::OverlayManager;
+//! # use ovr_overlay::overlay::dashboard::DashboardTab;
+//! # fn example(mngr: &mut OverlayManager) {
+//! let mut tab = DashboardTab::new(mngr, "com.example.tab", "Example").unwrap();
+//! loop {
+//!     for event in tab.poll_events(mngr) {
+//!         // Handle mouse input...
+//!     }
+//!     if tab.is_active() {
+//!         // Render and submit a frame...
+//!     }
+//! }
+//! # }
+//! ```
+
+use super::{OverlayHandle, OverlayManager};
+use crate::errors::EVROverlayError;
+use crate::sys;
+use crate::system::VREvent;
+
+use std::ffi::CString;
+use std::path::Path;
+
+pub struct DashboardTab {
+    overlay: OverlayHandle,
+    thumbnail: OverlayHandle,
+    dashboard_open: bool,
+    shown: bool,
+}
+
+impl DashboardTab {
+    pub fn new(
+        mngr: &mut OverlayManager,
+        key: &str,
+        friendly_name: &str,
+    ) -> Result<Self, EVROverlayError> {
+        let (overlay, thumbnail) = mngr.create_dashboard_overlay(key, friendly_name)?;
+        Ok(Self {
+            overlay,
+            thumbnail,
+            dashboard_open: mngr.is_dashboard_visible(),
+            shown: mngr.is_visible(overlay),
+        })
+    }
+
+    /// The overlay shown while the tab is selected.
+    pub fn overlay(&self) -> OverlayHandle {
+        self.overlay
+    }
+
+    /// The overlay shown as the tab's icon.
+    pub fn thumbnail(&self) -> OverlayHandle {
+        self.thumbnail
+    }
+
+    /// Loads the tab's icon from an image file.
+    pub fn set_icon(&self, mngr: &mut OverlayManager, path: &Path) -> Result<(), EVROverlayError> {
+        let Some(path) = path.to_str().and_then(|path| CString::new(path).ok()) else {
+            return EVROverlayError::new(sys::EVROverlayError::VROverlayError_InvalidParameter);
+        };
+        mngr.set_image(self.thumbnail, &path)
+    }
+
+    #[cfg(feature = "image")]
+    pub fn set_icon_image(
+        &self,
+        mngr: &mut OverlayManager,
+        image: &image::DynamicImage,
+    ) -> Result<(), EVROverlayError> {
+        mngr.set_overlay_image(self.thumbnail, image)
+    }
+
+    /// Whether the dashboard is open with this tab selected.
+    pub fn is_active(&self) -> bool {
+        self.dashboard_open && self.shown
+    }
+
+    /// Updates the tab's state from `event`, returning whether it was relevant.
+    ///
+    /// Pass every event polled from [`OverlayManager::poll_next_overlay_event()`] for
+    /// [`overlay()`](Self::overlay), which [`poll_events()`](Self::poll_events) does, as
+    /// well as the dashboard events from
+    /// [`SystemManager::poll_next_event()`](crate::system::SystemManager::poll_next_event).
+    pub fn handle_event(&mut self, event: &VREvent) -> bool {
+        use sys::EVREventType::*;
+        match event.event_type {
+            VREvent_OverlayShown => self.shown = true,
+            VREvent_OverlayHidden => self.shown = false,
+            VREvent_DashboardActivated => self.dashboard_open = true,
+            VREvent_DashboardDeactivated => self.dashboard_open = false,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Polls and handles every pending event of [`overlay()`](Self::overlay), returning
+    /// them for handling input.
+    pub fn poll_events(&mut self, mngr: &mut OverlayManager) -> Vec<VREvent> {
+        let events: Vec<VREvent> =
+            std::iter::from_fn(|| mngr.poll_next_overlay_event(self.overlay)).collect();
+        for event in &events {
+            self.handle_event(event);
+        }
+        events
+    }
+
+    pub fn destroy(self, mngr: &mut OverlayManager) -> Result<(), EVROverlayError> {
+        mngr.destroy_overlay(self.overlay)
+    }
+}
@@ -0,0 +1,99 @@
+//! Alternates between two textures when submitting an overlay, so rendering the next
+//! frame doesn't stall on (or tear) the texture the compositor is still reading.
+//!
+//! ```no_run
+//! # use ovr_overlay::overlay::{OverlayHandle, OverlayManager};
+//! # use ovr_overlay::overlay::double_buffer::{DoubleBufferedOverlayTexture, GlTexture};
+//! # fn render_into(texture: u32) {}
+//! # fn example(mngr: &mut OverlayManager, overlay: OverlayHandle, a: u32, b: u32) {
+//! let mut textures = DoubleBufferedOverlayTexture::new(GlTexture(a), GlTexture(b));
+//! loop {
+//!     render_into(textures.back().0);
+//!     textures.submit(mngr, overlay).unwrap();
+//! }
+//! # }
+//! ```
+
+use super::{OverlayHandle, OverlayManager};
+use crate::errors::EVROverlayError;
+use crate::sys;
+
+/// A GPU texture that can be submitted as the contents of an overlay.
+pub trait OverlayTexture {
+    fn submit(
+        &mut self,
+        mngr: &mut OverlayManager,
+        overlay: OverlayHandle,
+    ) -> Result<(), EVROverlayError>;
+}
+
+/// An OpenGL texture name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlTexture(pub u32);
+impl OverlayTexture for GlTexture {
+    fn submit(
+        &mut self,
+        mngr: &mut OverlayManager,
+        overlay: OverlayHandle,
+    ) -> Result<(), EVROverlayError> {
+        mngr.set_image_opengl(overlay, self.0)
+    }
+}
+
+impl OverlayTexture for sys::VRVulkanTextureData_t {
+    fn submit(
+        &mut self,
+        mngr: &mut OverlayManager,
+        overlay: OverlayHandle,
+    ) -> Result<(), EVROverlayError> {
+        mngr.set_image_vulkan(overlay, self)
+    }
+}
+
+/// Two textures of the same overlay, one being shown while the other is rendered to.
+pub struct DoubleBufferedOverlayTexture<T> {
+    textures: [T; 2],
+    back: usize,
+}
+
+impl<T: OverlayTexture> DoubleBufferedOverlayTexture<T> {
+    /// `first` is rendered to and submitted first.
+    pub fn new(first: T, second: T) -> Self {
+        Self {
+            textures: [first, second],
+            back: 0,
+        }
+    }
+
+    /// The texture to render the next frame into.
+    pub fn back(&self) -> &T {
+        &self.textures[self.back]
+    }
+
+    pub fn back_mut(&mut self) -> &mut T {
+        &mut self.textures[self.back]
+    }
+
+    /// The texture most recently submitted, which the compositor may still be reading.
+    pub fn front(&self) -> &T {
+        &self.textures[1 - self.back]
+    }
+
+    /// Submits the back texture as the contents of `overlay`, and swaps, so the next
+    /// frame is rendered into the other texture.
+    ///
+    /// If submitting fails, the textures are not swapped.
+    pub fn submit(
+        &mut self,
+        mngr: &mut OverlayManager,
+        overlay: OverlayHandle,
+    ) -> Result<(), EVROverlayError> {
+        self.back_mut().submit(mngr, overlay)?;
+        self.back = 1 - self.back;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> [T; 2] {
+        self.textures
+    }
+}
@@ -0,0 +1,301 @@
+//! Keeps an overlay in front of the user, lazily following the headset.
+//!
+//! Hard-locking an overlay to the headset (as [`Placement::Head`] does) makes it jitter
+//! with every small head movement, which is uncomfortable for anything shown for a
+//! while. [`HeadLocked`] instead leaves the overlay in place until the head moves past a
+//! dead zone, then eases it back in front.
+//!
+//! [`Placement::Head`]: super::layout::Placement::Head
+
+use super::OverlayHandle;
+use crate::errors::EVROverlayError;
+use crate::pose::{Matrix3x4, TrackingUniverseOrigin};
+use crate::traits::{Overlay, System};
+
+use std::time::Duration;
+
+/// Once following, it stops when this close to the target, relative to the dead zone.
+const SETTLE_FRACTION: f32 = 0.1;
+
+pub struct HeadLocked {
+    overlay: OverlayHandle,
+    offset: Matrix3x4,
+    speed: f32,
+    dead_zone_distance: f32,
+    dead_zone_angle: f32,
+    lock_pitch: bool,
+    current: Option<Matrix3x4>,
+    following: bool,
+}
+
+impl HeadLocked {
+    /// `offset` is the overlay's transform relative to the headset, e.g. half a meter
+    /// in front with `Matrix3x4::from_translation([0., 0., -0.5])`.
+    pub fn new(overlay: OverlayHandle, offset: Matrix3x4) -> Self {
+        Self {
+            overlay,
+            offset,
+            speed: 4.,
+            dead_zone_distance: 0.1,
+            dead_zone_angle: 15f32.to_radians(),
+            lock_pitch: false,
+            current: None,
+            following: false,
+        }
+    }
+
+    /// How quickly the overlay catches up, as the fraction of the remaining distance
+    /// covered per second is `1 - e^-speed`. Defaults to 4.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// How far (in meters and radians) the head can move before the overlay follows.
+    /// Defaults to 10cm and 15 degrees; zero for both follows continuously.
+    pub fn with_dead_zone(mut self, distance: f32, angle: f32) -> Self {
+        self.dead_zone_distance = distance;
+        self.dead_zone_angle = angle;
+        self
+    }
+
+    /// Ignores looking up and down, keeping the overlay upright at head height.
+    pub fn with_pitch_locked(mut self, lock_pitch: bool) -> Self {
+        self.lock_pitch = lock_pitch;
+        self
+    }
+
+    pub fn overlay(&self) -> OverlayHandle {
+        self.overlay
+    }
+
+    pub fn set_offset(&mut self, offset: Matrix3x4) {
+        self.offset = offset;
+    }
+
+    /// Makes the next step jump straight to the target, e.g. when the overlay is shown.
+    pub fn snap(&mut self) {
+        self.current = None;
+    }
+
+    /// Where the overlay would be if it were hard-locked to `head`.
+    pub fn target(&self, head: &Matrix3x4) -> Matrix3x4 {
+        let head = if self.lock_pitch {
+            yaw_only(head)
+        } else {
+            *head
+        };
+        head * self.offset
+    }
+
+    /// Advances the smoothing by `dt` seconds with the headset at `head`, returning the
+    /// overlay's new transform.
+    pub fn step(&mut self, head: &Matrix3x4, dt: f32) -> Matrix3x4 {
+        let target = self.target(head);
+        let Some(current) = self.current else {
+            self.current = Some(target);
+            return target;
+        };
+
+        let (distance, angle) = difference(&current, &target);
+        if distance > self.dead_zone_distance || angle > self.dead_zone_angle {
+            self.following = true;
+        } else if distance <= self.dead_zone_distance * SETTLE_FRACTION
+            && angle <= self.dead_zone_angle * SETTLE_FRACTION
+        {
+            self.following = false;
+        }
+        if !self.following {
+            return current;
+        }
+
+        let t = 1. - (-self.speed * dt).exp();
+        let next = interpolate(&current, &target, t);
+        self.current = Some(next);
+        next
+    }
+
+    /// Steps with the current headset pose and moves the overlay, in the standing
+    /// tracking universe. Does nothing while the headset isn't tracking.
+    pub fn update(
+        &mut self,
+        mngr: &mut (impl Overlay + ?Sized),
+        system: &mut (impl System + ?Sized),
+        dt: Duration,
+    ) -> Result<(), EVROverlayError> {
+        let origin = TrackingUniverseOrigin::TrackingUniverseStanding;
+        let Some(head) = system.hmd_pose(origin.clone()) else {
+            return Ok(());
+        };
+        let transform = self.step(&head, dt.as_secs_f32());
+        mngr.set_transform_absolute(self.overlay, origin, &transform)
+    }
+}
+
+/// `m` rotated only around the vertical axis, so its -Z axis stays horizontal.
+fn yaw_only(m: &Matrix3x4) -> Matrix3x4 {
+    let [x, _, z] = m.forward();
+    let len = (x * x + z * z).sqrt();
+    if len < 1e-4 {
+        // Looking straight up or down, the yaw is undefined.
+        return *m;
+    }
+    let (s, c) = (-x / len, -z / len);
+    let [tx, ty, tz] = m.translation();
+    Matrix3x4([[c, 0., s, tx], [0., 1., 0., ty], [-s, 0., c, tz]])
+}
+
+/// The distance between the translations and the angle between the rotations.
+fn difference(a: &Matrix3x4, b: &Matrix3x4) -> (f32, f32) {
+    let [ax, ay, az] = a.translation();
+    let [bx, by, bz] = b.translation();
+    let distance = ((ax - bx).powi(2) + (ay - by).powi(2) + (az - bz).powi(2)).sqrt();
+    let dot = dot(&to_quat(a), &to_quat(b)).abs().min(1.);
+    (distance, 2. * dot.acos())
+}
+
+/// Lerps the translation and slerps the rotation.
+fn interpolate(a: &Matrix3x4, b: &Matrix3x4, t: f32) -> Matrix3x4 {
+    let (ta, tb) = (a.translation(), b.translation());
+    let translation = std::array::from_fn(|i| ta[i] + (tb[i] - ta[i]) * t);
+    from_quat(&slerp(&to_quat(a), &to_quat(b), t), translation)
+}
+
+type Quat = [f32; 4];
+
+fn dot(a: &Quat, b: &Quat) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn normalize(q: Quat) -> Quat {
+    let len = dot(&q, &q).sqrt();
+    q.map(|x| x / len)
+}
+
+/// The rotation of `m` as `[x, y, z, w]`.
+fn to_quat(m: &Matrix3x4) -> Quat {
+    let m = &m.0;
+    let trace = m[0][0] + m[1][1] + m[2][2];
+    let q = if trace > 0. {
+        let s = 0.5 / (trace + 1.).sqrt();
+        [
+            (m[2][1] - m[1][2]) * s,
+            (m[0][2] - m[2][0]) * s,
+            (m[1][0] - m[0][1]) * s,
+            0.25 / s,
+        ]
+    } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+        let s = 2. * (1. + m[0][0] - m[1][1] - m[2][2]).sqrt();
+        [
+            0.25 * s,
+            (m[0][1] + m[1][0]) / s,
+            (m[0][2] + m[2][0]) / s,
+            (m[2][1] - m[1][2]) / s,
+        ]
+    } else if m[1][1] > m[2][2] {
+        let s = 2. * (1. + m[1][1] - m[0][0] - m[2][2]).sqrt();
+        [
+            (m[0][1] + m[1][0]) / s,
+            0.25 * s,
+            (m[1][2] + m[2][1]) / s,
+            (m[0][2] - m[2][0]) / s,
+        ]
+    } else {
+        let s = 2. * (1. + m[2][2] - m[0][0] - m[1][1]).sqrt();
+        [
+            (m[0][2] + m[2][0]) / s,
+            (m[1][2] + m[2][1]) / s,
+            0.25 * s,
+            (m[1][0] - m[0][1]) / s,
+        ]
+    };
+    normalize(q)
+}
+
+fn from_quat(q: &Quat, translation: [f32; 3]) -> Matrix3x4 {
+    let [x, y, z, w] = *q;
+    let [tx, ty, tz] = translation;
+    let r0 = [
+        1. - 2. * (y * y + z * z),
+        2. * (x * y - z * w),
+        2. * (x * z + y * w),
+    ];
+    let r1 = [
+        2. * (x * y + z * w),
+        1. - 2. * (x * x + z * z),
+        2. * (y * z - x * w),
+    ];
+    let r2 = [
+        2. * (x * z - y * w),
+        2. * (y * z + x * w),
+        1. - 2. * (x * x + y * y),
+    ];
+    Matrix3x4([
+        [r0[0], r0[1], r0[2], tx],
+        [r1[0], r1[1], r1[2], ty],
+        [r2[0], r2[1], r2[2], tz],
+    ])
+}
+
+fn slerp(a: &Quat, b: &Quat, t: f32) -> Quat {
+    // Take the short way around.
+    let (b, d) = match dot(a, b) {
+        d if d < 0. => (b.map(|x| -x), -d),
+        d => (*b, d),
+    };
+    if d > 0.9995 {
+        return normalize(std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t));
+    }
+    let theta = d.acos();
+    let (wa, wb) = (
+        ((1. - t) * theta).sin() / theta.sin(),
+        (t * theta).sin() / theta.sin(),
+    );
+    std::array::from_fn(|i| a[i] * wa + b[i] * wb)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn yaw(angle: f32) -> Matrix3x4 {
+        let (s, c) = angle.sin_cos();
+        Matrix3x4([[c, 0., s, 0.], [0., 1., 0., 1.7], [-s, 0., c, 0.]])
+    }
+
+    #[test]
+    fn test_dead_zone_and_follow() {
+        let offset = Matrix3x4::from_translation([0., 0., -1.]);
+        let mut follow = HeadLocked::new(OverlayHandle(1), offset);
+        let start = follow.step(&yaw(0.), 0.);
+        assert_eq!(start.translation(), [0., 1.7, -1.]);
+
+        // Inside the dead zone, the overlay stays put.
+        assert_eq!(follow.step(&yaw(0.05), 0.1), start);
+
+        // Past it, the overlay eases towards the new target without overshooting.
+        let head = yaw(1.);
+        let target = follow.target(&head);
+        let mut last = difference(&start, &target);
+        for _ in 0..200 {
+            let now = difference(&follow.step(&head, 1. / 60.), &target);
+            assert!(now.0 <= last.0 + 1e-4 && now.1 <= last.1 + 1e-4);
+            last = now;
+        }
+        assert!(last.0 <= 0.1 * SETTLE_FRACTION + 1e-4);
+        assert!(last.1 <= 15f32.to_radians() * SETTLE_FRACTION + 1e-4);
+    }
+
+    #[test]
+    fn test_pitch_locked() {
+        let (s, c) = 0.5f32.sin_cos();
+        let pitched = yaw(0.3) * Matrix3x4([[1., 0., 0., 0.], [0., c, -s, 0.], [0., s, c, 0.]]);
+        let follow = HeadLocked::new(OverlayHandle(1), Matrix3x4::from_translation([0., 0., -1.]))
+            .with_pitch_locked(true);
+        let target = follow.target(&pitched);
+        let expected = follow.target(&yaw(0.3));
+        for (a, b) in target.0.iter().flatten().zip(expected.0.iter().flatten()) {
+            assert!((a - b).abs() < 1e-5);
+        }
+    }
+}
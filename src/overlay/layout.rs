@@ -0,0 +1,485 @@
+//! Keeps a set of overlays where the user put them: fixed in the world, following the
+//! headset, or attached to a hand, with grab-and-move repositioning.
+//!
+//! With the `serde` feature, a [`SavedLayout`] can be stored and restored across runs.
+//!
+//! ```no_run
+//! # use ovr_overlay::overlay::OverlayHandle;
+//! # use ovr_overlay::overlay::layout::{Hand, LayoutManager, Placement};
+//! # use ovr_overlay::pose::Matrix3x4;
+//! # use ovr_overlay::traits::{Overlay, System};
+//! # fn example(mngr: &mut (impl Overlay + ?Sized), system: &mut (impl System + ?Sized), overlay: OverlayHandle) {
+//! let mut layout = LayoutManager::new();
+//! let watch = Matrix3x4::from_translation([0., 0.05, 0.1]);
+//! layout.insert("watch", overlay, Placement::Hand(Hand::Left, watch));
+//! layout.apply(mngr, system).unwrap();
+//! # }
+//! ```
+
+use super::OverlayHandle;
+use crate::errors::EVROverlayError;
+use crate::pose::{Matrix3x4, TrackingUniverseOrigin};
+use crate::traits::{Overlay, System};
+use crate::{sys, TrackedDeviceIndex};
+
+use std::collections::HashMap;
+
+/// How far (in meters) from a hand an overlay's center can be to be grabbed.
+pub const DEFAULT_GRAB_DISTANCE: f32 = 0.3;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Hand {
+    Left,
+    Right,
+}
+impl Hand {
+    fn role(self) -> sys::ETrackedControllerRole {
+        match self {
+            Self::Left => sys::ETrackedControllerRole::TrackedControllerRole_LeftHand,
+            Self::Right => sys::ETrackedControllerRole::TrackedControllerRole_RightHand,
+        }
+    }
+}
+
+/// Where an overlay is, as a transform relative to what it is attached to.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Placement {
+    /// Fixed in the standing tracking space.
+    World(Matrix3x4),
+    /// Follows the headset.
+    Head(Matrix3x4),
+    /// Follows a controller.
+    Hand(Hand, Matrix3x4),
+}
+
+/// The placements of a [`LayoutManager`]'s overlays, by key.
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedLayout(pub HashMap<String, Placement>);
+
+#[derive(Debug)]
+struct Entry {
+    handle: OverlayHandle,
+    placement: Placement,
+}
+
+/// An overlay being moved by a hand.
+#[derive(Debug)]
+struct Grab {
+    key: String,
+    hand: Hand,
+}
+
+/// Tracks the placement of a set of overlays, keyed by a name that stays the same
+/// across runs.
+#[derive(Debug)]
+pub struct LayoutManager {
+    entries: HashMap<String, Entry>,
+    grab: Option<Grab>,
+    grab_distance: f32,
+}
+
+impl Default for LayoutManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayoutManager {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            grab: None,
+            grab_distance: DEFAULT_GRAB_DISTANCE,
+        }
+    }
+
+    pub fn set_grab_distance(&mut self, meters: f32) {
+        self.grab_distance = meters;
+    }
+
+    /// Adds `overlay` under `key`, replacing any overlay already there. The placement
+    /// takes effect on the next [`Self::apply()`].
+    pub fn insert(&mut self, key: impl Into<String>, overlay: OverlayHandle, placement: Placement) {
+        self.entries.insert(
+            key.into(),
+            Entry {
+                handle: overlay,
+                placement,
+            },
+        );
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<OverlayHandle> {
+        if self.grab.as_ref().is_some_and(|grab| grab.key == key) {
+            self.grab = None;
+        }
+        self.entries.remove(key).map(|entry| entry.handle)
+    }
+
+    pub fn placement(&self, key: &str) -> Option<Placement> {
+        self.entries.get(key).map(|entry| entry.placement)
+    }
+
+    /// Changes the placement of the overlay under `key`, if there is one. It takes
+    /// effect on the next [`Self::apply()`].
+    pub fn set_placement(&mut self, key: &str, placement: Placement) -> bool {
+        match self.entries.get_mut(key) {
+            Some(entry) => {
+                entry.placement = placement;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The key of the overlay currently being moved, if any.
+    pub fn grabbed(&self) -> Option<&str> {
+        self.grab.as_ref().map(|grab| grab.key.as_str())
+    }
+
+    /// Moves every overlay to its placement. Overlays attached to a device that isn't
+    /// connected are left where they are.
+    pub fn apply(
+        &self,
+        mngr: &mut (impl Overlay + ?Sized),
+        system: &mut (impl System + ?Sized),
+    ) -> Result<(), EVROverlayError> {
+        for (key, entry) in &self.entries {
+            // A grabbed overlay follows the hand until it is released.
+            if self.grab.as_ref().is_some_and(|grab| &grab.key == key) {
+                continue;
+            }
+            apply_placement(mngr, system, entry.handle, &entry.placement)?;
+        }
+        Ok(())
+    }
+
+    /// Starts moving the overlay closest to `hand`, if one is within the grab
+    /// distance. The overlay follows the hand until [`Self::end_grab()`].
+    ///
+    /// Returns the key of the grabbed overlay.
+    pub fn begin_grab(
+        &mut self,
+        mngr: &mut (impl Overlay + ?Sized),
+        system: &mut (impl System + ?Sized),
+        hand: Hand,
+    ) -> Result<Option<&str>, EVROverlayError> {
+        if self.grab.is_some() {
+            return Ok(None);
+        }
+        let poses = Poses::get(system);
+        let Some(hand_pose) = poses.hand(hand) else {
+            return Ok(None);
+        };
+        let hand_pos = hand_pose.translation();
+        let closest = self
+            .entries
+            .iter()
+            .filter_map(|(key, entry)| {
+                let pos = poses.world(&entry.placement)?.translation();
+                let dist_sq: f32 = (0..3).map(|i| (pos[i] - hand_pos[i]).powi(2)).sum();
+                Some((key, dist_sq))
+            })
+            .filter(|&(_, dist_sq)| dist_sq <= self.grab_distance.powi(2))
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((key, _)) = closest else {
+            return Ok(None);
+        };
+
+        let key = key.clone();
+        let entry = self.entries.get_mut(&key).unwrap();
+        // Checked above that the placement resolves.
+        let world = poses.world(&entry.placement).unwrap();
+        let hand_to_overlay = hand_pose.inverse_rigid() * world;
+        // Only the overlay's transform changes, so the kind of placement can be
+        // restored on release.
+        let grabbed = Placement::Hand(hand, hand_to_overlay);
+        apply_placement(mngr, system, entry.handle, &grabbed)?;
+        self.grab = Some(Grab { key, hand });
+        Ok(self.grab.as_ref().map(|grab| grab.key.as_str()))
+    }
+
+    /// Drops the grabbed overlay where it is, keeping its kind of placement.
+    pub fn end_grab(
+        &mut self,
+        mngr: &mut (impl Overlay + ?Sized),
+        system: &mut (impl System + ?Sized),
+    ) -> Result<(), EVROverlayError> {
+        let Some(grab) = self.grab.take() else {
+            return Ok(());
+        };
+        let Some(entry) = self.entries.get_mut(&grab.key) else {
+            return Ok(());
+        };
+        let mut hand_to_overlay = Matrix3x4::IDENTITY;
+        mngr.get_transform_tracked_device_relative(entry.handle, &mut hand_to_overlay)?;
+        let poses = Poses::get(system);
+        if let Some(hand_pose) = poses.hand(grab.hand) {
+            let world = hand_pose * hand_to_overlay;
+            let placement = match entry.placement {
+                Placement::World(_) => Some(Placement::World(world)),
+                Placement::Head(_) => poses
+                    .hmd()
+                    .map(|hmd| Placement::Head(hmd.inverse_rigid() * world)),
+                Placement::Hand(hand, _) => poses
+                    .hand(hand)
+                    .map(|pose| Placement::Hand(hand, pose.inverse_rigid() * world)),
+            };
+            // If the device it belongs to was lost, it goes back to where it was.
+            if let Some(placement) = placement {
+                entry.placement = placement;
+            }
+        }
+        apply_placement(mngr, system, entry.handle, &entry.placement)
+    }
+
+    /// Grabs on press and releases on release of a digital action, such as the grip.
+    #[cfg(feature = "ovr_input")]
+    pub fn handle_grab_action(
+        &mut self,
+        mngr: &mut (impl Overlay + ?Sized),
+        system: &mut (impl System + ?Sized),
+        hand: Hand,
+        action: &crate::input::DigitalActionData,
+    ) -> Result<(), EVROverlayError> {
+        let action = &action.0;
+        if !action.bActive || !action.bChanged {
+            return Ok(());
+        }
+        if action.bState {
+            self.begin_grab(mngr, system, hand)?;
+        } else if self.grab.as_ref().is_some_and(|grab| grab.hand == hand) {
+            self.end_grab(mngr, system)?;
+        }
+        Ok(())
+    }
+
+    /// The placement of every overlay. An overlay that is being grabbed is saved where
+    /// it was before the grab.
+    pub fn save(&self) -> SavedLayout {
+        SavedLayout(
+            self.entries
+                .iter()
+                .map(|(key, entry)| (key.clone(), entry.placement))
+                .collect(),
+        )
+    }
+
+    /// Sets the placement of every overlay that is in `layout`, ignoring keys that
+    /// aren't. Takes effect on the next [`Self::apply()`].
+    pub fn restore(&mut self, layout: &SavedLayout) {
+        for (key, placement) in &layout.0 {
+            self.set_placement(key, *placement);
+        }
+    }
+}
+
+fn apply_placement(
+    mngr: &mut (impl Overlay + ?Sized),
+    system: &mut (impl System + ?Sized),
+    overlay: OverlayHandle,
+    placement: &Placement,
+) -> Result<(), EVROverlayError> {
+    match placement {
+        Placement::World(m) => mngr.set_transform_absolute(
+            overlay,
+            TrackingUniverseOrigin::TrackingUniverseStanding,
+            m,
+        ),
+        Placement::Head(m) => {
+            mngr.set_transform_tracked_device_relative(overlay, TrackedDeviceIndex::HMD, m)
+        }
+        Placement::Hand(hand, m) => {
+            match system.get_tracked_device_index_for_controller_role(hand.role()) {
+                Some(index) => mngr.set_transform_tracked_device_relative(overlay, index, m),
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+/// The standing poses of the headset and hands, at one point in time.
+struct Poses {
+    hmd: Option<Matrix3x4>,
+    hands: [Option<Matrix3x4>; 2],
+}
+impl Poses {
+    fn get(system: &mut (impl System + ?Sized)) -> Self {
+        let mut poses: [sys::TrackedDevicePose_t; sys::k_unMaxTrackedDeviceCount as usize] =
+            unsafe { std::mem::zeroed() };
+        system.get_device_to_absolute_tracking_pose_into(
+            TrackingUniverseOrigin::TrackingUniverseStanding,
+            0.,
+            &mut poses,
+        );
+        let pose = |index: Option<TrackedDeviceIndex>| {
+            let pose = poses.get(index?.0 as usize)?;
+            pose.bPoseIsValid
+                .then_some(*<&Matrix3x4>::from(&pose.mDeviceToAbsoluteTracking))
+        };
+        let mut hand =
+            |hand: Hand| pose(system.get_tracked_device_index_for_controller_role(hand.role()));
+        Self {
+            hmd: pose(Some(TrackedDeviceIndex::HMD)),
+            hands: [hand(Hand::Left), hand(Hand::Right)],
+        }
+    }
+
+    fn hmd(&self) -> Option<Matrix3x4> {
+        self.hmd
+    }
+
+    fn hand(&self, hand: Hand) -> Option<Matrix3x4> {
+        self.hands[hand as usize]
+    }
+
+    /// Where an overlay with `placement` currently is in the standing space.
+    fn world(&self, placement: &Placement) -> Option<Matrix3x4> {
+        match placement {
+            Placement::World(m) => Some(*m),
+            Placement::Head(m) => Some(self.hmd? * *m),
+            Placement::Hand(hand, m) => Some(self.hand(*hand)? * *m),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod test {
+    use super::*;
+    use crate::mock::{MockDevice, MockOverlay, MockSystem, MockTransform};
+
+    const LEFT: TrackedDeviceIndex = TrackedDeviceIndex(1);
+    const RIGHT: TrackedDeviceIndex = TrackedDeviceIndex(2);
+
+    fn at(translation: [f32; 3]) -> Matrix3x4 {
+        Matrix3x4::from_translation(translation)
+    }
+
+    /// A headset at `[0, 1.5, 0]`, the left hand at `[0, 1, -0.5]` and the right hand
+    /// half a meter to its right.
+    fn system() -> MockSystem {
+        use sys::ETrackedControllerRole::*;
+        use sys::ETrackedDeviceClass::*;
+        let mut system = MockSystem::new();
+        let devices = [
+            (
+                TrackedDeviceIndex::HMD,
+                MockDevice::new(TrackedDeviceClass_HMD),
+                [0., 1.5, 0.],
+            ),
+            (
+                LEFT,
+                MockDevice::new(TrackedDeviceClass_Controller)
+                    .with_role(TrackedControllerRole_LeftHand),
+                [0., 1., -0.5],
+            ),
+            (
+                RIGHT,
+                MockDevice::new(TrackedDeviceClass_Controller)
+                    .with_role(TrackedControllerRole_RightHand),
+                [0.5, 1., -0.5],
+            ),
+        ];
+        for (index, mut device, pose) in devices {
+            device.pose = at(pose);
+            system.set_device(index, device);
+        }
+        system
+    }
+
+    #[test]
+    fn test_grab_keeps_placement_kind() {
+        // Each placement puts the overlay at [0, 1.25, -0.5], a quarter meter above the
+        // left hand, and the move takes it to [0.25, 1.25, -0.25].
+        let cases = [
+            (
+                Placement::World(at([0., 1.25, -0.5])),
+                Placement::World(at([0.25, 1.25, -0.25])),
+            ),
+            (
+                Placement::Head(at([0., -0.25, -0.5])),
+                Placement::Head(at([0.25, -0.25, -0.25])),
+            ),
+            (
+                Placement::Hand(Hand::Right, at([-0.5, 0.25, 0.])),
+                Placement::Hand(Hand::Right, at([-0.25, 0.25, 0.25])),
+            ),
+        ];
+        for (before, after) in cases {
+            let mut system = system();
+            let mut mngr = MockOverlay::new();
+            let overlay = mngr.create_overlay("overlay", "Overlay").unwrap();
+            let mut layout = LayoutManager::new();
+            layout.insert("overlay", overlay, before);
+
+            let grabbed = layout.begin_grab(&mut mngr, &mut system, Hand::Left);
+            assert_eq!(grabbed.unwrap(), Some("overlay"));
+            let transform = mngr.overlay(overlay).unwrap().transform.clone();
+            assert!(matches!(
+                transform,
+                Some(MockTransform::TrackedDeviceRelative(LEFT, m)) if m == at([0., 0.25, 0.])
+            ));
+            // Placements aren't applied to the grabbed overlay.
+            layout.apply(&mut mngr, &mut system).unwrap();
+            assert!(matches!(
+                mngr.overlay(overlay).unwrap().transform,
+                Some(MockTransform::TrackedDeviceRelative(LEFT, _))
+            ));
+
+            system.device_mut(LEFT).unwrap().pose = at([0.25, 1., -0.25]);
+            layout.end_grab(&mut mngr, &mut system).unwrap();
+            assert_eq!(layout.grabbed(), None);
+            assert_eq!(layout.placement("overlay"), Some(after));
+
+            let transform = mngr.overlay(overlay).unwrap().transform.clone();
+            match after {
+                Placement::World(expected) => assert!(matches!(
+                    transform,
+                    Some(MockTransform::Absolute(
+                        TrackingUniverseOrigin::TrackingUniverseStanding,
+                        m,
+                    )) if m == expected
+                )),
+                Placement::Head(expected) => assert!(matches!(
+                    transform,
+                    Some(MockTransform::TrackedDeviceRelative(TrackedDeviceIndex::HMD, m))
+                        if m == expected
+                )),
+                Placement::Hand(_, expected) => assert!(matches!(
+                    transform,
+                    Some(MockTransform::TrackedDeviceRelative(RIGHT, m)) if m == expected
+                )),
+            }
+        }
+    }
+
+    #[test]
+    fn test_grab_picks_the_closest_overlay() {
+        let mut system = system();
+        let mut mngr = MockOverlay::new();
+        let mut layout = LayoutManager::new();
+        for (key, pose) in [
+            ("near", [0., 1.125, -0.5]),
+            ("farther", [0., 1.25, -0.5]),
+            ("out of reach", [0., 1.5, -0.5]),
+        ] {
+            let overlay = mngr.create_overlay(key, key).unwrap();
+            layout.insert(key, overlay, Placement::World(at(pose)));
+        }
+
+        let grabbed = layout.begin_grab(&mut mngr, &mut system, Hand::Left);
+        assert_eq!(grabbed.unwrap(), Some("near"));
+        // Only one overlay can be grabbed at a time.
+        let grabbed = layout.begin_grab(&mut mngr, &mut system, Hand::Right);
+        assert_eq!(grabbed.unwrap(), None);
+        layout.end_grab(&mut mngr, &mut system).unwrap();
+
+        layout.remove("near");
+        layout.remove("farther");
+        let grabbed = layout.begin_grab(&mut mngr, &mut system, Hand::Left);
+        assert_eq!(grabbed.unwrap(), None);
+    }
+}
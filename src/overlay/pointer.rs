@@ -0,0 +1,168 @@
+//! A laser pointer driven by a pose action, for pointing at overlays outside the
+//! dashboard.
+//!
+//! Each frame, [`Pointer::update()`] finds the closest overlay the pointer hits. With the
+//! `ovr_system` feature, [`Pointer::mouse_events()`] turns hits and a button state into
+//! the mouse events SteamVR's own pointer would send, for handling alongside the
+//! overlay's polled events.
+
+use super::{OverlayHandle, OverlayIntersection, OverlayManager};
+use crate::errors::EVRInputError;
+use crate::input::{ActionHandle, InputManager, InputValueHandle};
+use crate::pose::{Matrix3x4, Ray, TrackingUniverseOrigin};
+
+/// The closest overlay a [`Pointer`] hits.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PointerHit {
+    pub overlay: OverlayHandle,
+    pub intersection: OverlayIntersection,
+}
+
+pub struct Pointer {
+    action: ActionHandle,
+    restrict: InputValueHandle,
+    origin: TrackingUniverseOrigin,
+    hit: Option<PointerHit>,
+    #[cfg(feature = "ovr_system")]
+    hovered: Option<OverlayHandle>,
+    #[cfg(feature = "ovr_system")]
+    pressed: bool,
+    #[cfg(feature = "ovr_system")]
+    pressed_on: Option<OverlayHandle>,
+}
+
+impl Pointer {
+    /// `pose_action` is usually bound to a hand's aim pose.
+    pub fn new(pose_action: ActionHandle, origin: TrackingUniverseOrigin) -> Self {
+        Self {
+            action: pose_action,
+            restrict: InputValueHandle(0),
+            origin,
+            hit: None,
+            #[cfg(feature = "ovr_system")]
+            hovered: None,
+            #[cfg(feature = "ovr_system")]
+            pressed: false,
+            #[cfg(feature = "ovr_system")]
+            pressed_on: None,
+        }
+    }
+
+    /// Only use the pose from the given input source, like `/user/hand/left`.
+    pub fn restrict_to(mut self, device: InputValueHandle) -> Self {
+        self.restrict = device;
+        self
+    }
+
+    /// The ray along the pose action, if it is active and tracking.
+    pub fn ray(&self, input: &mut InputManager) -> Result<Option<Ray>, EVRInputError> {
+        let data = input.get_pose_action_data_relative_to_now(
+            self.action,
+            self.origin.clone(),
+            0.0,
+            self.restrict,
+        )?;
+        if !data.0.bActive || !data.0.pose.bPoseIsValid {
+            return Ok(None);
+        }
+        let pose = <&Matrix3x4>::from(&data.0.pose.mDeviceToAbsoluteTracking);
+        Ok(Some(Ray::from_pose(pose)))
+    }
+
+    /// Finds the closest of `targets` the pointer hits this frame.
+    pub fn update(
+        &mut self,
+        input: &mut InputManager,
+        mngr: &mut OverlayManager,
+        targets: &[OverlayHandle],
+    ) -> Result<Option<PointerHit>, EVRInputError> {
+        self.hit = self.ray(input)?.and_then(|ray| {
+            targets
+                .iter()
+                .filter_map(|&overlay| {
+                    if !mngr.is_visible(overlay) {
+                        return None;
+                    }
+                    let intersection =
+                        mngr.compute_overlay_intersection(overlay, self.origin.clone(), &ray)?;
+                    Some(PointerHit {
+                        overlay,
+                        intersection,
+                    })
+                })
+                .min_by(|a, b| a.intersection.distance.total_cmp(&b.intersection.distance))
+        });
+        Ok(self.hit)
+    }
+
+    /// The hit found by the last [`update()`](Self::update).
+    pub fn hit(&self) -> Option<PointerHit> {
+        self.hit
+    }
+
+    /// Mouse events for the last [`update()`](Self::update), in the overlays' mouse
+    /// scale. `pressed` is the state of the button that clicks, which is released on
+    /// the overlay it was pressed on.
+    #[cfg(feature = "ovr_system")]
+    pub fn mouse_events(
+        &mut self,
+        mngr: &mut OverlayManager,
+        pressed: bool,
+    ) -> Result<Vec<(OverlayHandle, crate::system::VREvent)>, crate::errors::EVROverlayError> {
+        use crate::sys::EVREventType::*;
+
+        let mut events = Vec::new();
+        let current = self.hit.map(|hit| hit.overlay);
+        if self.hovered != current {
+            if let Some(overlay) = std::mem::replace(&mut self.hovered, current) {
+                events.push((overlay, mouse_event(VREvent_FocusLeave, [0.; 2])));
+            }
+            if let Some(overlay) = current {
+                events.push((overlay, mouse_event(VREvent_FocusEnter, [0.; 2])));
+            }
+        }
+
+        if let Some(hit) = self.hit {
+            let [u, v] = hit.intersection.uv;
+            let [width, height] = mngr.mouse_scale(hit.overlay)?;
+            let pos = [u * width, v * height];
+            events.push((hit.overlay, mouse_event(VREvent_MouseMove, pos)));
+            if pressed && !self.pressed {
+                self.pressed_on = Some(hit.overlay);
+                events.push((hit.overlay, mouse_event(VREvent_MouseButtonDown, pos)));
+            }
+        }
+        if !pressed {
+            if let Some(overlay) = self.pressed_on.take() {
+                let pos = match self.hit {
+                    Some(hit) if hit.overlay == overlay => {
+                        let [u, v] = hit.intersection.uv;
+                        let [width, height] = mngr.mouse_scale(overlay)?;
+                        [u * width, v * height]
+                    }
+                    _ => [-1.; 2],
+                };
+                events.push((overlay, mouse_event(VREvent_MouseButtonUp, pos)));
+            }
+        }
+        self.pressed = pressed;
+        Ok(events)
+    }
+}
+
+/// A `VREvent_Mouse_t` event for the left button.
+#[cfg(feature = "ovr_system")]
+fn mouse_event(event_type: crate::sys::EVREventType, pos: [f32; 2]) -> crate::system::VREvent {
+    use crate::sys;
+    use byteorder::{ByteOrder, LittleEndian};
+
+    let index = crate::TrackedDeviceIndex(sys::k_unTrackedDeviceIndexInvalid);
+    let mut event = crate::system::VREvent::new(event_type, index);
+    LittleEndian::write_f32(&mut event.data[0..4], pos[0]);
+    LittleEndian::write_f32(&mut event.data[4..8], pos[1]);
+    LittleEndian::write_u32(
+        &mut event.data[8..12],
+        sys::EVRMouseButton::VRMouseButton_Left as u32,
+    );
+    event
+}
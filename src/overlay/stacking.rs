@@ -0,0 +1,123 @@
+//! Keeps the overlay the user is pointing at on top of the others in a group, for apps
+//! with several overlapping panels.
+//!
+//! ```no_run
+//! # use ovr_overlay::overlay::{OverlayHandle, OverlayManager};
+//! # use ovr_overlay::overlay::stacking::SortGroup;
+//! # fn example(mngr: &mut OverlayManager, panels: &[OverlayHandle]) {
+//! let mut group = SortGroup::new(10);
+//! for &panel in panels {
+//!     group.insert(panel);
+//! }
+//! loop {
+//!     group.update(mngr).unwrap();
+//! }
+//! # }
+//! ```
+
+use super::{OverlayHandle, OverlayManager};
+use crate::errors::EVROverlayError;
+
+/// A group of overlays sorted in the order they were inserted, except that the last
+/// hovered one is raised above the rest.
+pub struct SortGroup {
+    base: u32,
+    overlays: Vec<OverlayHandle>,
+    raised: Option<OverlayHandle>,
+    dirty: bool,
+}
+
+impl SortGroup {
+    /// The overlays get sort orders starting at `base`, up to `base` plus the number of
+    /// overlays.
+    pub fn new(base: u32) -> Self {
+        Self {
+            base,
+            overlays: Vec::new(),
+            raised: None,
+            dirty: false,
+        }
+    }
+
+    /// Adds an overlay above the ones already in the group.
+    pub fn insert(&mut self, overlay: OverlayHandle) {
+        if !self.overlays.contains(&overlay) {
+            self.overlays.push(overlay);
+            self.dirty = true;
+        }
+    }
+
+    /// Removes an overlay from the group. Its sort order is left as is.
+    pub fn remove(&mut self, overlay: OverlayHandle) {
+        self.overlays.retain(|&o| o != overlay);
+        if self.raised == Some(overlay) {
+            self.raised = None;
+        }
+        self.dirty = true;
+    }
+
+    /// The overlay currently on top, if any was hovered or raised yet.
+    pub fn raised(&self) -> Option<OverlayHandle> {
+        self.raised
+    }
+
+    /// Puts `overlay` on top at the next update, as if it was hovered.
+    pub fn raise(&mut self, overlay: OverlayHandle) {
+        if self.raised != Some(overlay) && self.overlays.contains(&overlay) {
+            self.raised = Some(overlay);
+            self.dirty = true;
+        }
+    }
+
+    /// Raises the hovered overlay, if any, and sets the sort orders when they changed.
+    /// Call this every frame.
+    pub fn update(&mut self, mngr: &mut OverlayManager) -> Result<(), EVROverlayError> {
+        let hovered = self
+            .overlays
+            .iter()
+            .copied()
+            .find(|&overlay| mngr.is_hover_target_overlay(overlay));
+        if let Some(hovered) = hovered {
+            self.raise(hovered);
+        }
+        if !self.dirty {
+            return Ok(());
+        }
+        for (overlay, sort_order) in self.sort_orders() {
+            mngr.set_sort_order(overlay, sort_order)?;
+        }
+        self.dirty = false;
+        Ok(())
+    }
+
+    fn sort_orders(&self) -> impl Iterator<Item = (OverlayHandle, u32)> + '_ {
+        let top = self.base + self.overlays.len() as u32;
+        let mut below = self.base..;
+        self.overlays.iter().map(move |&overlay| {
+            if Some(overlay) == self.raised {
+                (overlay, top)
+            } else {
+                (overlay, below.next().unwrap())
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sort_orders() {
+        let mut group = SortGroup::new(10);
+        for i in 1..=3 {
+            group.insert(OverlayHandle(i));
+        }
+        let orders = |group: &SortGroup| group.sort_orders().map(|(_, o)| o).collect::<Vec<_>>();
+        assert_eq!(orders(&group), [10, 11, 12]);
+        group.raise(OverlayHandle(1));
+        assert_eq!(orders(&group), [13, 10, 11]);
+        group.remove(OverlayHandle(2));
+        assert_eq!(orders(&group), [12, 10]);
+    }
+}
@@ -0,0 +1,144 @@
+//! Tracks the visibility and focus of overlays from their events, so applications can
+//! skip rendering overlays nobody is looking at or interacting with.
+//!
+//! ```no_run
+//! # use ovr_overlay::overlay::{OverlayHandle, OverlayManager};
+//! # use ovr_overlay::overlay::state::OverlayStates;
+//! # use std::time::Duration;
+//! # fn example(mngr: &mut OverlayManager, overlay: OverlayHandle) {
+//! let mut states = OverlayStates::new();
+//! loop {
+//!     for event in states.poll_events(mngr, overlay) {
+//!         // Handle input...
+//!     }
+//!     let state = states.get(overlay);
+//!     if state.visible && (state.focused || !state.idle_for(Duration::from_secs(5))) {
+//!         // Render at full rate...
+//!     }
+//! }
+//! # }
+//! ```
+
+use super::{OverlayHandle, OverlayManager};
+use crate::sys;
+use crate::system::VREvent;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// What the events of one overlay said about it so far.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct OverlayState {
+    pub visible: bool,
+    /// Whether the laser pointer or mouse is on the overlay.
+    pub focused: bool,
+    /// When the user last moved the pointer over the overlay, clicked or scrolled.
+    pub last_interaction: Option<Instant>,
+}
+
+impl OverlayState {
+    /// Whether there was no interaction for `duration`, or none at all.
+    pub fn idle_for(&self, duration: Duration) -> bool {
+        !self
+            .last_interaction
+            .is_some_and(|at| at.elapsed() < duration)
+    }
+}
+
+/// The [`OverlayState`] of each overlay whose events were handled.
+#[derive(Default)]
+pub struct OverlayStates {
+    states: HashMap<sys::VROverlayHandle_t, OverlayState>,
+}
+
+impl OverlayStates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The state of `overlay`, all false for overlays without events yet.
+    pub fn get(&self, overlay: OverlayHandle) -> OverlayState {
+        self.states.get(&overlay.0).copied().unwrap_or_default()
+    }
+
+    /// Updates the state of `overlay` from one of its events, returning whether it
+    /// was relevant.
+    pub fn handle_event(&mut self, overlay: OverlayHandle, event: &VREvent) -> bool {
+        self.handle_event_at(overlay, event, Instant::now())
+    }
+
+    fn handle_event_at(&mut self, overlay: OverlayHandle, event: &VREvent, now: Instant) -> bool {
+        use sys::EVREventType::*;
+        let state = self.states.entry(overlay.0).or_default();
+        match event.event_type {
+            VREvent_OverlayShown => state.visible = true,
+            VREvent_OverlayHidden => {
+                state.visible = false;
+                state.focused = false;
+            }
+            VREvent_FocusEnter => {
+                state.focused = true;
+                state.last_interaction = Some(now);
+            }
+            VREvent_FocusLeave => state.focused = false,
+            VREvent_MouseMove
+            | VREvent_MouseButtonDown
+            | VREvent_MouseButtonUp
+            | VREvent_ScrollDiscrete
+            | VREvent_ScrollSmooth => state.last_interaction = Some(now),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Polls and handles every pending event of `overlay`, returning them for handling
+    /// input.
+    pub fn poll_events(
+        &mut self,
+        mngr: &mut OverlayManager,
+        overlay: OverlayHandle,
+    ) -> Vec<VREvent> {
+        let events: Vec<VREvent> =
+            std::iter::from_fn(|| mngr.poll_next_overlay_event(overlay)).collect();
+        for event in &events {
+            self.handle_event(overlay, event);
+        }
+        events
+    }
+
+    /// Forgets the state of a destroyed overlay.
+    pub fn remove(&mut self, overlay: OverlayHandle) {
+        self.states.remove(&overlay.0);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::TrackedDeviceIndex;
+
+    #[test]
+    fn test_handle_event() {
+        use sys::EVREventType::*;
+        let mut states = OverlayStates::new();
+        let (a, b) = (OverlayHandle(1), OverlayHandle(2));
+        let event = |event_type| VREvent::new(event_type, TrackedDeviceIndex::HMD);
+        let now = Instant::now();
+
+        assert!(states.handle_event_at(a, &event(VREvent_OverlayShown), now));
+        assert!(states.handle_event_at(a, &event(VREvent_FocusEnter), now));
+        assert!(!states.handle_event_at(a, &event(VREvent_Quit), now));
+        assert_eq!(
+            states.get(a),
+            OverlayState {
+                visible: true,
+                focused: true,
+                last_interaction: Some(now),
+            }
+        );
+        assert_eq!(states.get(b), OverlayState::default());
+
+        states.handle_event_at(a, &event(VREvent_OverlayHidden), now);
+        assert!(!states.get(a).visible && !states.get(a).focused);
+    }
+}
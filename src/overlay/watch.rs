@@ -0,0 +1,168 @@
+//! Attaches an overlay to a hand like a watch, fading it in when the user turns their
+//! wrist to look at it and out again when they turn away.
+
+use super::OverlayHandle;
+use crate::animation::{Easing, Ramp};
+use crate::input::{ActionHandle, InputValueHandle};
+use crate::pose::{Matrix3x4, TrackingUniverseOrigin};
+use crate::traits::{Input, Overlay, System};
+
+use std::time::Duration;
+
+pub struct Watch {
+    overlay: OverlayHandle,
+    pose_action: ActionHandle,
+    restrict: InputValueHandle,
+    offset: Matrix3x4,
+    show_angle: f32,
+    hide_angle: f32,
+    fade_duration: Duration,
+    max_alpha: f32,
+    shown: bool,
+    fade: Ramp,
+}
+
+impl Watch {
+    /// `offset` is the overlay's transform relative to the hand pose of `pose_action`.
+    /// The overlay shows when its front (+Z) side faces the headset.
+    pub fn new(overlay: OverlayHandle, pose_action: ActionHandle, offset: Matrix3x4) -> Self {
+        Self {
+            overlay,
+            pose_action,
+            restrict: InputValueHandle(0),
+            offset,
+            show_angle: 35f32.to_radians(),
+            hide_angle: 50f32.to_radians(),
+            fade_duration: Duration::from_millis(150),
+            max_alpha: 1.,
+            shown: false,
+            fade: Ramp::new(0., 0., Duration::ZERO, Easing::Linear),
+        }
+    }
+
+    /// Only use the pose from the given input source, like `/user/hand/left`.
+    pub fn restrict_to(mut self, device: InputValueHandle) -> Self {
+        self.restrict = device;
+        self
+    }
+
+    /// The angles in radians between the overlay's front and the direction to the
+    /// headset below which it is shown, and above which it is hidden again. Defaults to
+    /// 35 and 50 degrees.
+    pub fn with_angles(mut self, show: f32, hide: f32) -> Self {
+        self.show_angle = show;
+        self.hide_angle = hide.max(show);
+        self
+    }
+
+    /// Defaults to 150ms.
+    pub fn with_fade_duration(mut self, duration: Duration) -> Self {
+        self.fade_duration = duration;
+        self
+    }
+
+    /// The opacity while shown, clamped to between 0 and 1. Defaults to 1.
+    pub fn with_alpha(mut self, alpha: f32) -> Self {
+        self.max_alpha = if alpha.is_nan() {
+            1.
+        } else {
+            alpha.clamp(0., 1.)
+        };
+        self
+    }
+
+    pub fn overlay(&self) -> OverlayHandle {
+        self.overlay
+    }
+
+    /// Whether the overlay is shown or fading in.
+    pub fn is_shown(&self) -> bool {
+        self.shown
+    }
+
+    /// Moves the overlay with the hand and fades it in or out, in the standing tracking
+    /// universe. Call this every frame, after updating the actions.
+    pub fn update(
+        &mut self,
+        input: &mut (impl Input + ?Sized),
+        mngr: &mut (impl Overlay + ?Sized),
+        system: &mut (impl System + ?Sized),
+    ) -> crate::Result<()> {
+        let origin = TrackingUniverseOrigin::TrackingUniverseStanding;
+        let hand = input.get_pose_action_data_relative_to_now(
+            self.pose_action,
+            origin.clone(),
+            0.0,
+            self.restrict,
+        )?;
+        let head = system
+            .hmd_pose(origin.clone())
+            .filter(|_| hand.0.bActive && hand.0.pose.bPoseIsValid);
+
+        let mut show = false;
+        if let Some(head) = head {
+            let hand = <&Matrix3x4>::from(&hand.0.pose.mDeviceToAbsoluteTracking);
+            let transform = *hand * self.offset;
+            mngr.set_transform_absolute(self.overlay, origin, &transform)?;
+
+            let angle = facing_angle(&transform, &head);
+            let threshold = if self.shown {
+                self.hide_angle
+            } else {
+                self.show_angle
+            };
+            show = angle < threshold;
+        }
+
+        if show != self.shown {
+            self.shown = show;
+            let to = if show { self.max_alpha } else { 0. };
+            self.fade = Ramp::new(self.fade.value(), to, self.fade_duration, Easing::EaseOut);
+        }
+        let alpha = self.fade.value();
+        mngr.set_opacity(self.overlay, alpha)?;
+        mngr.set_visibility(self.overlay, alpha > 0.)?;
+        Ok(())
+    }
+}
+
+/// The angle between the front (+Z) of `overlay` and the direction from it to `head`.
+fn facing_angle(overlay: &Matrix3x4, head: &Matrix3x4) -> f32 {
+    let m = &overlay.0;
+    let normal = [m[0][2], m[1][2], m[2][2]];
+    let (from, to) = (overlay.translation(), head.translation());
+    let dir: [f32; 3] = std::array::from_fn(|i| to[i] - from[i]);
+    let dot: f32 = (0..3).map(|i| normal[i] * dir[i]).sum();
+    let len = (0..3).map(|i| dir[i] * dir[i]).sum::<f32>().sqrt()
+        * (0..3).map(|i| normal[i] * normal[i]).sum::<f32>().sqrt();
+    if len == 0. {
+        return 0.;
+    }
+    (dot / len).clamp(-1., 1.).acos()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_facing_angle() {
+        let watch = Matrix3x4::IDENTITY;
+        let angle = |head| facing_angle(&watch, &Matrix3x4::from_translation(head));
+        assert_eq!(angle([0., 0., 1.]), 0.);
+        assert!((angle([1., 0., 1.]) - std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+        assert!((angle([0., 0., -1.]) - std::f32::consts::PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_alpha_is_clamped() {
+        let watch = |alpha| {
+            Watch::new(OverlayHandle(0), ActionHandle::from(0), Matrix3x4::IDENTITY)
+                .with_alpha(alpha)
+                .max_alpha
+        };
+        assert_eq!(watch(1.5), 1.);
+        assert_eq!(watch(-0.5), 0.);
+        assert_eq!(watch(0.5), 0.5);
+    }
+}
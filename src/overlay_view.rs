@@ -0,0 +1,110 @@
+//! Access to the textures of overlays owned by other applications.
+
+pub use crate::errors::EVROverlayError;
+use crate::overlay::OverlayHandle;
+use crate::system::VREvent;
+use crate::{sys, Context, Interface, InterfaceLock, TextureBounds};
+
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+pub struct OverlayViewManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVROverlayView>,
+    _lock: InterfaceLock<'c>,
+}
+
+/// The device that an acquired overlay texture will be used on.
+pub enum NativeDevice<'a> {
+    /// An `ID3D11Device*`.
+    DirectX11(*mut std::ffi::c_void),
+    Vulkan(&'a mut sys::VRVulkanDevice_t),
+}
+
+/// A view of another overlay's texture. Must be released with
+/// [`OverlayViewManager::release_overlay_view()`].
+pub struct OverlayView(sys::VROverlayView_t);
+impl OverlayView {
+    pub fn overlay(&self) -> OverlayHandle {
+        OverlayHandle(self.0.overlayHandle)
+    }
+
+    /// The texture of the overlay, usable on the device it was acquired for.
+    pub fn texture(&self) -> &sys::Texture_t {
+        &self.0.texture
+    }
+
+    pub fn texture_bounds(&self) -> TextureBounds {
+        let bounds = &self.0.textureBounds;
+        TextureBounds(sys::VRTextureBounds_t {
+            uMin: bounds.uMin,
+            vMin: bounds.vMin,
+            uMax: bounds.uMax,
+            vMax: bounds.vMax,
+        })
+    }
+}
+
+impl<'c> OverlayViewManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::OverlayView) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+        }
+    }
+
+    /// Acquires a view of the texture of `overlay`, shared to `device`.
+    ///
+    /// Wraps c++ `AcquireOverlayView`.
+    ///
+    /// # Safety
+    /// The handles in `device` must be valid for the lifetime of the returned view.
+    pub unsafe fn acquire_overlay_view(
+        &mut self,
+        overlay: OverlayHandle,
+        device: NativeDevice,
+    ) -> Result<OverlayView, EVROverlayError> {
+        let mut native_device = match device {
+            NativeDevice::DirectX11(handle) => sys::VRNativeDevice_t {
+                handle: handle.cast(),
+                eType: sys::EDeviceType::DeviceType_DirectX11,
+            },
+            NativeDevice::Vulkan(device) => sys::VRNativeDevice_t {
+                handle: (device as *mut sys::VRVulkanDevice_t).cast(),
+                eType: sys::EDeviceType::DeviceType_Vulkan,
+            },
+        };
+        let mut view = std::mem::MaybeUninit::<sys::VROverlayView_t>::zeroed();
+        let err = self.inner.as_mut().AcquireOverlayView(
+            overlay.0,
+            &mut native_device,
+            view.as_mut_ptr(),
+            std::mem::size_of::<sys::VROverlayView_t>() as u32,
+        );
+        EVROverlayError::new(err)?;
+        Ok(OverlayView(view.assume_init()))
+    }
+
+    /// Wraps c++ `ReleaseOverlayView`.
+    pub fn release_overlay_view(&mut self, mut view: OverlayView) {
+        unsafe { self.inner.as_mut().ReleaseOverlayView(&mut view.0) }
+    }
+
+    /// Sends an event to `overlay`, as if it came from SteamVR. Useful for forwarding
+    /// input to an overlay whose texture is being displayed elsewhere.
+    ///
+    /// Wraps c++ `PostOverlayEvent`.
+    pub fn post_overlay_event(&mut self, overlay: OverlayHandle, event: &VREvent) {
+        let event = event.to_sys();
+        unsafe { self.inner.as_mut().PostOverlayEvent(overlay.0, &event) }
+    }
+
+    /// Returns true if this application is allowed to view `overlay`.
+    ///
+    /// Wraps c++ `IsViewingPermitted`.
+    pub fn is_viewing_permitted(&mut self, overlay: OverlayHandle) -> bool {
+        unsafe { self.inner.as_mut().IsViewingPermitted(overlay.0) }
+    }
+}
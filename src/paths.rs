@@ -0,0 +1,120 @@
+//! Well-known OpenVR path strings, and builders that check the parts of a path when it
+//! is built, rather than OpenVR failing with `NameNotFound` later.
+//!
+//! ```
+//! # use ovr_overlay::paths::{self, ActionDirection};
+//! let toggle = paths::action("main", ActionDirection::In, "toggle").unwrap();
+//! assert_eq!(toggle, "/actions/main/in/toggle");
+//! assert!(paths::action("main", ActionDirection::In, "to ggle").is_err());
+//! ```
+
+pub const USER_HAND_LEFT: &str = "/user/hand/left";
+pub const USER_HAND_RIGHT: &str = "/user/hand/right";
+pub const USER_HEAD: &str = "/user/head";
+pub const USER_GAMEPAD: &str = "/user/gamepad";
+pub const USER_TREADMILL: &str = "/user/treadmill";
+pub const USER_STYLUS: &str = "/user/stylus";
+pub const USER_WAIST: &str = "/user/waist";
+pub const USER_CHEST: &str = "/user/chest";
+pub const USER_FOOT_LEFT: &str = "/user/foot/left";
+pub const USER_FOOT_RIGHT: &str = "/user/foot/right";
+
+/// Returned by the path builders for a part that can't be used in a path.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PathError {
+    #[error("the {0} in the path is empty")]
+    Empty(&'static str),
+    #[error("the {part} {value:?} contains {invalid:?}, which can't be used in a path")]
+    InvalidChar {
+        part: &'static str,
+        value: String,
+        invalid: char,
+    },
+}
+
+/// Whether an action reads input or sends output, like haptics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionDirection {
+    In,
+    Out,
+}
+impl ActionDirection {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::In => "in",
+            Self::Out => "out",
+        }
+    }
+}
+
+/// `/devices/<driver>/<serial>`, like `/devices/lighthouse/LHR-0123ABCD`.
+pub fn device(driver: &str, serial: &str) -> Result<String, PathError> {
+    check("driver", driver, is_name_char)?;
+    check("serial", serial, is_serial_char)?;
+    Ok(format!("/devices/{driver}/{serial}"))
+}
+
+/// `/actions/<set>`, for [`InputManager::get_action_set_handle()`].
+///
+/// [`InputManager::get_action_set_handle()`]: crate::input::InputManager::get_action_set_handle
+pub fn action_set(set: &str) -> Result<String, PathError> {
+    check("action set", set, is_name_char)?;
+    Ok(format!("/actions/{set}"))
+}
+
+/// `/actions/<set>/<in|out>/<action>`, for [`InputManager::get_action_handle()`].
+///
+/// [`InputManager::get_action_handle()`]: crate::input::InputManager::get_action_handle
+pub fn action(set: &str, direction: ActionDirection, action: &str) -> Result<String, PathError> {
+    check("action set", set, is_name_char)?;
+    check("action", action, is_name_char)?;
+    Ok(format!("/actions/{set}/{}/{action}", direction.as_str()))
+}
+
+fn check(part: &'static str, value: &str, valid: fn(char) -> bool) -> Result<(), PathError> {
+    if value.is_empty() {
+        return Err(PathError::Empty(part));
+    }
+    match value.chars().find(|&c| !valid(c)) {
+        Some(invalid) => Err(PathError::InvalidChar {
+            part,
+            value: value.to_owned(),
+            invalid,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Action, action set and driver names.
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+/// Serial numbers come from the device, so anything visible but a separator is allowed.
+fn is_serial_char(c: char) -> bool {
+    c.is_ascii_graphic() && c != '/'
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_builders() {
+        assert_eq!(
+            device("lighthouse", "LHR-0123ABCD").unwrap(),
+            "/devices/lighthouse/LHR-0123ABCD"
+        );
+        assert_eq!(action_set("main").unwrap(), "/actions/main");
+        assert_eq!(
+            action("main", ActionDirection::Out, "haptic").unwrap(),
+            "/actions/main/out/haptic"
+        );
+        assert_eq!(action_set(""), Err(PathError::Empty("action set")));
+        assert!(matches!(
+            device("lighthouse", "LHR/1"),
+            Err(PathError::InvalidChar { invalid: '/', .. })
+        ));
+        assert!(action("main", ActionDirection::In, "tögg").is_err());
+    }
+}
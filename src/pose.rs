@@ -1,9 +1,67 @@
 use crate::sys;
 
+use bytemuck::{Pod, Zeroable};
+
 /// Row-major 3x4 matrix
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct Matrix3x4(pub [[f32; 4]; 3]);
+impl Matrix3x4 {
+    #[rustfmt::skip]
+    pub const IDENTITY: Self = Self([
+        [1., 0., 0., 0.],
+        [0., 1., 0., 0.],
+        [0., 0., 1., 0.],
+    ]);
+
+    pub fn from_translation(translation: [f32; 3]) -> Self {
+        let mut m = Self::IDENTITY;
+        for (row, t) in m.0.iter_mut().zip(translation) {
+            row[3] = t;
+        }
+        m
+    }
+
+    pub fn translation(&self) -> [f32; 3] {
+        [self.0[0][3], self.0[1][3], self.0[2][3]]
+    }
+
+    /// The direction of the -Z axis, which OpenVR devices point along.
+    pub fn forward(&self) -> [f32; 3] {
+        [-self.0[0][2], -self.0[1][2], -self.0[2][2]]
+    }
+
+    /// Inverts a rotation and translation, which is all a tracking pose contains.
+    /// The result is meaningless if `self` also scales or shears.
+    pub fn inverse_rigid(&self) -> Self {
+        let m = &self.0;
+        let mut out = [[0.; 4]; 3];
+        for (i, row) in out.iter_mut().enumerate() {
+            for (j, x) in row[..3].iter_mut().enumerate() {
+                *x = m[j][i];
+            }
+            row[3] = -(0..3).map(|k| m[k][i] * m[k][3]).sum::<f32>();
+        }
+        Self(out)
+    }
+}
+/// Treats both sides as 4x4 matrices with a `[0, 0, 0, 1]` last row.
+impl std::ops::Mul for Matrix3x4 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (&self.0, &rhs.0);
+        let mut out = [[0.; 4]; 3];
+        for (i, row) in out.iter_mut().enumerate() {
+            for (j, x) in row.iter_mut().enumerate() {
+                *x = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+            }
+            row[3] += a[i][3];
+        }
+        Self(out)
+    }
+}
 impl From<&Matrix3x4> for &sys::HmdMatrix34_t {
     fn from(other: &Matrix3x4) -> Self {
         let other = other as *const Matrix3x4;
@@ -88,6 +146,127 @@ mod nalgebra_impls {
     }
 }
 
+/// A point or size on the floor, like the corners of a chaperone perimeter.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Vec2(pub [f32; 2]);
+impl Vec2 {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self([x, y])
+    }
+
+    pub fn x(&self) -> f32 {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> f32 {
+        self.0[1]
+    }
+}
+impl From<[f32; 2]> for Vec2 {
+    fn from(v: [f32; 2]) -> Self {
+        Self(v)
+    }
+}
+impl From<Vec2> for [f32; 2] {
+    fn from(v: Vec2) -> Self {
+        v.0
+    }
+}
+impl From<sys::HmdVector2_t> for Vec2 {
+    fn from(v: sys::HmdVector2_t) -> Self {
+        Self(v.v)
+    }
+}
+impl From<Vec2> for sys::HmdVector2_t {
+    fn from(v: Vec2) -> Self {
+        Self { v: v.0 }
+    }
+}
+
+/// Four corners in 3D space, like a wall of the chaperone bounds.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
+pub struct Quad(pub [[f32; 3]; 4]);
+impl Quad {
+    pub fn corners(&self) -> &[[f32; 3]; 4] {
+        &self.0
+    }
+
+    /// # Panics
+    /// Panics if `index` is 4 or more.
+    pub fn corner(&self, index: usize) -> [f32; 3] {
+        self.0[index]
+    }
+
+    /// The sides as pairs of corners, from each corner to the next one.
+    pub fn edges(&self) -> impl Iterator<Item = ([f32; 3], [f32; 3])> + '_ {
+        (0..4).map(move |i| (self.0[i], self.0[(i + 1) % 4]))
+    }
+}
+impl From<sys::HmdQuad_t> for Quad {
+    fn from(quad: sys::HmdQuad_t) -> Self {
+        Self(quad.vCorners.map(|corner| corner.v))
+    }
+}
+impl From<Quad> for sys::HmdQuad_t {
+    fn from(quad: Quad) -> Self {
+        Self {
+            vCorners: quad.0.map(|v| sys::HmdVector3_t { v }),
+        }
+    }
+}
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::*;
+
+    impl From<Vec2> for glam::Vec2 {
+        fn from(v: Vec2) -> Self {
+            Self::from_array(v.0)
+        }
+    }
+
+    impl From<glam::Vec2> for Vec2 {
+        fn from(v: glam::Vec2) -> Self {
+            Self(v.to_array())
+        }
+    }
+
+    impl From<Quad> for [glam::Vec3; 4] {
+        fn from(quad: Quad) -> Self {
+            quad.0.map(glam::Vec3::from_array)
+        }
+    }
+
+    impl From<[glam::Vec3; 4]> for Quad {
+        fn from(corners: [glam::Vec3; 4]) -> Self {
+            Self(corners.map(|corner| corner.to_array()))
+        }
+    }
+}
+
+/// A ray in some tracking universe, such as where the user is looking.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ray {
+    pub origin: [f32; 3],
+    /// Normalized.
+    pub direction: [f32; 3],
+}
+
+impl Ray {
+    /// The ray along the -Z axis of `pose`.
+    pub fn from_pose(pose: &Matrix3x4) -> Self {
+        Self {
+            origin: pose.translation(),
+            direction: pose.forward(),
+        }
+    }
+}
+
 pub use sys::ETrackingUniverseOrigin as TrackingUniverseOrigin;
 
 #[cfg(test)]
@@ -151,6 +330,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rigid_inverse() {
+        #[rustfmt::skip]
+        let rot_z = Matrix3x4([
+            [0., -1., 0., 1.],
+            [1.,  0., 0., 2.],
+            [0.,  0., 1., 3.],
+        ]);
+        assert_eq!(rot_z * rot_z.inverse_rigid(), Matrix3x4::IDENTITY);
+        assert_eq!(rot_z.inverse_rigid() * rot_z, Matrix3x4::IDENTITY);
+        assert_eq!(
+            Matrix3x4::from_translation([1., 2., 3.]) * Matrix3x4::from_translation([1., 1., 1.]),
+            Matrix3x4::from_translation([2., 3., 4.])
+        );
+    }
+
+    #[test]
+    fn test_quad_conversion() {
+        let quad = Quad([[0., 0., 0.], [1., 0., 0.], [1., 2., 0.], [0., 2., 0.]]);
+        let raw = sys::HmdQuad_t::from(quad);
+        assert_eq!(raw.vCorners[2].v, [1., 2., 0.]);
+        assert_eq!(Quad::from(raw), quad);
+        assert_eq!(quad.edges().last(), Some(([0., 2., 0.], [0., 0., 0.])));
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn test_glam_conversion() {
+        let v = Vec2::new(1., 2.);
+        assert_eq!(glam::Vec2::from(v), glam::Vec2::new(1., 2.));
+        assert_eq!(Vec2::from(glam::Vec2::new(1., 2.)), v);
+        let quad = Quad([[0., 1., 2.]; 4]);
+        let corners = <[glam::Vec3; 4]>::from(quad);
+        assert_eq!(corners[3], glam::Vec3::new(0., 1., 2.));
+        assert_eq!(Quad::from(corners), quad);
+    }
+
     #[cfg(feature = "nalgebra")]
     #[test]
     fn test_nalgebra_conversion() {
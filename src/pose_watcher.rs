@@ -0,0 +1,217 @@
+//! Watches the poses of selected devices, reporting only changes larger than a
+//! threshold, so applications don't have to diff every device's pose each frame.
+//!
+//! ```no_run
+//! # use ovr_overlay::pose::TrackingUniverseOrigin;
+//! # use ovr_overlay::pose_watcher::{PoseEvent, PoseWatcher};
+//! # use ovr_overlay::system::SystemManager;
+//! # use ovr_overlay::TrackedDeviceIndex;
+//! # fn example(system: &mut SystemManager, tracker: TrackedDeviceIndex) {
+//! let mut watcher = PoseWatcher::new(TrackingUniverseOrigin::TrackingUniverseStanding)
+//!     .with_thresholds(0.05, 10f32.to_radians());
+//! watcher.watch(tracker);
+//! loop {
+//!     for event in watcher.poll(system) {
+//!         if let PoseEvent::Moved { device, pose } = event {
+//!             println!("{device:?} moved to {:?}", pose.translation());
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+
+use crate::pose::{Matrix3x4, TrackingUniverseOrigin};
+use crate::traits::System;
+use crate::{sys, TrackedDeviceIndex};
+
+use std::collections::HashMap;
+
+/// Changes reported by [`PoseWatcher::poll()`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PoseEvent {
+    /// The device moved or rotated by more than the thresholds since the last event.
+    Moved {
+        device: TrackedDeviceIndex,
+        pose: Matrix3x4,
+    },
+    /// The device's pose became valid, like when it starts being tracked, or invalid.
+    /// `pose` is the last valid pose if it became invalid.
+    TrackingChanged {
+        device: TrackedDeviceIndex,
+        valid: bool,
+        pose: Matrix3x4,
+    },
+}
+
+/// Polls the poses of the watched devices in one tracking universe.
+pub struct PoseWatcher {
+    origin: TrackingUniverseOrigin,
+    position_threshold: f32,
+    rotation_threshold: f32,
+    /// The watched devices, and their last reported validity and pose.
+    devices: HashMap<TrackedDeviceIndex, Option<Matrix3x4>>,
+    poses: Vec<sys::TrackedDevicePose_t>,
+}
+
+impl PoseWatcher {
+    /// Defaults to thresholds of 1cm and 1 degree, without any devices.
+    pub fn new(origin: TrackingUniverseOrigin) -> Self {
+        Self {
+            origin,
+            position_threshold: 0.01,
+            rotation_threshold: 1f32.to_radians(),
+            devices: HashMap::new(),
+            poses: Vec::new(),
+        }
+    }
+
+    /// Only report moves of more than `position` meters or `rotation` radians.
+    pub fn with_thresholds(mut self, position: f32, rotation: f32) -> Self {
+        self.position_threshold = position;
+        self.rotation_threshold = rotation;
+        self
+    }
+
+    /// Starts watching `device`. Its first valid pose is reported as
+    /// [`PoseEvent::TrackingChanged`].
+    pub fn watch(&mut self, device: TrackedDeviceIndex) {
+        self.devices.entry(device).or_insert(None);
+    }
+
+    pub fn unwatch(&mut self, device: TrackedDeviceIndex) {
+        self.devices.remove(&device);
+    }
+
+    /// The last reported pose of `device`, if it is watched and valid.
+    pub fn get(&self, device: TrackedDeviceIndex) -> Option<Matrix3x4> {
+        self.devices.get(&device).copied().flatten()
+    }
+
+    /// Reads the current poses and returns the changes since the last events. Call
+    /// this every frame, or as often as changes should be noticed.
+    pub fn poll(&mut self, system: &mut (impl System + ?Sized)) -> Vec<PoseEvent> {
+        let len = self.devices.keys().map(|d| d.0 as usize + 1).max();
+        let len = len
+            .unwrap_or(0)
+            .min(sys::k_unMaxTrackedDeviceCount as usize);
+        self.poses
+            .resize_with(len, || unsafe { std::mem::zeroed() });
+        system.get_device_to_absolute_tracking_pose_into(self.origin.clone(), 0., &mut self.poses);
+
+        let mut events = Vec::new();
+        for (&device, last) in &mut self.devices {
+            let current = self
+                .poses
+                .get(device.0 as usize)
+                .filter(|pose| pose.bPoseIsValid)
+                .map(|pose| *<&Matrix3x4>::from(&pose.mDeviceToAbsoluteTracking));
+            match (*last, current) {
+                (Some(old), Some(new)) => {
+                    if moved(&old, &new, self.position_threshold, self.rotation_threshold) {
+                        *last = Some(new);
+                        events.push(PoseEvent::Moved { device, pose: new });
+                    }
+                }
+                (None, Some(pose)) | (Some(pose), None) => {
+                    *last = current;
+                    events.push(PoseEvent::TrackingChanged {
+                        device,
+                        valid: current.is_some(),
+                        pose,
+                    });
+                }
+                (None, None) => {}
+            }
+        }
+        events
+    }
+}
+
+/// Whether `new` is more than `position` meters or `rotation` radians away from `old`.
+fn moved(old: &Matrix3x4, new: &Matrix3x4, position: f32, rotation: f32) -> bool {
+    let (a, b) = (old.translation(), new.translation());
+    let distance = (0..3).map(|i| (a[i] - b[i]).powi(2)).sum::<f32>().sqrt();
+    if distance > position {
+        return true;
+    }
+    // The trace of old^T * new is 1 + 2cos(angle) for the rotation between them.
+    let trace: f32 = (0..3)
+        .flat_map(|i| (0..3).map(move |j| (i, j)))
+        .map(|(i, j)| old.0[i][j] * new.0[i][j])
+        .sum();
+    let angle = ((trace - 1.) / 2.).clamp(-1., 1.).acos();
+    angle > rotation
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod test {
+    use super::*;
+    use crate::mock::{MockDevice, MockSystem};
+
+    #[test]
+    fn test_poll() {
+        let tracker = TrackedDeviceIndex(3);
+        let mut system = MockSystem::new();
+        system.set_device(
+            tracker,
+            MockDevice::new(sys::ETrackedDeviceClass::TrackedDeviceClass_GenericTracker),
+        );
+        let mut watcher = PoseWatcher::new(TrackingUniverseOrigin::TrackingUniverseStanding)
+            .with_thresholds(0.05, 10f32.to_radians());
+        watcher.watch(tracker);
+
+        assert_eq!(
+            watcher.poll(&mut system),
+            [PoseEvent::TrackingChanged {
+                device: tracker,
+                valid: true,
+                pose: Matrix3x4::IDENTITY,
+            }]
+        );
+
+        system.device_mut(tracker).unwrap().pose = Matrix3x4::from_translation([0.03, 0., 0.]);
+        assert!(watcher.poll(&mut system).is_empty());
+
+        // Small moves add up, since they're compared to the last reported pose.
+        let pose = Matrix3x4::from_translation([0.06, 0., 0.]);
+        system.device_mut(tracker).unwrap().pose = pose;
+        assert_eq!(
+            watcher.poll(&mut system),
+            [PoseEvent::Moved {
+                device: tracker,
+                pose,
+            }]
+        );
+
+        system.device_mut(tracker).unwrap().connected = false;
+        assert_eq!(
+            watcher.poll(&mut system),
+            [PoseEvent::TrackingChanged {
+                device: tracker,
+                valid: false,
+                pose,
+            }]
+        );
+        assert_eq!(watcher.get(tracker), None);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let (sin, cos) = 20f32.to_radians().sin_cos();
+        #[rustfmt::skip]
+        let rotated = Matrix3x4([
+            [cos, -sin, 0., 0.],
+            [sin, cos,  0., 0.],
+            [0.,  0.,   1., 0.],
+        ]);
+        let threshold = 10f32.to_radians();
+        assert!(moved(&Matrix3x4::IDENTITY, &rotated, 0.01, threshold));
+        assert!(!moved(
+            &Matrix3x4::IDENTITY,
+            &rotated,
+            0.01,
+            30f32.to_radians()
+        ));
+        assert!(!moved(&rotated, &rotated, 0.01, threshold));
+    }
+}
@@ -0,0 +1,133 @@
+//! Tracks whether the user is wearing and using the headset.
+//!
+//! ```no_run
+//! # use ovr_overlay::presence::{PresenceMonitor, UserPresence};
+//! # use ovr_overlay::system::SystemManager;
+//! # fn example(system: &mut SystemManager) {
+//! let mut presence = PresenceMonitor::new();
+//! loop {
+//!     while let Some(event) = system.poll_next_event() {
+//!         presence.handle_event(&event);
+//!     }
+//!     if let Some(change) = presence.update(system) {
+//!         if change.to == UserPresence::Standby {
+//!             // the user took the headset off a while ago
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+
+use crate::system::VREvent;
+use crate::traits::System;
+use crate::{sys, TrackedDeviceIndex};
+
+/// What the user is doing with the headset, as far as OpenVR can tell.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum UserPresence {
+    /// The headset is being worn.
+    Active,
+    /// The headset hasn't been worn or moved recently.
+    Idle,
+    /// The headset went into standby after being idle.
+    Standby,
+    /// There is no headset connected.
+    HmdOff,
+}
+
+/// A transition reported by [`PresenceMonitor::update()`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PresenceChange {
+    pub from: UserPresence,
+    pub to: UserPresence,
+}
+
+/// Combines the headset's activity level and standby events into a [`UserPresence`].
+#[derive(Debug)]
+pub struct PresenceMonitor {
+    presence: UserPresence,
+    standby: bool,
+}
+
+impl Default for PresenceMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PresenceMonitor {
+    /// Starts out [`UserPresence::HmdOff`] until the first [`Self::update()`].
+    pub fn new() -> Self {
+        Self {
+            presence: UserPresence::HmdOff,
+            standby: false,
+        }
+    }
+
+    pub fn presence(&self) -> UserPresence {
+        self.presence
+    }
+
+    /// Feeds an event polled from [`System::poll_next_event()`] to the monitor,
+    /// so it can notice the runtime entering and leaving standby.
+    pub fn handle_event(&mut self, event: &VREvent) {
+        match event.event_type {
+            sys::EVREventType::VREvent_EnterStandbyMode => self.standby = true,
+            sys::EVREventType::VREvent_LeaveStandbyMode => self.standby = false,
+            _ => {}
+        }
+    }
+
+    /// Reads the headset's state, returning the transition if it changed.
+    pub fn update(&mut self, system: &mut (impl System + ?Sized)) -> Option<PresenceChange> {
+        let presence = if system.is_tracked_device_connected(TrackedDeviceIndex::HMD) {
+            classify(
+                system.get_tracked_device_activity_level(TrackedDeviceIndex::HMD),
+                self.standby,
+            )
+        } else {
+            UserPresence::HmdOff
+        };
+        let from = std::mem::replace(&mut self.presence, presence);
+        (from != presence).then_some(PresenceChange { from, to: presence })
+    }
+}
+
+/// The presence of a connected headset.
+fn classify(activity: sys::EDeviceActivityLevel, standby: bool) -> UserPresence {
+    use sys::EDeviceActivityLevel::*;
+    match activity {
+        _ if standby => UserPresence::Standby,
+        k_EDeviceActivityLevel_Standby => UserPresence::Standby,
+        k_EDeviceActivityLevel_UserInteraction | k_EDeviceActivityLevel_UserInteraction_Timeout => {
+            UserPresence::Active
+        }
+        _ => UserPresence::Idle,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sys::EDeviceActivityLevel::*;
+
+    #[test]
+    fn standby_event_overrides_activity() {
+        assert_eq!(
+            classify(k_EDeviceActivityLevel_UserInteraction, false),
+            UserPresence::Active
+        );
+        assert_eq!(
+            classify(k_EDeviceActivityLevel_UserInteraction, true),
+            UserPresence::Standby
+        );
+        assert_eq!(
+            classify(k_EDeviceActivityLevel_Idle_Timeout, false),
+            UserPresence::Idle
+        );
+        assert_eq!(
+            classify(k_EDeviceActivityLevel_Standby, false),
+            UserPresence::Standby
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use crate::pose::Matrix3x4;
+use crate::strings::get_string;
+use crate::{errors::EVRRenderModelError, sys, Context, Interface, InterfaceLock};
+
+use enumset::{EnumSet, EnumSetType};
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+pub mod mesh;
+
+pub struct RenderModelsManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVRRenderModels>,
+    _lock: InterfaceLock<'c>,
+}
+
+type Result<T> = std::result::Result<T, EVRRenderModelError>;
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[repr(C)]
+pub struct Vertex {
+    pub position: [f32; 3],
+    pub normal: [f32; 3],
+    pub texture_coord: [f32; 2],
+}
+impl From<&sys::RenderModel_Vertex_t> for Vertex {
+    fn from(v: &sys::RenderModel_Vertex_t) -> Self {
+        Self {
+            position: v.vPosition.v,
+            normal: v.vNormal.v,
+            texture_coord: v.rfTextureCoord,
+        }
+    }
+}
+
+/// A render model copied out of OpenVR's memory.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RenderModel {
+    pub vertices: Vec<Vertex>,
+    /// Triangle list, three indices into `vertices` per triangle.
+    pub indices: Vec<u16>,
+    /// Id of the diffuse texture, if the model has one.
+    pub diffuse_texture_id: Option<sys::TextureID_t>,
+}
+impl RenderModel {
+    /// # Safety
+    /// `model` must point to a fully loaded `RenderModel_t`.
+    unsafe fn from_raw(model: &sys::RenderModel_t) -> Self {
+        let vertices = if model.unVertexCount == 0 {
+            Vec::new()
+        } else {
+            std::slice::from_raw_parts(model.rVertexData, model.unVertexCount as usize)
+                .iter()
+                .map(Vertex::from)
+                .collect()
+        };
+        let indices = if model.unTriangleCount == 0 {
+            Vec::new()
+        } else {
+            std::slice::from_raw_parts(model.rIndexData, model.unTriangleCount as usize * 3)
+                .to_vec()
+        };
+        let diffuse_texture_id = if model.diffuseTextureId < 0 {
+            None
+        } else {
+            Some(model.diffuseTextureId)
+        };
+        Self {
+            vertices,
+            indices,
+            diffuse_texture_id,
+        }
+    }
+}
+
+/// Properties of a render model component in a given controller state.
+#[derive(EnumSetType, Debug)]
+#[enumset(repr = "u32")]
+pub enum ComponentProperty {
+    IsStatic,
+    IsVisible,
+    IsTouched,
+    IsPressed,
+    IsScrolled,
+    IsHighlighted,
+}
+
+/// The pose and properties of a render model component.
+#[derive(Debug, PartialEq)]
+pub struct ComponentState {
+    /// Transform to apply to the component's render model, in tracking space.
+    pub tracking_to_component_render_model: Matrix3x4,
+    /// Transform to the component's local coordinate system, for attaching things.
+    pub tracking_to_component_local: Matrix3x4,
+    pub properties: EnumSet<ComponentProperty>,
+}
+
+fn to_cstring(s: &str) -> Result<CString> {
+    CString::new(s).map_err(|_| {
+        EVRRenderModelError::new(sys::EVRRenderModelError::VRRenderModelError_InvalidArg)
+            .unwrap_err()
+    })
+}
+
+impl<'c> RenderModelsManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::RenderModels) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+        }
+    }
+
+    /// Polls the loading of the render model called `name`. Returns `Ok(None)` while
+    /// the model is still loading, in which case this should be called again later,
+    /// for example on the next frame.
+    ///
+    /// The model is copied out and freed from OpenVR once it has loaded.
+    ///
+    /// Wraps c++ `LoadRenderModel_Async` and `FreeRenderModel`.
+    pub fn load_render_model(&mut self, name: &str) -> Result<Option<RenderModel>> {
+        let name = to_cstring(name)?;
+        let mut model: *mut sys::RenderModel_t = std::ptr::null_mut();
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .LoadRenderModel_Async(name.as_ptr(), &mut model)
+        };
+        if err == sys::EVRRenderModelError::VRRenderModelError_Loading {
+            return Ok(None);
+        }
+        EVRRenderModelError::new(err)?;
+        if model.is_null() {
+            return EVRRenderModelError::new(
+                sys::EVRRenderModelError::VRRenderModelError_InvalidModel,
+            )
+            .map(|_| unreachable!());
+        }
+        let result = unsafe { RenderModel::from_raw(&*model) };
+        unsafe { self.inner.as_mut().FreeRenderModel(model) };
+        Ok(Some(result))
+    }
+
+    // ---- Names ----
+
+    /// Returns the number of render models SteamVR knows about.
+    ///
+    /// Wraps c++ `GetRenderModelCount`.
+    pub fn get_render_model_count(&mut self) -> u32 {
+        unsafe { self.inner.as_mut().GetRenderModelCount() }
+    }
+
+    /// Returns the name of the render model at `index`, or `None` if `index` is out of range.
+    ///
+    /// Wraps c++ `GetRenderModelName`.
+    pub fn get_render_model_name(&mut self, index: u32) -> Option<String> {
+        unsafe { get_string(|ptr, n| self.inner.as_mut().GetRenderModelName(index, ptr, n)) }
+    }
+
+    /// Returns the names of all render models SteamVR knows about.
+    pub fn get_render_model_names(&mut self) -> Vec<String> {
+        (0..self.get_render_model_count())
+            .filter_map(|index| self.get_render_model_name(index))
+            .collect()
+    }
+
+    /// Wraps c++ `GetRenderModelThumbnailURL`.
+    pub fn get_render_model_thumbnail_url(&mut self, name: &str) -> Result<String> {
+        let name = to_cstring(name)?;
+        let mut err = sys::EVRRenderModelError::VRRenderModelError_None;
+        let url = unsafe {
+            get_string(|ptr, n| {
+                self.inner
+                    .as_mut()
+                    .GetRenderModelThumbnailURL(name.as_ptr(), ptr, n, &mut err)
+            })
+        };
+        EVRRenderModelError::new(err)?;
+        Ok(url.unwrap_or_default())
+    }
+
+    /// Returns the path of the file the render model was loaded from.
+    ///
+    /// Wraps c++ `GetRenderModelOriginalPath`.
+    pub fn get_render_model_original_path(&mut self, name: &str) -> Result<String> {
+        let name = to_cstring(name)?;
+        let mut err = sys::EVRRenderModelError::VRRenderModelError_None;
+        let path = unsafe {
+            get_string(|ptr, n| {
+                self.inner
+                    .as_mut()
+                    .GetRenderModelOriginalPath(name.as_ptr(), ptr, n, &mut err)
+            })
+        };
+        EVRRenderModelError::new(err)?;
+        Ok(path.unwrap_or_default())
+    }
+
+    // ---- Components ----
+
+    /// Returns the number of components of the render model called `name`.
+    ///
+    /// Wraps c++ `GetComponentCount`.
+    pub fn get_component_count(&mut self, name: &str) -> Result<u32> {
+        let name = to_cstring(name)?;
+        Ok(unsafe { self.inner.as_mut().GetComponentCount(name.as_ptr()) })
+    }
+
+    /// Returns the name of the component at `index`, or `None` if `index` is out of range.
+    ///
+    /// Wraps c++ `GetComponentName`.
+    pub fn get_component_name(&mut self, name: &str, index: u32) -> Result<Option<String>> {
+        let name = to_cstring(name)?;
+        Ok(unsafe {
+            get_string(|ptr, n| {
+                self.inner
+                    .as_mut()
+                    .GetComponentName(name.as_ptr(), index, ptr, n)
+            })
+        })
+    }
+
+    /// Returns the names of all components of the render model called `name`.
+    pub fn get_component_names(&mut self, name: &str) -> Result<Vec<String>> {
+        let count = self.get_component_count(name)?;
+        let mut names = Vec::with_capacity(count as usize);
+        for index in 0..count {
+            if let Some(component) = self.get_component_name(name, index)? {
+                names.push(component);
+            }
+        }
+        Ok(names)
+    }
+
+    /// Returns the render model to draw for a component, or `None` if the component
+    /// has no geometry.
+    ///
+    /// Wraps c++ `GetComponentRenderModelName`.
+    pub fn get_component_render_model_name(
+        &mut self,
+        name: &str,
+        component: &str,
+    ) -> Result<Option<String>> {
+        let name = to_cstring(name)?;
+        let component = to_cstring(component)?;
+        Ok(unsafe {
+            get_string(|ptr, n| {
+                self.inner.as_mut().GetComponentRenderModelName(
+                    name.as_ptr(),
+                    component.as_ptr(),
+                    ptr,
+                    n,
+                )
+            })
+        })
+    }
+
+    /// Returns the mask of button ids that affect the component.
+    ///
+    /// Wraps c++ `GetComponentButtonMask`.
+    pub fn get_component_button_mask(&mut self, name: &str, component: &str) -> Result<u64> {
+        let name = to_cstring(name)?;
+        let component = to_cstring(component)?;
+        Ok(unsafe {
+            self.inner
+                .as_mut()
+                .GetComponentButtonMask(name.as_ptr(), component.as_ptr())
+        })
+    }
+
+    /// Returns the current state of a component for the device at `device_path`,
+    /// as driven by the user's input. Returns `Ok(None)` if the state is unavailable.
+    ///
+    /// Wraps c++ `GetComponentStateForDevicePath`.
+    pub fn get_component_state_for_device_path(
+        &mut self,
+        name: &str,
+        component: &str,
+        device_path: impl Into<sys::VRInputValueHandle_t>,
+        scroll_wheel_visible: bool,
+    ) -> Result<Option<ComponentState>> {
+        let name = to_cstring(name)?;
+        let component = to_cstring(component)?;
+        let mode = sys::RenderModel_ControllerMode_State_t {
+            bScrollWheelVisible: scroll_wheel_visible,
+        };
+        let mut state = std::mem::MaybeUninit::<sys::RenderModel_ComponentState_t>::uninit();
+        let success = unsafe {
+            self.inner.as_mut().GetComponentStateForDevicePath(
+                name.as_ptr(),
+                component.as_ptr(),
+                device_path.into(),
+                &mode,
+                state.as_mut_ptr(),
+            )
+        };
+        if !success {
+            return Ok(None);
+        }
+        let state = unsafe { state.assume_init() };
+        Ok(Some(ComponentState {
+            tracking_to_component_render_model: state.mTrackingToComponentRenderModel.into(),
+            tracking_to_component_local: state.mTrackingToComponentLocal.into(),
+            properties: EnumSet::from_u32_truncated(state.uProperties),
+        }))
+    }
+}
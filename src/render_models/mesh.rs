@@ -0,0 +1,106 @@
+//! Converts [`RenderModel`]s into separate vertex attribute arrays, which most renderers
+//! and mesh crates take, and writes them as Wavefront OBJ files.
+//!
+//! ```no_run
+//! # use ovr_overlay::render_models::RenderModelsManager;
+//! # use ovr_overlay::render_models::mesh::Mesh;
+//! # fn example(models: &mut RenderModelsManager) -> std::io::Result<()> {
+//! let Some(model) = models.load_render_model("vr_controller_vive_1_5").unwrap() else {
+//!     return Ok(()); // Still loading.
+//! };
+//! let file = std::fs::File::create("controller.obj")?;
+//! Mesh::from(&model).write_obj(std::io::BufWriter::new(file))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::RenderModel;
+
+use std::io::{self, Write};
+
+/// The vertices of a render model as one array per attribute, all of the same length.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Mesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    /// With the origin at the top left of the texture, like OpenVR and most graphics
+    /// APIs.
+    pub texture_coords: Vec<[f32; 2]>,
+    /// Triangle list, three indices into the vertex arrays per triangle.
+    pub indices: Vec<u32>,
+}
+
+impl From<&RenderModel> for Mesh {
+    fn from(model: &RenderModel) -> Self {
+        Self {
+            positions: model.vertices.iter().map(|v| v.position).collect(),
+            normals: model.vertices.iter().map(|v| v.normal).collect(),
+            texture_coords: model.vertices.iter().map(|v| v.texture_coord).collect(),
+            indices: model.indices.iter().map(|&i| i as u32).collect(),
+        }
+    }
+}
+
+impl Mesh {
+    /// The triangles, as three indices each. A trailing incomplete triangle is skipped.
+    pub fn triangles(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        self.indices
+            .chunks_exact(3)
+            .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+    }
+
+    /// Writes the mesh as a Wavefront OBJ file, without a material. Texture
+    /// coordinates are flipped vertically, since OBJ has its origin at the bottom left.
+    ///
+    /// Writes many small pieces, so `out` should be buffered.
+    pub fn write_obj(&self, mut out: impl Write) -> io::Result<()> {
+        for [x, y, z] in &self.positions {
+            writeln!(out, "v {x} {y} {z}")?;
+        }
+        for [x, y, z] in &self.normals {
+            writeln!(out, "vn {x} {y} {z}")?;
+        }
+        for [u, v] in &self.texture_coords {
+            writeln!(out, "vt {u} {}", 1. - v)?;
+        }
+        for triangle in self.triangles() {
+            // OBJ indices start at 1.
+            let [a, b, c] = triangle.map(|i| i + 1);
+            writeln!(out, "f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}")?;
+        }
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::render_models::Vertex;
+
+    #[test]
+    fn test_write_obj() {
+        let vertex = |x, u| Vertex {
+            position: [x, 0., 0.],
+            normal: [0., 1., 0.],
+            texture_coord: [u, 0.25],
+        };
+        let model = RenderModel {
+            vertices: vec![vertex(0., 0.), vertex(1., 1.), vertex(0.5, 0.5)],
+            indices: vec![0, 1, 2],
+            diffuse_texture_id: None,
+        };
+        let mesh = Mesh::from(&model);
+        assert_eq!(mesh.positions[1], [1., 0., 0.]);
+        assert_eq!(mesh.triangles().collect::<Vec<_>>(), [[0, 1, 2]]);
+
+        let mut obj = Vec::new();
+        mesh.write_obj(&mut obj).unwrap();
+        let obj = String::from_utf8(obj).unwrap();
+        let lines: Vec<&str> = obj.lines().collect();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], "v 0 0 0");
+        assert_eq!(lines[3], "vn 0 1 0");
+        assert_eq!(lines[8], "vt 0.5 0.75");
+        assert_eq!(lines[9], "f 1/1/1 2/2/2 3/3/3");
+    }
+}
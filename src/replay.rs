@@ -0,0 +1,390 @@
+//! Recording of device poses and events, for replaying a session offline through
+//! [`MockSystem`].
+//!
+//! A [`Recorder`] takes the place of polling events each frame, and the resulting
+//! [`Recording`] can be saved to any writer. A [`Player`] later feeds it back into a
+//! [`MockSystem`], either at the original timing or one frame at a time for tests.
+//!
+//! Recordings store enum values as their raw numbers. [`Recording::load()`] rejects values
+//! that aren't known variants, and [`Player`] skips them in recordings built by hand.
+
+use std::io::{Read, Write};
+use std::time::{Duration, Instant};
+
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+
+use crate::mock::{MockDevice, MockSystem};
+use crate::pose::{Matrix3x4, TrackingUniverseOrigin};
+use crate::system::{event_types, VREvent};
+use crate::traits::System;
+use crate::{sys, TrackedDeviceIndex};
+
+const VERSION: u32 = 1;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReplayError {
+    #[error("failed to encode or decode recording: {0}")]
+    Encoding(#[from] bincode::Error),
+    #[error("unsupported recording version {0}, expected {VERSION}")]
+    UnsupportedVersion(u32),
+    #[error("invalid {field} {value} in recording")]
+    InvalidValue { field: &'static str, value: u32 },
+}
+
+fn device_class(raw: u32) -> Option<sys::ETrackedDeviceClass> {
+    use sys::ETrackedDeviceClass::*;
+    [
+        TrackedDeviceClass_Invalid,
+        TrackedDeviceClass_HMD,
+        TrackedDeviceClass_Controller,
+        TrackedDeviceClass_GenericTracker,
+        TrackedDeviceClass_TrackingReference,
+        TrackedDeviceClass_DisplayRedirect,
+    ]
+    .into_iter()
+    .find(|class| class.clone() as u32 == raw)
+}
+
+fn controller_role(raw: u32) -> Option<sys::ETrackedControllerRole> {
+    use sys::ETrackedControllerRole::*;
+    [
+        TrackedControllerRole_Invalid,
+        TrackedControllerRole_LeftHand,
+        TrackedControllerRole_RightHand,
+        TrackedControllerRole_OptOut,
+        TrackedControllerRole_Treadmill,
+        TrackedControllerRole_Stylus,
+    ]
+    .into_iter()
+    .find(|role| role.clone() as u32 == raw)
+}
+
+/// A connected device in a [`Frame`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RecordedDevice {
+    pub index: u32,
+    pub class: u32,
+    pub role: u32,
+    pub pose: Matrix3x4,
+}
+
+/// A [`VREvent`] in a [`Frame`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct RecordedEvent {
+    pub event_type: u32,
+    pub tracked_device_index: u32,
+    pub event_age_seconds: f32,
+    pub data: Vec<u8>,
+}
+
+impl From<&VREvent> for RecordedEvent {
+    fn from(event: &VREvent) -> Self {
+        Self {
+            event_type: event.event_type.clone() as u32,
+            tracked_device_index: event.tracked_device_index.0,
+            event_age_seconds: event.event_age_seconds,
+            data: event.data.to_vec(),
+        }
+    }
+}
+
+impl RecordedEvent {
+    /// `None` if the event type isn't known.
+    fn to_event(&self) -> Option<VREvent> {
+        let event_type = event_types::from_raw(self.event_type)?;
+        let mut event = VREvent::new(event_type, TrackedDeviceIndex(self.tracked_device_index));
+        event.event_age_seconds = self.event_age_seconds;
+        let len = self.data.len().min(event.data.len());
+        event.data[..len].copy_from_slice(&self.data[..len]);
+        Some(event)
+    }
+}
+
+/// The state recorded in one call to [`Recorder::record()`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Frame {
+    /// Time since the recording started.
+    pub time: Duration,
+    pub devices: Vec<RecordedDevice>,
+    pub events: Vec<RecordedEvent>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Recording {
+    version: u32,
+    pub frames: Vec<Frame>,
+}
+
+impl Recording {
+    pub fn save(&self, writer: impl Write) -> Result<(), ReplayError> {
+        Ok(bincode::options().serialize_into(writer, self)?)
+    }
+
+    pub fn load(reader: impl Read) -> Result<Self, ReplayError> {
+        let recording: Self = bincode::options().deserialize_from(reader)?;
+        if recording.version != VERSION {
+            return Err(ReplayError::UnsupportedVersion(recording.version));
+        }
+        recording.validate()?;
+        Ok(recording)
+    }
+
+    /// Checks that every enum value is a known variant.
+    fn validate(&self) -> Result<(), ReplayError> {
+        let invalid = |field, value| ReplayError::InvalidValue { field, value };
+        for frame in &self.frames {
+            for device in &frame.devices {
+                device_class(device.class).ok_or(invalid("device class", device.class))?;
+                controller_role(device.role).ok_or(invalid("controller role", device.role))?;
+            }
+            for event in &frame.events {
+                event_types::from_raw(event.event_type)
+                    .ok_or(invalid("event type", event.event_type))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct Recorder {
+    start: Instant,
+    recording: Recording,
+    poses: Vec<sys::TrackedDevicePose_t>,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            recording: Recording {
+                version: VERSION,
+                frames: Vec::new(),
+            },
+            poses: (0..TrackedDeviceIndex::MAX)
+                .map(|_| unsafe { std::mem::zeroed() })
+                .collect(),
+        }
+    }
+
+    /// Polls all pending events and records them along with the current device poses.
+    /// Events of types not listed in [`event_types`] aren't recorded.
+    ///
+    /// The events are returned for the application to handle as usual.
+    pub fn record(
+        &mut self,
+        system: &mut impl System,
+        origin: TrackingUniverseOrigin,
+    ) -> Vec<VREvent> {
+        let events: Vec<VREvent> = std::iter::from_fn(|| system.poll_next_event()).collect();
+        system.get_device_to_absolute_tracking_pose_into(origin, 0.0, &mut self.poses);
+
+        let mut devices = Vec::new();
+        for (i, pose) in self.poses.iter().enumerate() {
+            if !pose.bDeviceIsConnected {
+                continue;
+            }
+            let index = TrackedDeviceIndex(i as u32);
+            devices.push(RecordedDevice {
+                index: index.0,
+                class: system.get_tracked_device_class(index) as u32,
+                role: system.get_controller_role_for_tracked_device_index(index) as u32,
+                pose: *<&Matrix3x4>::from(&pose.mDeviceToAbsoluteTracking),
+            });
+        }
+
+        self.recording.frames.push(Frame {
+            time: self.start.elapsed(),
+            devices,
+            events: events
+                .iter()
+                // Recordings with unknown event types couldn't be loaded again.
+                .filter(|event| event_types::name(&event.event_type).is_some())
+                .map(RecordedEvent::from)
+                .collect(),
+        });
+        events
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    pub fn finish(self) -> Recording {
+        self.recording
+    }
+}
+
+/// Plays a [`Recording`] back into a [`MockSystem`].
+///
+/// Devices in a frame are created or updated, devices missing from it are marked as
+/// disconnected, and its events are queued. Devices and events with unknown enum values
+/// are skipped.
+pub struct Player {
+    recording: Recording,
+    next: usize,
+    start: Option<Instant>,
+}
+
+impl Player {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            next: 0,
+            start: None,
+        }
+    }
+
+    /// Applies every frame that is due at the original timing, counted from the first
+    /// call. Returns `false` once all frames have been applied.
+    pub fn update(&mut self, system: &mut MockSystem) -> bool {
+        let elapsed = self.start.get_or_insert_with(Instant::now).elapsed();
+        while self
+            .recording
+            .frames
+            .get(self.next)
+            .is_some_and(|frame| frame.time <= elapsed)
+        {
+            self.step(system);
+        }
+        !self.is_finished()
+    }
+
+    /// Applies the next frame regardless of timing. Returns `false` if there was none.
+    pub fn step(&mut self, system: &mut MockSystem) -> bool {
+        let Some(frame) = self.recording.frames.get(self.next) else {
+            return false;
+        };
+        self.next += 1;
+
+        for i in 0..TrackedDeviceIndex::MAX as u32 {
+            let index = TrackedDeviceIndex(i);
+            let recorded = frame
+                .devices
+                .iter()
+                .find(|d| d.index == i)
+                .and_then(|d| Some((d, device_class(d.class)?, controller_role(d.role)?)));
+            match (system.device_mut(index), recorded) {
+                (Some(device), Some((recorded, class, role))) => {
+                    device.class = class;
+                    device.role = role;
+                    device.pose = recorded.pose;
+                    device.connected = true;
+                }
+                (None, Some((recorded, class, role))) => {
+                    let mut device = MockDevice::new(class).with_role(role);
+                    device.pose = recorded.pose;
+                    system.set_device(index, device);
+                }
+                (Some(device), None) => device.connected = false,
+                (None, None) => {}
+            }
+        }
+
+        for event in frame.events.iter().filter_map(RecordedEvent::to_event) {
+            system.push_event(event);
+        }
+        true
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.frames.len()
+    }
+
+    /// Time from the first [`update()`](Self::update) until the next frame is due.
+    pub fn next_frame_time(&self) -> Option<Duration> {
+        self.recording.frames.get(self.next).map(|frame| frame.time)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let left = TrackedDeviceIndex(3);
+        let mut live = MockSystem::new();
+        live.set_device(
+            TrackedDeviceIndex::HMD,
+            MockDevice::new(sys::ETrackedDeviceClass::TrackedDeviceClass_HMD),
+        );
+        live.set_device(
+            left,
+            MockDevice::new(sys::ETrackedDeviceClass::TrackedDeviceClass_Controller)
+                .with_role(sys::ETrackedControllerRole::TrackedControllerRole_LeftHand),
+        );
+        live.device_mut(left).unwrap().pose = Matrix3x4::from_translation([0.1, 1.0, -0.3]);
+        live.push_event(VREvent::new(sys::EVREventType::VREvent_ButtonPress, left));
+
+        let mut recorder = Recorder::new();
+        assert_eq!(
+            recorder
+                .record(&mut live, TrackingUniverseOrigin::TrackingUniverseStanding)
+                .len(),
+            1
+        );
+        live.device_mut(left).unwrap().connected = false;
+        recorder.record(&mut live, TrackingUniverseOrigin::TrackingUniverseStanding);
+
+        let mut file = Vec::new();
+        recorder.finish().save(&mut file).unwrap();
+        let mut player = Player::new(Recording::load(file.as_slice()).unwrap());
+
+        let mut replayed = MockSystem::new();
+        assert!(player.step(&mut replayed));
+        assert_eq!(
+            replayed.get_tracked_device_index_for_controller_role(
+                sys::ETrackedControllerRole::TrackedControllerRole_LeftHand
+            ),
+            Some(left)
+        );
+        assert_eq!(
+            replayed.device(left).unwrap().pose.translation(),
+            [0.1, 1.0, -0.3]
+        );
+        let event = replayed.poll_next_event().unwrap();
+        assert!(event.event_type == sys::EVREventType::VREvent_ButtonPress);
+        assert_eq!(event.tracked_device_index, left);
+
+        assert!(player.step(&mut replayed));
+        assert!(!replayed.is_tracked_device_connected(left));
+        assert!(replayed.is_tracked_device_connected(TrackedDeviceIndex::HMD));
+        assert!(!player.step(&mut replayed));
+        assert!(player.is_finished());
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let mut recording = Recorder::new().finish();
+        recording.frames.push(Frame {
+            time: Duration::ZERO,
+            devices: Vec::new(),
+            events: vec![RecordedEvent {
+                event_type: u32::MAX,
+                tracked_device_index: 0,
+                event_age_seconds: 0.0,
+                data: Vec::new(),
+            }],
+        });
+        let mut file = Vec::new();
+        recording.save(&mut file).unwrap();
+        assert!(matches!(
+            Recording::load(file.as_slice()),
+            Err(ReplayError::InvalidValue {
+                field: "event type",
+                value: u32::MAX
+            })
+        ));
+
+        let mut replayed = MockSystem::new();
+        assert!(Player::new(recording).step(&mut replayed));
+        assert!(replayed.poll_next_event().is_none());
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{sys, Context, Interface, InterfaceLock};
+
+use std::ffi::CString;
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+pub mod sounds;
+
+/// Access to the files shipped with SteamVR, like its sounds and icons.
+pub struct ResourcesManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVRResources>,
+    _lock: InterfaceLock<'c>,
+}
+
+impl<'c> ResourcesManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::Resources) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+        }
+    }
+
+    /// Reads the resource called `name`, like `icons/headset_status.png`. Returns `None`
+    /// if it doesn't exist, or `name` contains a nul byte.
+    ///
+    /// Wraps c++ `LoadSharedResource`.
+    pub fn load_shared_resource(&mut self, name: &str) -> Option<Vec<u8>> {
+        let name = CString::new(name).ok()?;
+        let len = unsafe {
+            self.inner
+                .as_mut()
+                .LoadSharedResource(name.as_ptr(), std::ptr::null_mut(), 0)
+        };
+        if len == 0 {
+            return None;
+        }
+        let mut data = vec![0u8; len as usize];
+        let len = unsafe {
+            self.inner.as_mut().LoadSharedResource(
+                name.as_ptr(),
+                data.as_mut_ptr() as *mut c_char,
+                data.len() as u32,
+            )
+        };
+        data.truncate(len as usize);
+        Some(data)
+    }
+
+    /// The path of the resource called `name` in `directory`, like `sounds`, looked up
+    /// in the current application's and SteamVR's resources. Returns `None` if it
+    /// doesn't exist, or either argument contains a nul byte.
+    ///
+    /// Wraps c++ `GetResourceFullPath`.
+    pub fn get_resource_full_path(&mut self, name: &str, directory: &str) -> Option<PathBuf> {
+        let name = CString::new(name).ok()?;
+        let directory = CString::new(directory).ok()?;
+        let len = unsafe {
+            self.inner.as_mut().GetResourceFullPath(
+                name.as_ptr(),
+                directory.as_ptr(),
+                std::ptr::null_mut(),
+                0,
+            )
+        };
+        if len == 0 {
+            return None;
+        }
+        let mut buf = vec![0u8; len as usize];
+        unsafe {
+            self.inner.as_mut().GetResourceFullPath(
+                name.as_ptr(),
+                directory.as_ptr(),
+                buf.as_mut_ptr() as *mut c_char,
+                buf.len() as u32,
+            )
+        };
+        let end = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+        buf.truncate(end);
+        let path = String::from_utf8(buf).ok()?;
+        (!path.is_empty()).then(|| PathBuf::from(path))
+    }
+}
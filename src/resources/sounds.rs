@@ -0,0 +1,93 @@
+//! Finds SteamVR's own sounds, so overlays can give the same audio feedback as the
+//! system UI, and optionally plays them with `rodio`.
+//!
+//! ```no_run
+//! # use ovr_overlay::resources::ResourcesManager;
+//! # fn example(resources: &mut ResourcesManager) {
+//! # #[cfg(feature = "rodio")]
+//! # {
+//! use ovr_overlay::resources::sounds::SoundPlayer;
+//!
+//! let player = SoundPlayer::new().unwrap().with_volume(0.5);
+//! if let Some(path) = resources.resolve_sound("notification.wav") {
+//!     player.play(&path).unwrap();
+//! }
+//! # }
+//! # }
+//! ```
+//!
+//! The names of SteamVR's sounds aren't part of the OpenVR API and can change between
+//! SteamVR versions, so check that they resolve instead of relying on them.
+
+use super::ResourcesManager;
+
+use std::path::PathBuf;
+
+/// The resource directory SteamVR's sounds are in.
+pub const SOUNDS_DIRECTORY: &str = "sounds";
+
+impl ResourcesManager<'_> {
+    /// The path of the sound file called `name`, like `notification.wav`, if it exists.
+    pub fn resolve_sound(&mut self, name: &str) -> Option<PathBuf> {
+        self.get_resource_full_path(name, SOUNDS_DIRECTORY)
+    }
+}
+
+#[cfg(feature = "rodio")]
+pub use self::player::{SoundError, SoundPlayer};
+#[cfg(feature = "rodio")]
+mod player {
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::path::Path;
+
+    #[derive(Debug, thiserror::Error)]
+    pub enum SoundError {
+        #[error("failed to open the audio output: {0}")]
+        Stream(#[from] rodio::StreamError),
+        #[error("failed to open the sound file: {0}")]
+        Io(#[from] std::io::Error),
+        #[error("failed to decode the sound: {0}")]
+        Decoder(#[from] rodio::decoder::DecoderError),
+        #[error("failed to play the sound: {0}")]
+        Play(#[from] rodio::PlayError),
+    }
+
+    /// Plays sound files on the default audio output, which is usually the headset
+    /// while SteamVR is running.
+    ///
+    /// Not `Send`, because the audio output isn't on every platform.
+    pub struct SoundPlayer {
+        // Playback stops when the stream is dropped.
+        _stream: rodio::OutputStream,
+        handle: rodio::OutputStreamHandle,
+        volume: f32,
+    }
+
+    impl SoundPlayer {
+        pub fn new() -> Result<Self, SoundError> {
+            let (stream, handle) = rodio::OutputStream::try_default()?;
+            Ok(Self {
+                _stream: stream,
+                handle,
+                volume: 1.,
+            })
+        }
+
+        /// Scales the volume of the sounds, 1 being unchanged.
+        pub fn with_volume(mut self, volume: f32) -> Self {
+            self.volume = volume;
+            self
+        }
+
+        /// Starts playing the sound at `path` without waiting for it to finish.
+        pub fn play(&self, path: &Path) -> Result<(), SoundError> {
+            let source = rodio::Decoder::new(BufReader::new(File::open(path)?))?;
+            let sink = rodio::Sink::try_new(&self.handle)?;
+            sink.set_volume(self.volume);
+            sink.append(source);
+            sink.detach();
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,395 @@
+#[cfg(feature = "ovr_system")]
+use crate::system::VREvent;
+use crate::{errors::EVRScreenshotError, sys, Context, Interface, InterfaceLock};
+
+#[cfg(feature = "ovr_system")]
+use byteorder::ByteOrder;
+use derive_more::{From, Into};
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+pub struct ScreenshotManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVRScreenshots>,
+    _lock: InterfaceLock<'c>,
+}
+
+type Result<T> = std::result::Result<T, EVRScreenshotError>;
+
+#[derive(From, Into, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[repr(transparent)]
+pub struct ScreenshotHandle(pub sys::ScreenshotHandle_t);
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ScreenshotType {
+    Mono,
+    Stereo,
+    Cubemap,
+    MonoPanorama,
+    StereoPanorama,
+}
+impl From<ScreenshotType> for sys::EVRScreenshotType {
+    fn from(ty: ScreenshotType) -> Self {
+        use sys::EVRScreenshotType::*;
+        match ty {
+            ScreenshotType::Mono => VRScreenshotType_Mono,
+            ScreenshotType::Stereo => VRScreenshotType_Stereo,
+            ScreenshotType::Cubemap => VRScreenshotType_Cubemap,
+            ScreenshotType::MonoPanorama => VRScreenshotType_MonoPanorama,
+            ScreenshotType::StereoPanorama => VRScreenshotType_StereoPanorama,
+        }
+    }
+}
+impl TryFrom<sys::EVRScreenshotType> for ScreenshotType {
+    type Error = ();
+
+    fn try_from(ty: sys::EVRScreenshotType) -> std::result::Result<Self, ()> {
+        use sys::EVRScreenshotType::*;
+        Ok(match ty {
+            VRScreenshotType_None => return Err(()),
+            VRScreenshotType_Mono => Self::Mono,
+            VRScreenshotType_Stereo => Self::Stereo,
+            VRScreenshotType_Cubemap => Self::Cubemap,
+            VRScreenshotType_MonoPanorama => Self::MonoPanorama,
+            VRScreenshotType_StereoPanorama => Self::StereoPanorama,
+        })
+    }
+}
+
+/// Which of the files of a screenshot to refer to.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ScreenshotFile {
+    /// The flat image shown in the screenshot browser.
+    Preview,
+    /// The image in the format of the [`ScreenshotType`].
+    Vr,
+}
+impl From<ScreenshotFile> for sys::EVRScreenshotPropertyFilenames {
+    fn from(file: ScreenshotFile) -> Self {
+        use sys::EVRScreenshotPropertyFilenames::*;
+        match file {
+            ScreenshotFile::Preview => VRScreenshotPropertyFilenames_Preview,
+            ScreenshotFile::Vr => VRScreenshotPropertyFilenames_VR,
+        }
+    }
+}
+
+/// Returned by the screenshot calls that take file paths.
+#[derive(Debug, thiserror::Error)]
+pub enum ScreenshotPathError {
+    /// The path isn't valid UTF-8 or contains a nul byte, so OpenVR can't use it.
+    #[error("invalid screenshot path {0:?}")]
+    InvalidPath(PathBuf),
+    #[error("{0}")]
+    Screenshot(#[from] EVRScreenshotError),
+}
+
+fn path_to_cstring(path: &Path) -> std::result::Result<CString, ScreenshotPathError> {
+    path.to_str()
+        .and_then(|path| CString::new(path).ok())
+        .ok_or_else(|| ScreenshotPathError::InvalidPath(path.to_owned()))
+}
+
+impl<'c> ScreenshotManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::Screenshots) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+        }
+    }
+
+    /// Asks the scene application to take a screenshot. Filenames are full paths
+    /// without an extension.
+    ///
+    /// Wraps c++ `RequestScreenshot`.
+    pub fn request_screenshot(
+        &mut self,
+        ty: ScreenshotType,
+        preview_filename: &Path,
+        vr_filename: &Path,
+    ) -> std::result::Result<ScreenshotHandle, ScreenshotPathError> {
+        let preview_filename = path_to_cstring(preview_filename)?;
+        let vr_filename = path_to_cstring(vr_filename)?;
+        let mut handle: sys::ScreenshotHandle_t = 0;
+        let err = unsafe {
+            self.inner.as_mut().RequestScreenshot(
+                &mut handle,
+                ty.into(),
+                preview_filename.as_ptr(),
+                vr_filename.as_ptr(),
+            )
+        };
+        EVRScreenshotError::new(err)?;
+        Ok(ScreenshotHandle(handle))
+    }
+
+    /// Takes a stereo screenshot of what the compositor currently shows, without
+    /// involving the scene application. Filenames are full paths without an extension.
+    ///
+    /// Wraps c++ `TakeStereoScreenshot`.
+    pub fn take_stereo_screenshot(
+        &mut self,
+        preview_filename: &Path,
+        vr_filename: &Path,
+    ) -> std::result::Result<ScreenshotHandle, ScreenshotPathError> {
+        let preview_filename = path_to_cstring(preview_filename)?;
+        let vr_filename = path_to_cstring(vr_filename)?;
+        let mut handle: sys::ScreenshotHandle_t = 0;
+        let err = unsafe {
+            self.inner.as_mut().TakeStereoScreenshot(
+                &mut handle,
+                preview_filename.as_ptr(),
+                vr_filename.as_ptr(),
+            )
+        };
+        EVRScreenshotError::new(err)?;
+        Ok(ScreenshotHandle(handle))
+    }
+
+    /// Tells SteamVR that this application will take screenshots of the given types
+    /// itself. Requests then arrive as `VREvent_RequestScreenshot` events.
+    ///
+    /// Wraps c++ `HookScreenshot`.
+    pub fn hook_screenshot(&mut self, supported_types: &[ScreenshotType]) -> Result<()> {
+        let types: Vec<sys::EVRScreenshotType> =
+            supported_types.iter().map(|&ty| ty.into()).collect();
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .HookScreenshot(types.as_ptr(), types.len() as i32)
+        };
+        EVRScreenshotError::new(err)
+    }
+
+    /// Wraps c++ `GetScreenshotPropertyType`.
+    pub fn get_screenshot_property_type(
+        &mut self,
+        handle: ScreenshotHandle,
+    ) -> Result<Option<ScreenshotType>> {
+        let mut err = sys::EVRScreenshotError::VRScreenshotError_None;
+        let ty = unsafe {
+            self.inner
+                .as_mut()
+                .GetScreenshotPropertyType(handle.0, &mut err)
+        };
+        EVRScreenshotError::new(err)?;
+        Ok(ty.try_into().ok())
+    }
+
+    /// Returns the path of one of the files of a screenshot.
+    ///
+    /// Wraps c++ `GetScreenshotPropertyFilename`.
+    pub fn get_screenshot_property_filename(
+        &mut self,
+        handle: ScreenshotHandle,
+        file: ScreenshotFile,
+    ) -> Result<PathBuf> {
+        let file: sys::EVRScreenshotPropertyFilenames = file.into();
+        let mut err = sys::EVRScreenshotError::VRScreenshotError_None;
+        // Passing a null buffer merely returns the required length, including the nul.
+        let len = unsafe {
+            self.inner.as_mut().GetScreenshotPropertyFilename(
+                handle.0,
+                file.clone(),
+                std::ptr::null_mut(),
+                0,
+                &mut err,
+            )
+        };
+        if len == 0 {
+            EVRScreenshotError::new(err)?;
+            return Ok(PathBuf::new());
+        }
+        let mut buf = vec![0 as c_char; len as usize];
+        let mut err = sys::EVRScreenshotError::VRScreenshotError_None;
+        unsafe {
+            self.inner.as_mut().GetScreenshotPropertyFilename(
+                handle.0,
+                file,
+                buf.as_mut_ptr(),
+                len,
+                &mut err,
+            )
+        };
+        EVRScreenshotError::new(err)?;
+        let filename = unsafe { CStr::from_ptr(buf.as_ptr()) };
+        Ok(PathBuf::from(filename.to_string_lossy().into_owned()))
+    }
+
+    /// Reports the progress of a hooked screenshot, with `progress` in `[0,1]`.
+    ///
+    /// Wraps c++ `UpdateScreenshotProgress`.
+    pub fn update_screenshot_progress(
+        &mut self,
+        handle: ScreenshotHandle,
+        progress: f32,
+    ) -> Result<()> {
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .UpdateScreenshotProgress(handle.0, progress)
+        };
+        EVRScreenshotError::new(err)
+    }
+
+    /// Hands a finished screenshot to SteamVR. The source files are copied, so they
+    /// may be deleted afterwards.
+    ///
+    /// Wraps c++ `SubmitScreenshot`.
+    pub fn submit_screenshot(
+        &mut self,
+        handle: ScreenshotHandle,
+        ty: ScreenshotType,
+        source_preview_filename: &Path,
+        source_vr_filename: &Path,
+    ) -> std::result::Result<(), ScreenshotPathError> {
+        let source_preview_filename = path_to_cstring(source_preview_filename)?;
+        let source_vr_filename = path_to_cstring(source_vr_filename)?;
+        let err = unsafe {
+            self.inner.as_mut().SubmitScreenshot(
+                handle.0,
+                ty.into(),
+                source_preview_filename.as_ptr(),
+                source_vr_filename.as_ptr(),
+            )
+        };
+        Ok(EVRScreenshotError::new(err)?)
+    }
+}
+
+/// Where a [`PngScreenshot`] was saved.
+#[cfg(feature = "ovr_system")]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ScreenshotPaths {
+    pub preview: PathBuf,
+    pub vr: PathBuf,
+}
+
+#[cfg(feature = "ovr_system")]
+#[derive(Debug, thiserror::Error)]
+pub enum PngScreenshotError {
+    #[error("{0}")]
+    Screenshot(#[from] EVRScreenshotError),
+    /// The temporary directory isn't a path OpenVR can use.
+    #[error("invalid screenshot path {0:?}")]
+    InvalidPath(PathBuf),
+    /// SteamVR reported that taking the screenshot failed.
+    #[error("SteamVR failed to take the screenshot")]
+    Failed,
+    #[error("failed to copy the screenshot: {0}")]
+    Io(#[from] std::io::Error),
+}
+#[cfg(feature = "ovr_system")]
+impl From<ScreenshotPathError> for PngScreenshotError {
+    fn from(err: ScreenshotPathError) -> Self {
+        match err {
+            ScreenshotPathError::InvalidPath(path) => Self::InvalidPath(path),
+            ScreenshotPathError::Screenshot(err) => Self::Screenshot(err),
+        }
+    }
+}
+
+/// A stereo screenshot of what the compositor shows, which is saved as a PNG once
+/// SteamVR has taken it.
+///
+/// Pass the events polled from the system manager to [`Self::handle_event()`] until it
+/// returns the result. Giving up on it after a while is up to the caller.
+///
+/// ```no_run
+/// # use ovr_overlay::screenshots::{PngScreenshot, ScreenshotManager};
+/// # use ovr_overlay::system::SystemManager;
+/// # fn example(system: &mut SystemManager, screenshots: &mut ScreenshotManager) {
+/// let screenshot = PngScreenshot::take(screenshots, "shot.png".as_ref()).unwrap();
+/// 'wait: loop {
+///     while let Some(event) = system.poll_next_event() {
+///         if let Some(result) = screenshot.handle_event(screenshots, &event) {
+///             println!("{:?}", result);
+///             break 'wait;
+///         }
+///         // Handle the other events as usual.
+///     }
+///     std::thread::sleep(std::time::Duration::from_millis(10));
+/// }
+/// # }
+/// ```
+#[cfg(feature = "ovr_system")]
+#[derive(Debug)]
+pub struct PngScreenshot {
+    handle: ScreenshotHandle,
+    path: PathBuf,
+}
+
+#[cfg(feature = "ovr_system")]
+impl PngScreenshot {
+    /// Starts taking the screenshot, to be saved at `path`. The preview is saved next
+    /// to it, with `_preview` appended to the file name.
+    pub fn take(
+        screenshots: &mut ScreenshotManager,
+        path: &Path,
+    ) -> std::result::Result<Self, PngScreenshotError> {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+        // SteamVR appends the extension.
+        let tmp = std::env::temp_dir();
+        let name = format!(
+            "ovr_overlay_screenshot_{}_{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        let tmp_vr = tmp.join(&name);
+        let tmp_preview = tmp.join(name + "_preview");
+        let handle = screenshots.take_stereo_screenshot(&tmp_preview, &tmp_vr)?;
+        Ok(Self {
+            handle,
+            path: path.to_owned(),
+        })
+    }
+
+    pub fn handle(&self) -> ScreenshotHandle {
+        self.handle
+    }
+
+    /// Returns `None` if `event` isn't about this screenshot. Otherwise, the
+    /// screenshot is done, and once it was taken, its files are moved to where they
+    /// belong.
+    pub fn handle_event(
+        &self,
+        screenshots: &mut ScreenshotManager,
+        event: &VREvent,
+    ) -> Option<std::result::Result<ScreenshotPaths, PngScreenshotError>> {
+        let screenshot = byteorder::LittleEndian::read_u32(&event.data[0..4]);
+        if screenshot != self.handle.0 {
+            return None;
+        }
+        match event.event_type {
+            sys::EVREventType::VREvent_ScreenshotTaken => Some(self.save(screenshots)),
+            sys::EVREventType::VREvent_ScreenshotFailed => Some(Err(PngScreenshotError::Failed)),
+            _ => None,
+        }
+    }
+
+    fn save(
+        &self,
+        screenshots: &mut ScreenshotManager,
+    ) -> std::result::Result<ScreenshotPaths, PngScreenshotError> {
+        // SteamVR already writes PNGs, so they only need to be copied.
+        let mut move_file = |file, dest: PathBuf| {
+            let src = screenshots.get_screenshot_property_filename(self.handle, file)?;
+            std::fs::copy(&src, &dest)?;
+            let _ = std::fs::remove_file(src);
+            Ok::<_, PngScreenshotError>(dest)
+        };
+        let mut preview = self.path.with_extension("").into_os_string();
+        preview.push("_preview.png");
+        Ok(ScreenshotPaths {
+            preview: move_file(ScreenshotFile::Preview, preview.into())?,
+            vr: move_file(ScreenshotFile::Vr, self.path.clone())?,
+        })
+    }
+}
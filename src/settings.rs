@@ -1,19 +1,26 @@
-use sys::EVRSettingsError;
-
-use crate::{sys, Context};
+use crate::errors::EVRSettingsError;
+use crate::{sys, Context, Interface, InterfaceLock};
 use std::{ffi::CStr, marker::PhantomData, mem::MaybeUninit, pin::Pin};
 
+pub mod power;
+pub mod trackers;
+
+/// The longest string [`SettingsManager::get_string()`] reads.
+const MAX_STRING_LEN: usize = 4096;
+
 pub struct SettingsManager<'c> {
     ctx: PhantomData<&'c Context>,
     inner: Pin<&'c mut sys::IVRSettings>,
+    _lock: InterfaceLock<'c>,
 }
 
 impl<'c> SettingsManager<'c> {
-    pub(super) fn new(_ctx: &'c Context) -> Self {
-        let inner = unsafe { Pin::new_unchecked(sys::VRSettings().as_mut::<'c>().unwrap()) };
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::Settings) };
         Self {
             ctx: Default::default(),
             inner,
+            _lock: lock,
         }
     }
 
@@ -23,16 +30,13 @@ impl<'c> SettingsManager<'c> {
         pch_settings_key: &CStr,
     ) -> Result<f32, EVRSettingsError> {
         unsafe {
-            let mut error: MaybeUninit<EVRSettingsError> = MaybeUninit::uninit();
+            let mut error: MaybeUninit<sys::EVRSettingsError> = MaybeUninit::uninit();
             let result = self.inner.as_mut().GetFloat(
                 pch_section.as_ptr() as *mut _,
                 pch_settings_key.as_ptr() as *mut _,
-                error.as_mut_ptr() as *mut EVRSettingsError,
+                error.as_mut_ptr(),
             );
-            let error = error.assume_init();
-            if error != EVRSettingsError::VRSettingsError_None {
-                return Err(error);
-            }
+            EVRSettingsError::new(error.assume_init())?;
             return Ok(result);
         };
     }
@@ -44,17 +48,14 @@ impl<'c> SettingsManager<'c> {
         value: f32,
     ) -> Result<(), EVRSettingsError> {
         unsafe {
-            let mut error: MaybeUninit<EVRSettingsError> = MaybeUninit::uninit();
+            let mut error: MaybeUninit<sys::EVRSettingsError> = MaybeUninit::uninit();
             self.inner.as_mut().SetFloat(
                 pch_section.as_ptr() as *mut _,
                 pch_settings_key.as_ptr() as *mut _,
                 value,
-                error.as_mut_ptr() as *mut EVRSettingsError,
+                error.as_mut_ptr(),
             );
-            let error = error.assume_init();
-            if error != EVRSettingsError::VRSettingsError_None {
-                return Err(error);
-            }
+            EVRSettingsError::new(error.assume_init())?;
             return Ok(());
         };
     }
@@ -65,16 +66,13 @@ impl<'c> SettingsManager<'c> {
         pch_settings_key: &CStr,
     ) -> Result<bool, EVRSettingsError> {
         unsafe {
-            let mut error: MaybeUninit<EVRSettingsError> = MaybeUninit::uninit();
+            let mut error: MaybeUninit<sys::EVRSettingsError> = MaybeUninit::uninit();
             let result = self.inner.as_mut().GetBool(
                 pch_section.as_ptr() as *mut _,
                 pch_settings_key.as_ptr() as *mut _,
-                error.as_mut_ptr() as *mut EVRSettingsError,
+                error.as_mut_ptr(),
             );
-            let error = error.assume_init();
-            if error != EVRSettingsError::VRSettingsError_None {
-                return Err(error);
-            }
+            EVRSettingsError::new(error.assume_init())?;
             return Ok(result);
         };
     }
@@ -86,18 +84,107 @@ impl<'c> SettingsManager<'c> {
         value: bool,
     ) -> Result<(), EVRSettingsError> {
         unsafe {
-            let mut error: MaybeUninit<EVRSettingsError> = MaybeUninit::uninit();
+            let mut error: MaybeUninit<sys::EVRSettingsError> = MaybeUninit::uninit();
             self.inner.as_mut().SetBool(
                 pch_section.as_ptr() as *mut _,
                 pch_settings_key.as_ptr() as *mut _,
                 value,
-                error.as_mut_ptr() as *mut EVRSettingsError,
+                error.as_mut_ptr(),
             );
-            let error = error.assume_init();
-            if error != EVRSettingsError::VRSettingsError_None {
-                return Err(error);
-            }
+            EVRSettingsError::new(error.assume_init())?;
             return Ok(());
         };
     }
+
+    pub fn get_int32(
+        &mut self,
+        pch_section: &CStr,
+        pch_settings_key: &CStr,
+    ) -> Result<i32, EVRSettingsError> {
+        unsafe {
+            let mut error: MaybeUninit<sys::EVRSettingsError> = MaybeUninit::uninit();
+            let result = self.inner.as_mut().GetInt32(
+                pch_section.as_ptr() as *mut _,
+                pch_settings_key.as_ptr() as *mut _,
+                error.as_mut_ptr(),
+            );
+            EVRSettingsError::new(error.assume_init())?;
+            Ok(result)
+        }
+    }
+
+    pub fn set_int32(
+        &mut self,
+        pch_section: &CStr,
+        pch_settings_key: &CStr,
+        value: i32,
+    ) -> Result<(), EVRSettingsError> {
+        unsafe {
+            let mut error: MaybeUninit<sys::EVRSettingsError> = MaybeUninit::uninit();
+            self.inner.as_mut().SetInt32(
+                pch_section.as_ptr() as *mut _,
+                pch_settings_key.as_ptr() as *mut _,
+                value,
+                error.as_mut_ptr(),
+            );
+            EVRSettingsError::new(error.assume_init())
+        }
+    }
+
+    /// Invalid UTF-8 is replaced with U+FFFD.
+    pub fn get_string(
+        &mut self,
+        pch_section: &CStr,
+        pch_settings_key: &CStr,
+    ) -> Result<String, EVRSettingsError> {
+        let mut buf = vec![0u8; MAX_STRING_LEN];
+        unsafe {
+            let mut error: MaybeUninit<sys::EVRSettingsError> = MaybeUninit::uninit();
+            self.inner.as_mut().GetString(
+                pch_section.as_ptr() as *mut _,
+                pch_settings_key.as_ptr() as *mut _,
+                buf.as_mut_ptr() as *mut _,
+                buf.len() as u32,
+                error.as_mut_ptr(),
+            );
+            EVRSettingsError::new(error.assume_init())?;
+        }
+        let len = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+        Ok(String::from_utf8_lossy(&buf[..len]).into_owned())
+    }
+
+    pub fn set_string(
+        &mut self,
+        pch_section: &CStr,
+        pch_settings_key: &CStr,
+        value: &CStr,
+    ) -> Result<(), EVRSettingsError> {
+        unsafe {
+            let mut error: MaybeUninit<sys::EVRSettingsError> = MaybeUninit::uninit();
+            self.inner.as_mut().SetString(
+                pch_section.as_ptr() as *mut _,
+                pch_settings_key.as_ptr() as *mut _,
+                value.as_ptr() as *mut _,
+                error.as_mut_ptr(),
+            );
+            EVRSettingsError::new(error.assume_init())
+        }
+    }
+
+    /// Removes a key, so it goes back to its default value.
+    pub fn remove_key_in_section(
+        &mut self,
+        pch_section: &CStr,
+        pch_settings_key: &CStr,
+    ) -> Result<(), EVRSettingsError> {
+        unsafe {
+            let mut error: MaybeUninit<sys::EVRSettingsError> = MaybeUninit::uninit();
+            self.inner.as_mut().RemoveKeyInSection(
+                pch_section.as_ptr() as *mut _,
+                pch_settings_key.as_ptr() as *mut _,
+                error.as_mut_ptr(),
+            );
+            EVRSettingsError::new(error.assume_init())
+        }
+    }
 }
@@ -0,0 +1,91 @@
+//! The `power` section, which controls when SteamVR turns off the headset's screens
+//! and idle controllers.
+//!
+//! Timeouts are stored as float seconds, which is easy to get wrong with the raw
+//! [`SettingsManager::get_float()`], so these helpers use [`Duration`]s instead.
+//!
+//! ```no_run
+//! # use ovr_overlay::settings::SettingsManager;
+//! # use std::time::Duration;
+//! # fn example(settings: &mut SettingsManager) {
+//! settings
+//!     .set_screens_off_timeout(Duration::from_secs(5 * 60))
+//!     .unwrap();
+//! # }
+//! ```
+//!
+//! OpenVR has no way for applications to put the headset into standby directly, so
+//! the closest is a short screen timeout.
+
+use super::SettingsManager;
+use crate::errors::EVRSettingsError;
+
+use std::ffi::CStr;
+use std::time::Duration;
+
+fn section() -> &'static CStr {
+    CStr::from_bytes_with_nul(b"power\0").unwrap()
+}
+
+fn screens_timeout_key() -> &'static CStr {
+    CStr::from_bytes_with_nul(b"turnOffScreensTimeout\0").unwrap()
+}
+
+fn controllers_timeout_key() -> &'static CStr {
+    CStr::from_bytes_with_nul(b"turnOffControllersTimeout\0").unwrap()
+}
+
+fn pause_compositor_key() -> &'static CStr {
+    CStr::from_bytes_with_nul(b"pauseCompositorOnStandby\0").unwrap()
+}
+
+/// Negative and invalid values, which SteamVR doesn't write, are zero.
+fn duration_from_secs(secs: f32) -> Duration {
+    Duration::try_from_secs_f32(secs).unwrap_or_default()
+}
+
+impl SettingsManager<'_> {
+    /// How long the headset has to be idle before its screens turn off.
+    pub fn get_screens_off_timeout(&mut self) -> Result<Duration, EVRSettingsError> {
+        self.get_float(section(), screens_timeout_key())
+            .map(duration_from_secs)
+    }
+
+    pub fn set_screens_off_timeout(&mut self, timeout: Duration) -> Result<(), EVRSettingsError> {
+        self.set_float(section(), screens_timeout_key(), timeout.as_secs_f32())
+    }
+
+    /// How long controllers have to be idle before they turn off.
+    pub fn get_controllers_off_timeout(&mut self) -> Result<Duration, EVRSettingsError> {
+        self.get_float(section(), controllers_timeout_key())
+            .map(duration_from_secs)
+    }
+
+    pub fn set_controllers_off_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<(), EVRSettingsError> {
+        self.set_float(section(), controllers_timeout_key(), timeout.as_secs_f32())
+    }
+
+    /// Whether the compositor stops rendering while the headset is in standby.
+    pub fn get_pause_compositor_on_standby(&mut self) -> Result<bool, EVRSettingsError> {
+        self.get_bool(section(), pause_compositor_key())
+    }
+
+    pub fn set_pause_compositor_on_standby(&mut self, pause: bool) -> Result<(), EVRSettingsError> {
+        self.set_bool(section(), pause_compositor_key(), pause)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_duration_from_secs() {
+        assert_eq!(duration_from_secs(1.5), Duration::from_millis(1500));
+        assert_eq!(duration_from_secs(-1.), Duration::ZERO);
+        assert_eq!(duration_from_secs(f32::NAN), Duration::ZERO);
+    }
+}
@@ -0,0 +1,182 @@
+//! The roles assigned to Vive trackers in SteamVR's "Manage Trackers" settings.
+//!
+//! Roles are stored in the `trackers` section, keyed by `/devices/` followed by the
+//! device's [`Prop_RegisteredDeviceType_String`], like
+//! `/devices/htc/vive_trackerLHR-0123ABCD`, which contains its serial number.
+//!
+//! ```no_run
+//! # use ovr_overlay::settings::SettingsManager;
+//! # use ovr_overlay::settings::trackers::TrackerRole;
+//! # fn example(settings: &mut SettingsManager) {
+//! let tracker = "htc/vive_trackerLHR-0123ABCD";
+//! if settings.get_tracker_role(tracker).unwrap().is_none() {
+//!     settings.set_tracker_role(tracker, Some(TrackerRole::Waist)).unwrap();
+//! }
+//! # }
+//! ```
+//!
+//! [`Prop_RegisteredDeviceType_String`]: crate::sys::ETrackedDeviceProperty::Prop_RegisteredDeviceType_String
+
+use super::SettingsManager;
+use crate::errors::EVRSettingsError;
+use crate::sys;
+
+use std::ffi::{CStr, CString};
+
+fn section() -> &'static CStr {
+    CStr::from_bytes_with_nul(b"trackers\0").unwrap()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrackerRole {
+    /// Held in a hand, like a controller.
+    Handed,
+    LeftFoot,
+    RightFoot,
+    LeftShoulder,
+    RightShoulder,
+    LeftElbow,
+    RightElbow,
+    LeftKnee,
+    RightKnee,
+    Waist,
+    Chest,
+    Camera,
+    Keyboard,
+    LeftWrist,
+    RightWrist,
+    LeftAnkle,
+    RightAnkle,
+}
+
+impl TrackerRole {
+    pub const ALL: [Self; 17] = [
+        Self::Handed,
+        Self::LeftFoot,
+        Self::RightFoot,
+        Self::LeftShoulder,
+        Self::RightShoulder,
+        Self::LeftElbow,
+        Self::RightElbow,
+        Self::LeftKnee,
+        Self::RightKnee,
+        Self::Waist,
+        Self::Chest,
+        Self::Camera,
+        Self::Keyboard,
+        Self::LeftWrist,
+        Self::RightWrist,
+        Self::LeftAnkle,
+        Self::RightAnkle,
+    ];
+
+    /// The value stored in the settings, like `TrackerRole_Waist`.
+    pub fn as_setting(self) -> &'static str {
+        match self {
+            Self::Handed => "TrackerRole_Handed",
+            Self::LeftFoot => "TrackerRole_LeftFoot",
+            Self::RightFoot => "TrackerRole_RightFoot",
+            Self::LeftShoulder => "TrackerRole_LeftShoulder",
+            Self::RightShoulder => "TrackerRole_RightShoulder",
+            Self::LeftElbow => "TrackerRole_LeftElbow",
+            Self::RightElbow => "TrackerRole_RightElbow",
+            Self::LeftKnee => "TrackerRole_LeftKnee",
+            Self::RightKnee => "TrackerRole_RightKnee",
+            Self::Waist => "TrackerRole_Waist",
+            Self::Chest => "TrackerRole_Chest",
+            Self::Camera => "TrackerRole_Camera",
+            Self::Keyboard => "TrackerRole_Keyboard",
+            Self::LeftWrist => "TrackerRole_LeftWrist",
+            Self::RightWrist => "TrackerRole_RightWrist",
+            Self::LeftAnkle => "TrackerRole_LeftAnkle",
+            Self::RightAnkle => "TrackerRole_RightAnkle",
+        }
+    }
+
+    /// Parses a settings value. `TrackerRole_None` and unknown roles are `None`.
+    pub fn from_setting(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|role| role.as_setting() == value)
+    }
+}
+
+/// Returned by the tracker role calls.
+#[derive(Debug, thiserror::Error)]
+pub enum TrackerRoleError {
+    /// The registered device type contains a nul byte, so it can't be a settings key.
+    #[error("invalid registered device type {0:?}")]
+    InvalidDeviceType(String),
+    #[error("{0}")]
+    Settings(#[from] EVRSettingsError),
+}
+
+/// The settings key for a device with the given registered device type.
+fn key(registered_device_type: &str) -> Result<CString, TrackerRoleError> {
+    CString::new(format!("/devices/{registered_device_type}"))
+        .map_err(|_| TrackerRoleError::InvalidDeviceType(registered_device_type.to_owned()))
+}
+
+impl SettingsManager<'_> {
+    /// The role of the tracker with the given
+    /// [`Prop_RegisteredDeviceType_String`](sys::ETrackedDeviceProperty::Prop_RegisteredDeviceType_String),
+    /// or `None` if it has none.
+    pub fn get_tracker_role(
+        &mut self,
+        registered_device_type: &str,
+    ) -> Result<Option<TrackerRole>, TrackerRoleError> {
+        let key = key(registered_device_type)?;
+        match self.get_string(section(), &key) {
+            Ok(value) => Ok(TrackerRole::from_setting(&value)),
+            Err(err)
+                if err.inner()
+                    == sys::EVRSettingsError::VRSettingsError_UnsetSettingHasNoDefault =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Assigns a role to a tracker, or removes its role with `None`. SteamVR applies
+    /// the change once the tracker reconnects.
+    pub fn set_tracker_role(
+        &mut self,
+        registered_device_type: &str,
+        role: Option<TrackerRole>,
+    ) -> Result<(), TrackerRoleError> {
+        let key = key(registered_device_type)?;
+        match role {
+            Some(role) => {
+                let value = CString::new(role.as_setting()).unwrap();
+                self.set_string(section(), &key, &value)?;
+            }
+            None => self.remove_key_in_section(section(), &key)?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_role_settings() {
+        for role in TrackerRole::ALL {
+            assert_eq!(TrackerRole::from_setting(role.as_setting()), Some(role));
+        }
+        assert_eq!(TrackerRole::from_setting("TrackerRole_None"), None);
+        assert_eq!(
+            key("htc/vive_trackerLHR-0123ABCD")
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "/devices/htc/vive_trackerLHR-0123ABCD"
+        );
+        assert!(matches!(
+            key("a\0b"),
+            Err(TrackerRoleError::InvalidDeviceType(ty)) if ty == "a\0b"
+        ));
+    }
+}
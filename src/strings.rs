@@ -0,0 +1,70 @@
+//! Reading the strings OpenVR returns into Rust strings.
+
+use std::os::raw::c_char;
+
+/// Helper to call OpenVR functions that return the string length they need,
+/// including the nul. Returns `None` if the length is 0. Invalid UTF-8 is replaced
+/// with U+FFFD.
+///
+/// # Safety
+/// `f` must write at most as many chars as it is given to the pointer it is given.
+#[cfg(any(
+    feature = "ovr_applications",
+    feature = "ovr_render_models",
+    feature = "ovr_system"
+))]
+pub(crate) unsafe fn get_string<F: FnMut(*mut c_char, u32) -> u32>(mut f: F) -> Option<String> {
+    let n = f(std::ptr::null_mut(), 0);
+    if n == 0 {
+        return None;
+    }
+    let mut buf = vec![0 as c_char; n as usize];
+    f(buf.as_mut_ptr(), n);
+    Some(string_from(&buf))
+}
+
+/// Reads a nul-terminated string, like a fixed size string field, up to its end if the
+/// nul is missing. Invalid UTF-8 is replaced with U+FFFD.
+pub(crate) fn string_from(chars: &[c_char]) -> String {
+    let bytes: Vec<u8> = chars
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_string_from() {
+        let mut chars = [0 as c_char; 8];
+        for (c, b) in chars.iter_mut().zip(b"grip") {
+            *c = *b as c_char;
+        }
+        assert_eq!(string_from(&chars), "grip");
+        assert_eq!(string_from(&[b'a' as c_char; 4]), "aaaa");
+    }
+
+    #[cfg(any(
+        feature = "ovr_applications",
+        feature = "ovr_render_models",
+        feature = "ovr_system"
+    ))]
+    #[test]
+    fn test_get_string() {
+        let value = b"/user/hand/left\0";
+        let read = unsafe {
+            get_string(|ptr, n| {
+                if !ptr.is_null() {
+                    std::ptr::copy_nonoverlapping(value.as_ptr().cast(), ptr, n as usize);
+                }
+                value.len() as u32
+            })
+        };
+        assert_eq!(read.as_deref(), Some("/user/hand/left"));
+        assert_eq!(unsafe { get_string(|_, _| 0) }, None);
+    }
+}
@@ -3,16 +3,24 @@ use slice_of_array::SliceArrayExt;
 use sys::{ETrackingUniverseOrigin, HmdMatrix34_t};
 
 use crate::errors::ETrackedPropertyError;
-use crate::{sys, Context, TrackedDeviceIndex};
+use crate::strings::get_string;
+use crate::{sys, Context, Interface, InterfaceLock, TrackedDeviceIndex};
 
+use std::borrow::Cow;
 use std::ffi::CString;
 use std::marker::PhantomData;
+use std::os::raw::c_char;
 use std::pin::Pin;
 use std::ptr::null_mut;
 
+pub mod event_types;
+pub mod property_cache;
+pub mod props;
+
 pub struct SystemManager<'c> {
     ctx: PhantomData<&'c Context>,
     inner: Pin<&'c mut sys::IVRSystem>,
+    _lock: InterfaceLock<'c>,
 }
 
 mod private {
@@ -54,6 +62,7 @@ impl_property_type!(i32, GetInt32TrackedDeviceProperty);
 impl_property_type!(u64, GetUint64TrackedDeviceProperty);
 
 impl private::Sealed for String {}
+/// Invalid UTF-8 is replaced with U+FFFD. Request a [`CString`] for the exact bytes.
 impl<'ret> TrackedDeviceProperty<'ret> for String {
     fn get<'manager: 'ret>(
         index: TrackedDeviceIndex,
@@ -73,10 +82,7 @@ impl<'ret> TrackedDeviceProperty<'ret> for String {
             })
         };
         ETrackedPropertyError::new(err)?;
-        match res {
-            Some(s) => Ok(s.into_string().unwrap()),
-            None => Ok("".to_string()),
-        }
+        Ok(res.unwrap_or_default())
     }
 }
 
@@ -123,25 +129,68 @@ impl<'ret> TrackedDeviceProperty<'ret> for CString {
             system.inner.as_mut().GetStringTrackedDeviceProperty(
                 index.0,
                 prop.clone(),
-                data.as_mut_ptr() as *mut i8,
+                data.as_mut_ptr() as *mut c_char,
                 len,
                 &mut err,
             )
         };
         ETrackedPropertyError::new(err)?;
 
-        Ok(CString::from_vec_with_nul(data).expect("missing nul byte from openvr!"))
+        // Keep everything up to the first nul, in case OpenVR wrote less than it asked for.
+        let end = data.iter().position(|&c| c == 0).unwrap_or(data.len());
+        data.truncate(end);
+        Ok(CString::new(data).expect("data was truncated at the first nul"))
     }
 }
 
 // TODO: arrays. I don't feel like dealing with them right now.
 
+/// A property to read with [`SystemManager::get_properties()`], tagged with its type.
+#[derive(Clone)]
+pub enum PropRequest {
+    Bool(sys::ETrackedDeviceProperty),
+    Float(sys::ETrackedDeviceProperty),
+    Int32(sys::ETrackedDeviceProperty),
+    Uint64(sys::ETrackedDeviceProperty),
+    String(sys::ETrackedDeviceProperty),
+}
+
+/// A property value read by [`SystemManager::get_properties()`].
+#[derive(Clone, PartialEq, Debug)]
+pub enum PropValue {
+    Bool(bool),
+    Float(f32),
+    Int32(i32),
+    Uint64(u64),
+    String(String),
+}
+
+/// Everything a device list usually shows about a device, read by
+/// [`SystemManager::snapshot_devices()`]. Properties the device doesn't have are
+/// `None`.
+#[derive(Clone)]
+pub struct DeviceSnapshot {
+    pub index: TrackedDeviceIndex,
+    pub class: sys::ETrackedDeviceClass,
+    pub role: sys::ETrackedControllerRole,
+    pub connected: bool,
+    /// Between 0 and 1.
+    pub battery: Option<f32>,
+    pub charging: bool,
+    pub serial: Option<String>,
+    pub model: Option<String>,
+    pub firmware_update_available: bool,
+    /// The pose in the requested tracking universe, if it is valid.
+    pub pose: Option<crate::pose::Matrix3x4>,
+}
+
 impl<'c> SystemManager<'c> {
-    pub(super) fn new(_ctx: &'c Context) -> Self {
-        let inner = unsafe { Pin::new_unchecked(sys::VRSystem().as_mut::<'c>().unwrap()) };
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::System) };
         Self {
             ctx: Default::default(),
             inner,
+            _lock: lock,
         }
     }
 
@@ -153,6 +202,144 @@ impl<'c> SystemManager<'c> {
         T::get(index, self, prop)
     }
 
+    /// Reads a string property into `buf`, returning the part of `buf` holding the
+    /// string. Fails with `BufferTooSmall` if it doesn't fit;
+    /// `sys::k_unMaxPropertyStringSize` is always enough.
+    ///
+    /// Only allocates if the string isn't valid UTF-8, to replace the invalid sequences
+    /// with `U+FFFD`. Request a [`CString`] property for the exact bytes.
+    pub fn get_string_tracked_device_property_into<'b>(
+        &mut self,
+        index: TrackedDeviceIndex,
+        prop: sys::ETrackedDeviceProperty,
+        buf: &'b mut [u8],
+    ) -> PropResult<Cow<'b, str>> {
+        let mut err = sys::ETrackedPropertyError::TrackedProp_Success;
+        let len = unsafe {
+            self.inner.as_mut().GetStringTrackedDeviceProperty(
+                index.0,
+                prop,
+                buf.as_mut_ptr() as *mut _,
+                buf.len() as u32,
+                &mut err,
+            )
+        };
+        ETrackedPropertyError::new(err)?;
+        let buf = &buf[..(len as usize).min(buf.len())];
+        let buf = match buf.iter().position(|&c| c == 0) {
+            Some(end) => &buf[..end],
+            None => buf,
+        };
+        Ok(String::from_utf8_lossy(buf))
+    }
+
+    /// Reads several properties of one device, reusing a single string buffer. The
+    /// results are in the same order as `requests`.
+    pub fn get_properties(
+        &mut self,
+        index: TrackedDeviceIndex,
+        requests: &[PropRequest],
+    ) -> Vec<PropResult<PropValue>> {
+        let mut buf = Vec::new();
+        requests
+            .iter()
+            .map(|request| self.get_property(index, request, &mut buf))
+            .collect()
+    }
+
+    fn get_property(
+        &mut self,
+        index: TrackedDeviceIndex,
+        request: &PropRequest,
+        buf: &mut Vec<u8>,
+    ) -> PropResult<PropValue> {
+        Ok(match request.clone() {
+            PropRequest::Bool(prop) => {
+                PropValue::Bool(self.get_tracked_device_property(index, prop)?)
+            }
+            PropRequest::Float(prop) => {
+                PropValue::Float(self.get_tracked_device_property(index, prop)?)
+            }
+            PropRequest::Int32(prop) => {
+                PropValue::Int32(self.get_tracked_device_property(index, prop)?)
+            }
+            PropRequest::Uint64(prop) => {
+                PropValue::Uint64(self.get_tracked_device_property(index, prop)?)
+            }
+            PropRequest::String(prop) => {
+                if buf.is_empty() {
+                    buf.resize(256, 0);
+                }
+                let mut err = sys::ETrackedPropertyError::TrackedProp_Success;
+                let mut len = 0;
+                for _ in 0..2 {
+                    len = unsafe {
+                        self.inner.as_mut().GetStringTrackedDeviceProperty(
+                            index.0,
+                            prop.clone(),
+                            buf.as_mut_ptr() as *mut _,
+                            buf.len() as u32,
+                            &mut err,
+                        )
+                    };
+                    if err != sys::ETrackedPropertyError::TrackedProp_BufferTooSmall {
+                        break;
+                    }
+                    buf.resize(len as usize, 0);
+                }
+                ETrackedPropertyError::new(err)?;
+                let value = &buf[..(len as usize).min(buf.len())];
+                let end = value.iter().position(|&c| c == 0).unwrap_or(value.len());
+                PropValue::String(String::from_utf8_lossy(&value[..end]).into_owned())
+            }
+        })
+    }
+
+    /// Reads the state of every known device at once, with poses in `origin`.
+    pub fn snapshot_devices(&mut self, origin: ETrackingUniverseOrigin) -> Vec<DeviceSnapshot> {
+        use sys::ETrackedDeviceProperty::*;
+        let poses = self.get_device_to_absolute_tracking_pose(origin, 0.);
+        let mut devices = Vec::new();
+        for (i, pose) in poses.iter().enumerate() {
+            let index = TrackedDeviceIndex(i as u32);
+            let class = self.get_tracked_device_class(index);
+            if class == sys::ETrackedDeviceClass::TrackedDeviceClass_Invalid {
+                continue;
+            }
+            let has_battery = self
+                .get_tracked_device_property(index, Prop_DeviceProvidesBatteryStatus_Bool)
+                .unwrap_or(false);
+            devices.push(DeviceSnapshot {
+                index,
+                class,
+                role: self.get_controller_role_for_tracked_device_index(index),
+                connected: self.is_tracked_device_connected(index),
+                battery: has_battery
+                    .then(|| {
+                        self.get_tracked_device_property(index, Prop_DeviceBatteryPercentage_Float)
+                            .ok()
+                    })
+                    .flatten(),
+                charging: self
+                    .get_tracked_device_property(index, Prop_DeviceIsCharging_Bool)
+                    .unwrap_or(false),
+                serial: self
+                    .get_tracked_device_property(index, Prop_SerialNumber_String)
+                    .ok(),
+                model: self
+                    .get_tracked_device_property(index, Prop_ModelNumber_String)
+                    .ok(),
+                firmware_update_available: self
+                    .get_tracked_device_property(index, Prop_Firmware_UpdateAvailable_Bool)
+                    .unwrap_or(false),
+                pose: pose
+                    .bPoseIsValid
+                    .then(|| *<&crate::pose::Matrix3x4>::from(&pose.mDeviceToAbsoluteTracking)),
+            });
+        }
+        devices
+    }
+
     pub fn get_controller_role_for_tracked_device_index<'ret, 'manager: 'ret>(
         &'manager mut self,
         index: TrackedDeviceIndex,
@@ -164,6 +351,45 @@ impl<'c> SystemManager<'c> {
         }
     }
 
+    pub fn get_tracked_device_activity_level(
+        &mut self,
+        index: TrackedDeviceIndex,
+    ) -> sys::EDeviceActivityLevel {
+        unsafe { self.inner.as_mut().GetTrackedDeviceActivityLevel(index.0) }
+    }
+
+    /// Sends a single haptic pulse to a device, for devices and drivers that still
+    /// support the legacy haptics API. `duration` is capped at 3999µs by OpenVR.
+    ///
+    /// Applications with an action manifest should prefer
+    /// `InputManager::trigger_haptic_vibration_action()`.
+    pub fn trigger_haptic_pulse(
+        &mut self,
+        index: TrackedDeviceIndex,
+        axis_id: u32,
+        duration: std::time::Duration,
+    ) {
+        let micros = duration.as_micros().min(3999) as u16;
+        unsafe {
+            self.inner
+                .as_mut()
+                .TriggerHapticPulse(index.0, axis_id, micros)
+        }
+    }
+
+    /// Returns `None` if no device currently has `role`.
+    pub fn get_tracked_device_index_for_controller_role(
+        &mut self,
+        role: sys::ETrackedControllerRole,
+    ) -> Option<TrackedDeviceIndex> {
+        let index = unsafe {
+            self.inner
+                .as_mut()
+                .GetTrackedDeviceIndexForControllerRole(role)
+        };
+        TrackedDeviceIndex::new(index).ok()
+    }
+
     pub fn get_tracked_device_class<'ret, 'manager: 'ret>(
         &'manager mut self,
         index: TrackedDeviceIndex,
@@ -185,15 +411,35 @@ impl<'c> SystemManager<'c> {
     ) -> [sys::TrackedDevicePose_t; sys::k_unMaxTrackedDeviceCount as usize] {
         let mut poses: [sys::TrackedDevicePose_t; sys::k_unMaxTrackedDeviceCount as usize] =
             unsafe { std::mem::zeroed() };
+        self.get_device_to_absolute_tracking_pose_into(
+            origin,
+            predicted_seconds_to_photons_from_now,
+            &mut poses,
+        );
+        poses
+    }
+
+    /// Like [`Self::get_device_to_absolute_tracking_pose()`], but writes into `poses`
+    /// so the buffer can be reused across frames. Only the first `poses.len()` devices
+    /// are queried.
+    pub fn get_device_to_absolute_tracking_pose_into(
+        &mut self,
+        origin: ETrackingUniverseOrigin,
+        predicted_seconds_to_photons_from_now: f32,
+        poses: &mut [sys::TrackedDevicePose_t],
+    ) {
+        let len = poses.len().min(sys::k_unMaxTrackedDeviceCount as usize);
+        if len == 0 {
+            return;
+        }
         unsafe {
             self.inner.as_mut().GetDeviceToAbsoluteTrackingPose(
                 origin,
                 predicted_seconds_to_photons_from_now,
-                &mut poses[0],
-                sys::k_unMaxTrackedDeviceCount,
+                poses.as_mut_ptr(),
+                len as u32,
             )
         };
-        poses
     }
 
     pub fn get_raw_zero_pose_to_standing_absolute_tracking_pose<'ret, 'manager: 'ret>(
@@ -206,6 +452,78 @@ impl<'c> SystemManager<'c> {
         }
     }
 
+    /// The transform from the seated zero pose to the standing tracking universe.
+    pub fn get_seated_zero_pose_to_standing_absolute_tracking_pose(&mut self) -> HmdMatrix34_t {
+        unsafe {
+            self.inner
+                .as_mut()
+                .GetSeatedZeroPoseToStandingAbsoluteTrackingPose()
+        }
+    }
+
+    /// Both zero poses relative to the standing universe, as
+    /// `(seated_to_standing, raw_to_standing)`. Multiply a seated pose by
+    /// `seated_to_standing` to get the same pose in the standing universe.
+    pub fn get_zero_poses(&mut self) -> (crate::pose::Matrix3x4, crate::pose::Matrix3x4) {
+        (
+            self.get_seated_zero_pose_to_standing_absolute_tracking_pose()
+                .into(),
+            self.get_raw_zero_pose_to_standing_absolute_tracking_pose()
+                .into(),
+        )
+    }
+
+    /// The transform from the given eye to the head, including the IPD.
+    pub fn get_eye_to_head_transform(&mut self, eye: sys::EVREye) -> crate::pose::Matrix3x4 {
+        unsafe { self.inner.as_mut().GetEyeToHeadTransform(eye) }.into()
+    }
+
+    /// The HMD pose in `origin`, or `None` if it isn't valid.
+    pub fn hmd_pose(&mut self, origin: ETrackingUniverseOrigin) -> Option<crate::pose::Matrix3x4> {
+        self.predicted_hmd_pose(origin, 0.)
+    }
+
+    /// Like [`Self::hmd_pose()`], but predicted for when the next frame reaches the
+    /// user's eyes.
+    pub fn predicted_hmd_pose(
+        &mut self,
+        origin: ETrackingUniverseOrigin,
+        predicted_seconds_to_photons_from_now: f32,
+    ) -> Option<crate::pose::Matrix3x4> {
+        let mut hmd: [sys::TrackedDevicePose_t; 1] = unsafe { std::mem::zeroed() };
+        self.get_device_to_absolute_tracking_pose_into(
+            origin,
+            predicted_seconds_to_photons_from_now,
+            &mut hmd,
+        );
+        hmd[0]
+            .bPoseIsValid
+            .then(|| hmd[0].mDeviceToAbsoluteTracking.into())
+    }
+
+    /// The left and right eye poses in `origin`, or `None` if the HMD pose isn't valid.
+    pub fn get_eye_poses(
+        &mut self,
+        origin: ETrackingUniverseOrigin,
+        predicted_seconds_to_photons_from_now: f32,
+    ) -> Option<[crate::pose::Matrix3x4; 2]> {
+        let head = self.predicted_hmd_pose(origin, predicted_seconds_to_photons_from_now)?;
+        Some(
+            [sys::EVREye::Eye_Left, sys::EVREye::Eye_Right]
+                .map(|eye| head * self.get_eye_to_head_transform(eye)),
+        )
+    }
+
+    /// The ray the HMD is facing along in `origin`, or `None` if its pose isn't valid.
+    /// Test it against overlays with [`OverlayManager::compute_overlay_intersection()`].
+    ///
+    /// [`OverlayManager::compute_overlay_intersection()`]:
+    ///     crate::overlay::OverlayManager::compute_overlay_intersection
+    pub fn hmd_gaze_ray(&mut self, origin: ETrackingUniverseOrigin) -> Option<crate::pose::Ray> {
+        let head = self.hmd_pose(origin)?;
+        Some(crate::pose::Ray::from_pose(&head))
+    }
+
     pub fn get_time_since_last_vsync<'ret, 'manager: 'ret>(
         &'manager mut self,
         seconds_since_last_vsync: &mut f32,
@@ -233,13 +551,26 @@ impl<'c> SystemManager<'c> {
         let event = VREvent::parse(event);
         Some(event)
     }
-}
 
-unsafe impl Send for SystemManager<'_> {}
-unsafe impl Sync for SystemManager<'_> {}
+    /// Paths that the application needs read access to when running inside an app
+    /// container, such as the SteamVR runtime and its configuration directories.
+    pub fn get_app_container_file_paths(&mut self) -> Vec<std::path::PathBuf> {
+        let paths =
+            unsafe { get_string(|ptr, n| self.inner.as_mut().GetAppContainerFilePaths(ptr, n)) };
+        let Some(paths) = paths else {
+            return Vec::new();
+        };
+        paths
+            .split(';')
+            .filter(|path| !path.is_empty())
+            .map(std::path::PathBuf::from)
+            .collect()
+    }
+}
 
 const VREVENT_SIZE: usize = std::mem::size_of::<sys::VREvent_t>();
 
+#[derive(Clone)]
 pub struct VREvent {
     pub event_type: sys::EVREventType,
     pub tracked_device_index: TrackedDeviceIndex,
@@ -247,8 +578,40 @@ pub struct VREvent {
     pub data: [u8; VREVENT_SIZE - 12],
 }
 
+impl std::fmt::Debug for VREvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VREvent")
+            .field("event_type", &DebugEventType(&self.event_type))
+            .field("tracked_device_index", &self.tracked_device_index)
+            .field("event_age_seconds", &self.event_age_seconds)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Prints the event type by name, or as its number if it has none.
+struct DebugEventType<'a>(&'a sys::EVREventType);
+
+impl std::fmt::Debug for DebugEventType<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match event_types::name(self.0) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "EVREventType({})", self.0.clone() as u32),
+        }
+    }
+}
+
 impl VREvent {
-    fn parse(event: sys::VREvent_t) -> VREvent {
+    /// Creates an event with zeroed data, for injecting events in tests.
+    pub fn new(event_type: sys::EVREventType, tracked_device_index: TrackedDeviceIndex) -> Self {
+        VREvent {
+            event_type,
+            tracked_device_index,
+            event_age_seconds: 0.0,
+            data: [0; VREVENT_SIZE - 12],
+        }
+    }
+
+    pub(crate) fn parse(event: sys::VREvent_t) -> VREvent {
         let bytes: [u8; VREVENT_SIZE] = unsafe {
             *std::slice::from_raw_parts(
                 &event as *const sys::VREvent_t as *const u8,
@@ -270,23 +633,16 @@ impl VREvent {
             }
         }
     }
-}
 
-/// Helper to call OpenVR functions that return strings
-unsafe fn get_string<F: FnMut(*mut std::os::raw::c_char, u32) -> u32>(
-    mut f: F,
-) -> Option<std::ffi::CString> {
-    let n = f(std::ptr::null_mut(), 0);
-    if n == 0 {
-        return None;
-    }
-    let mut storage = Vec::new();
-    storage.reserve_exact(n as usize);
-    storage.resize_with(n as usize, || std::mem::MaybeUninit::zeroed().assume_init());
-    let n_ = f(storage.as_mut_ptr() as *mut _, n);
-    assert!(n == n_);
-    storage.truncate((n - 1) as usize); // Strip trailing null
-    Some(std::ffi::CString::from_vec_unchecked(storage))
+    /// Converts back into the raw OpenVR event, for passing events to OpenVR.
+    pub fn to_sys(&self) -> sys::VREvent_t {
+        let mut bytes = [0u8; VREVENT_SIZE];
+        byteorder::LittleEndian::write_u32(&mut bytes[0..4], self.event_type.clone() as u32);
+        byteorder::LittleEndian::write_u32(&mut bytes[4..8], self.tracked_device_index.0);
+        byteorder::LittleEndian::write_f32(&mut bytes[8..12], self.event_age_seconds);
+        bytes[12..VREVENT_SIZE].copy_from_slice(&self.data);
+        unsafe { std::mem::transmute(bytes) }
+    }
 }
 
 #[cfg(test)]
@@ -309,9 +665,3 @@ mod test {
             .unwrap();
     }
 }
-
-impl std::fmt::Debug for ETrackedPropertyError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ETrackedPropertyError").finish()
-    }
-}
@@ -0,0 +1,165 @@
+//! Names of the event types, since the sys enum can't be printed, and conversion from
+//! their raw values.
+//!
+//! Only event types that applications commonly see are listed. Others, like the ones
+//! only drivers or SteamVR itself receive, are reported as unknown.
+
+use crate::sys;
+
+macro_rules! event_types {
+    ($($name:ident),+ $(,)?) => {
+        /// The name of `event_type` as in OpenVR, like `VREvent_ButtonPress`, if it is
+        /// one of the listed types.
+        pub fn name(event_type: &sys::EVREventType) -> Option<&'static str> {
+            match event_type {
+                $(sys::EVREventType::$name => Some(stringify!($name)),)+
+                _ => None,
+            }
+        }
+
+        /// The listed event type with the raw value `raw`, like one read from a file.
+        pub fn from_raw(raw: u32) -> Option<sys::EVREventType> {
+            $(if raw == sys::EVREventType::$name as u32 {
+                return Some(sys::EVREventType::$name);
+            })+
+            None
+        }
+    };
+}
+
+event_types!(
+    VREvent_None,
+    VREvent_TrackedDeviceActivated,
+    VREvent_TrackedDeviceDeactivated,
+    VREvent_TrackedDeviceUpdated,
+    VREvent_TrackedDeviceUserInteractionStarted,
+    VREvent_TrackedDeviceUserInteractionEnded,
+    VREvent_IpdChanged,
+    VREvent_EnterStandbyMode,
+    VREvent_LeaveStandbyMode,
+    VREvent_TrackedDeviceRoleChanged,
+    VREvent_WatchdogWakeUpRequested,
+    VREvent_LensDistortionChanged,
+    VREvent_PropertyChanged,
+    VREvent_WirelessDisconnect,
+    VREvent_WirelessReconnect,
+    VREvent_ButtonPress,
+    VREvent_ButtonUnpress,
+    VREvent_ButtonTouch,
+    VREvent_ButtonUntouch,
+    VREvent_MouseMove,
+    VREvent_MouseButtonDown,
+    VREvent_MouseButtonUp,
+    VREvent_FocusEnter,
+    VREvent_FocusLeave,
+    VREvent_ScrollDiscrete,
+    VREvent_TouchPadMove,
+    VREvent_OverlayFocusChanged,
+    VREvent_ReloadOverlays,
+    VREvent_ScrollSmooth,
+    VREvent_InputFocusCaptured,
+    VREvent_InputFocusReleased,
+    VREvent_SceneApplicationChanged,
+    VREvent_InputFocusChanged,
+    VREvent_ActionBindingReloaded,
+    VREvent_HideRenderModels,
+    VREvent_ShowRenderModels,
+    VREvent_OverlayShown,
+    VREvent_OverlayHidden,
+    VREvent_DashboardActivated,
+    VREvent_DashboardDeactivated,
+    VREvent_DashboardRequested,
+    VREvent_ResetDashboard,
+    VREvent_ImageLoaded,
+    VREvent_ShowKeyboard,
+    VREvent_HideKeyboard,
+    VREvent_OverlayGamepadFocusGained,
+    VREvent_OverlayGamepadFocusLost,
+    VREvent_OverlaySharedTextureChanged,
+    VREvent_ScreenshotTriggered,
+    VREvent_ImageFailed,
+    VREvent_DashboardOverlayCreated,
+    VREvent_SwitchGamepadFocus,
+    VREvent_RequestScreenshot,
+    VREvent_ScreenshotTaken,
+    VREvent_ScreenshotFailed,
+    VREvent_SubmitScreenshotToDashboard,
+    VREvent_ScreenshotProgressToDashboard,
+    VREvent_PrimaryDashboardDeviceChanged,
+    VREvent_Notification_Shown,
+    VREvent_Notification_Hidden,
+    VREvent_Notification_BeginInteraction,
+    VREvent_Notification_Destroyed,
+    VREvent_Quit,
+    VREvent_ProcessQuit,
+    VREvent_QuitAcknowledged,
+    VREvent_DriverRequestedQuit,
+    VREvent_RestartRequested,
+    VREvent_ChaperoneDataHasChanged,
+    VREvent_ChaperoneUniverseHasChanged,
+    VREvent_ChaperoneTempDataHasChanged,
+    VREvent_ChaperoneSettingsHaveChanged,
+    VREvent_SeatedZeroPoseReset,
+    VREvent_ChaperoneFlushCache,
+    VREvent_ChaperoneRoomSetupStarting,
+    VREvent_ChaperoneRoomSetupFinished,
+    VREvent_AudioSettingsHaveChanged,
+    VREvent_BackgroundSettingHasChanged,
+    VREvent_CameraSettingsHaveChanged,
+    VREvent_ReprojectionSettingHasChanged,
+    VREvent_ModelSkinSettingsHaveChanged,
+    VREvent_EnvironmentSettingsHaveChanged,
+    VREvent_PowerSettingsHaveChanged,
+    VREvent_SteamVRSectionSettingChanged,
+    VREvent_LighthouseSectionSettingChanged,
+    VREvent_NullSectionSettingChanged,
+    VREvent_UserInterfaceSectionSettingChanged,
+    VREvent_NotificationsSectionSettingChanged,
+    VREvent_KeyboardSectionSettingChanged,
+    VREvent_PerfSectionSettingChanged,
+    VREvent_DashboardSectionSettingChanged,
+    VREvent_WebInterfaceSectionSettingChanged,
+    VREvent_StatusUpdate,
+    VREvent_FirmwareUpdateStarted,
+    VREvent_FirmwareUpdateFinished,
+    VREvent_KeyboardClosed,
+    VREvent_KeyboardCharInput,
+    VREvent_KeyboardDone,
+    VREvent_ApplicationListUpdated,
+    VREvent_ApplicationMimeTypeLoad,
+    VREvent_ProcessConnected,
+    VREvent_ProcessDisconnected,
+    VREvent_Compositor_ChaperoneBoundsShown,
+    VREvent_Compositor_ChaperoneBoundsHidden,
+    VREvent_TrackedCamera_StartVideoStream,
+    VREvent_TrackedCamera_StopVideoStream,
+    VREvent_TrackedCamera_PauseVideoStream,
+    VREvent_TrackedCamera_ResumeVideoStream,
+    VREvent_MessageOverlay_Closed,
+    VREvent_MessageOverlayCloseRequested,
+    VREvent_Input_HapticVibration,
+    VREvent_Input_BindingLoadFailed,
+    VREvent_Input_BindingLoadSuccessful,
+    VREvent_Input_ActionManifestReloaded,
+    VREvent_Input_ActionManifestLoadFailed,
+    VREvent_Input_ProgressUpdate,
+    VREvent_Input_TrackerActivated,
+    VREvent_Input_BindingsUpdated,
+    VREvent_Input_BindingSubscriptionChanged,
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_name() {
+        assert_eq!(
+            name(&sys::EVREventType::VREvent_ButtonPress),
+            Some("VREvent_ButtonPress")
+        );
+        let raw = sys::EVREventType::VREvent_Quit as u32;
+        assert!(from_raw(raw) == Some(sys::EVREventType::VREvent_Quit));
+        assert!(from_raw(u32::MAX).is_none());
+    }
+}
@@ -0,0 +1,257 @@
+//! Memoizes tracked device property reads, so dashboards can query the same
+//! properties every frame without a round trip to SteamVR each time.
+//!
+//! ```no_run
+//! # use ovr_overlay::{sys, TrackedDeviceIndex};
+//! # use ovr_overlay::system::property_cache::PropertyCache;
+//! # fn example(ctx: &ovr_overlay::Context) {
+//! let mut system = ctx.system_mngr();
+//! let mut cache = PropertyCache::new();
+//! loop {
+//!     while let Some(event) = system.poll_next_event() {
+//!         cache.handle_event(&event);
+//!     }
+//!     let battery: Result<f32, _> = cache.get(
+//!         &mut system,
+//!         TrackedDeviceIndex::HMD,
+//!         sys::ETrackedDeviceProperty::Prop_DeviceBatteryPercentage_Float,
+//!     );
+//! }
+//! # }
+//! ```
+
+use super::props::read_as_any_type;
+use super::{PropRequest, PropResult, PropValue, VREvent};
+use crate::errors::ETrackedPropertyError;
+use crate::traits::System;
+use crate::{sys, TrackedDeviceIndex};
+
+use byteorder::{ByteOrder, LittleEndian};
+use std::collections::HashMap;
+
+/// Property types that can be stored in a [`PropertyCache`].
+pub trait CachedProperty: Sized {
+    #[doc(hidden)]
+    fn request(prop: sys::ETrackedDeviceProperty) -> PropRequest;
+    #[doc(hidden)]
+    fn from_value(value: &PropValue) -> Option<Self>;
+}
+
+macro_rules! impl_cached_property {
+    ($ty:ty, $variant:ident) => {
+        impl CachedProperty for $ty {
+            fn request(prop: sys::ETrackedDeviceProperty) -> PropRequest {
+                PropRequest::$variant(prop)
+            }
+
+            fn from_value(value: &PropValue) -> Option<Self> {
+                match value {
+                    PropValue::$variant(v) => Some(v.clone()),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_cached_property!(bool, Bool);
+impl_cached_property!(f32, Float);
+impl_cached_property!(i32, Int32);
+impl_cached_property!(u64, Uint64);
+impl_cached_property!(String, String);
+
+/// A property that changed, returned by [`PropertyCache::handle_event_diff()`].
+#[derive(Clone, Debug)]
+pub struct PropertyChange {
+    pub index: TrackedDeviceIndex,
+    pub prop: sys::ETrackedDeviceProperty,
+    /// The cached value from before the change, if there was one.
+    pub old: Option<PropValue>,
+    pub new: PropValue,
+}
+
+/// Caches property values per device, until an event says they may have changed.
+///
+/// Pass every event from [`System::poll_next_event()`] to
+/// [`Self::handle_event()`], otherwise stale values will be returned.
+#[derive(Default)]
+pub struct PropertyCache {
+    entries: HashMap<(sys::TrackedDeviceIndex_t, i32), Result<PropValue, ETrackedPropertyError>>,
+}
+
+impl PropertyCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value of `prop`, reading it from `system` if it isn't cached.
+    ///
+    /// Errors that won't go away until the device changes, such as the device not
+    /// providing the property, are cached as well.
+    pub fn get<T: CachedProperty>(
+        &mut self,
+        system: &mut (impl System + ?Sized),
+        index: TrackedDeviceIndex,
+        prop: sys::ETrackedDeviceProperty,
+    ) -> PropResult<T> {
+        let key = (index.0, prop.clone() as i32);
+        match self.entries.get(&key) {
+            Some(Ok(value)) => {
+                // A different type may have been requested before, in which case
+                // the new read replaces it.
+                if let Some(value) = T::from_value(value) {
+                    return Ok(value);
+                }
+            }
+            Some(Err(err)) => return Err(err.clone()),
+            None => {}
+        }
+
+        let result = system.read_any(index, &T::request(prop));
+        match &result {
+            Ok(value) => {
+                self.entries.insert(key, Ok(value.clone()));
+            }
+            Err(err) if is_persistent(err) => {
+                self.entries.insert(key, Err(err.clone()));
+            }
+            Err(_) => {}
+        }
+        T::from_value(&result?).ok_or_else(|| {
+            ETrackedPropertyError::new(sys::ETrackedPropertyError::TrackedProp_WrongDataType)
+                .unwrap_err()
+        })
+    }
+
+    /// Invalidates entries affected by `event`.
+    pub fn handle_event(&mut self, event: &VREvent) {
+        use sys::EVREventType::*;
+        match event.event_type {
+            VREvent_PropertyChanged
+            | VREvent_TrackedDeviceActivated
+            | VREvent_TrackedDeviceDeactivated
+            | VREvent_TrackedDeviceUpdated
+            | VREvent_TrackedDeviceRoleChanged => {
+                self.invalidate_device(event.tracked_device_index)
+            }
+            _ => {}
+        }
+    }
+
+    /// Like [`Self::handle_event()`], but refreshes only the property a
+    /// `VREvent_PropertyChanged` event is about, returning its old and new values.
+    ///
+    /// The new value is read with the type it was cached as, or else as each type in
+    /// turn until one matches. Returns `None` for other
+    /// events, and if the property can't be read anymore. Properties not listed in
+    /// [`props`](super::props) are only invalidated, also returning `None`.
+    pub fn handle_event_diff(
+        &mut self,
+        system: &mut (impl System + ?Sized),
+        event: &VREvent,
+    ) -> Option<PropertyChange> {
+        if event.event_type != sys::EVREventType::VREvent_PropertyChanged {
+            self.handle_event(event);
+            return None;
+        }
+        let index = event.tracked_device_index;
+        // VREvent_Property_t: the property container handle, then the property.
+        let raw = LittleEndian::read_u32(&event.data[8..12]);
+        let key = (index.0, raw as i32);
+
+        let old = match self.entries.remove(&key) {
+            Some(Ok(value)) => Some(value),
+            _ => None,
+        };
+        let prop = super::props::find(raw)?.prop();
+        let new = match read_as_any_type(system, index, prop.clone(), old.as_ref()) {
+            Ok(value) => value,
+            Err(err) => {
+                if is_persistent(&err) {
+                    self.entries.insert(key, Err(err));
+                }
+                return None;
+            }
+        };
+        self.entries.insert(key, Ok(new.clone()));
+        Some(PropertyChange {
+            index,
+            prop,
+            old,
+            new,
+        })
+    }
+
+    /// Forgets every cached property of the device at `index`.
+    pub fn invalidate_device(&mut self, index: TrackedDeviceIndex) {
+        self.entries.retain(|&(device, _), _| device != index.0);
+    }
+
+    /// Forgets every cached property.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+fn is_persistent(err: &ETrackedPropertyError) -> bool {
+    use sys::ETrackedPropertyError::*;
+    matches!(
+        err.inner(),
+        TrackedProp_UnknownProperty
+            | TrackedProp_ValueNotProvidedByDevice
+            | TrackedProp_InvalidDevice
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: sys::EVREventType, index: u32) -> VREvent {
+        VREvent {
+            event_type,
+            tracked_device_index: TrackedDeviceIndex(index),
+            event_age_seconds: 0.0,
+            data: [0; std::mem::size_of::<sys::VREvent_t>() - 12],
+        }
+    }
+
+    #[test]
+    fn events_invalidate_only_their_device() {
+        let mut cache = PropertyCache::new();
+        cache.entries.insert((0, 1), Ok(PropValue::Bool(true)));
+        cache.entries.insert((1, 1), Ok(PropValue::Float(0.5)));
+
+        cache.handle_event(&event(sys::EVREventType::VREvent_ButtonPress, 0));
+        assert_eq!(cache.entries.len(), 2);
+
+        cache.handle_event(&event(sys::EVREventType::VREvent_PropertyChanged, 0));
+        assert!(!cache.entries.contains_key(&(0, 1)));
+        assert!(cache.entries.contains_key(&(1, 1)));
+    }
+
+    #[cfg(feature = "mock")]
+    #[test]
+    fn reads_through_the_system_trait() {
+        use crate::mock::{MockDevice, MockSystem};
+        use sys::ETrackedDeviceProperty::*;
+
+        let mut system = MockSystem::new();
+        system.set_device(
+            TrackedDeviceIndex::HMD,
+            MockDevice::new(sys::ETrackedDeviceClass::TrackedDeviceClass_HMD)
+                .with_property(Prop_DisplayFrequency_Float, PropValue::Float(90.)),
+        );
+        let mut cache = PropertyCache::new();
+        let read = |cache: &mut PropertyCache, system: &mut MockSystem| {
+            cache.get::<f32>(system, TrackedDeviceIndex::HMD, Prop_DisplayFrequency_Float)
+        };
+        assert_eq!(read(&mut cache, &mut system).unwrap(), 90.);
+
+        // Served from the cache until the device's properties change.
+        system.remove_device(TrackedDeviceIndex::HMD);
+        assert_eq!(read(&mut cache, &mut system).unwrap(), 90.);
+        cache.invalidate_device(TrackedDeviceIndex::HMD);
+        assert!(read(&mut cache, &mut system).is_err());
+    }
+}
@@ -0,0 +1,268 @@
+//! Well-known tracked device properties grouped by category, tagged with the type they
+//! are stored as, so they can be read without knowing each property's type.
+//!
+//! ```no_run
+//! # use ovr_overlay::system::{props, SystemManager};
+//! # use ovr_overlay::TrackedDeviceIndex;
+//! # fn example(system: &mut SystemManager) {
+//! for prop in props::BATTERY {
+//!     match system.read_any(TrackedDeviceIndex::HMD, &prop.request) {
+//!         Ok(value) => println!("{}: {value:?}", prop.name),
+//!         Err(err) => println!("{}: {err}", prop.name),
+//!     }
+//! }
+//! # }
+//! ```
+
+use super::{PropRequest, PropResult, PropValue, SystemManager};
+use crate::errors::ETrackedPropertyError;
+use crate::traits::System;
+use crate::{sys, TrackedDeviceIndex};
+
+/// A property with its name and storage type.
+#[derive(Clone)]
+pub struct PropInfo {
+    /// The name of the property without the `Prop_` prefix and type suffix, like
+    /// `DisplayFrequency`.
+    pub name: &'static str,
+    pub request: PropRequest,
+}
+
+macro_rules! props {
+    ($(#[$meta:meta])* $category:ident: [$($ty:ident $name:ident => $prop:ident),+ $(,)?]) => {
+        $(#[$meta])*
+        pub const $category: &[PropInfo] = &[$(PropInfo {
+            name: stringify!($name),
+            request: PropRequest::$ty(sys::ETrackedDeviceProperty::$prop),
+        }),+];
+    };
+}
+
+props!(
+    /// What the device is and who made it.
+    IDENTIFICATION: [
+        String TrackingSystemName => Prop_TrackingSystemName_String,
+        String ModelNumber => Prop_ModelNumber_String,
+        String SerialNumber => Prop_SerialNumber_String,
+        String ManufacturerName => Prop_ManufacturerName_String,
+        String RenderModelName => Prop_RenderModelName_String,
+        String RegisteredDeviceType => Prop_RegisteredDeviceType_String,
+        Int32 DeviceClass => Prop_DeviceClass_Int32,
+        Int32 ControllerRoleHint => Prop_ControllerRoleHint_Int32,
+        Bool WillDriftInYaw => Prop_WillDriftInYaw_Bool,
+    ]
+);
+
+props!(
+    /// The headset's display. Only headsets provide these.
+    DISPLAY: [
+        Float DisplayFrequency => Prop_DisplayFrequency_Float,
+        Float SecondsFromVsyncToPhotons => Prop_SecondsFromVsyncToPhotons_Float,
+        Bool ReportsTimeSinceVSync => Prop_ReportsTimeSinceVSync_Bool,
+        Float UserIpdMeters => Prop_UserIpdMeters_Float,
+        Float UserHeadToEyeDepthMeters => Prop_UserHeadToEyeDepthMeters_Float,
+        Uint64 CurrentUniverseId => Prop_CurrentUniverseId_Uint64,
+        Bool IsOnDesktop => Prop_IsOnDesktop_Bool,
+        Bool DisplaySuppressed => Prop_DisplaySuppressed_Bool,
+        Int32 EdidVendorID => Prop_EdidVendorID_Int32,
+        Int32 EdidProductID => Prop_EdidProductID_Int32,
+        Uint64 DisplayHardwareVersion => Prop_DisplayHardwareVersion_Uint64,
+        Uint64 DisplayFirmwareVersion => Prop_DisplayFirmwareVersion_Uint64,
+        Bool DisplaySupportsMultipleFramerates => Prop_DisplaySupportsMultipleFramerates_Bool,
+    ]
+);
+
+props!(
+    /// Battery and power state.
+    BATTERY: [
+        Bool DeviceProvidesBatteryStatus => Prop_DeviceProvidesBatteryStatus_Bool,
+        Float DeviceBatteryPercentage => Prop_DeviceBatteryPercentage_Float,
+        Bool DeviceIsCharging => Prop_DeviceIsCharging_Bool,
+        Bool DeviceIsWireless => Prop_DeviceIsWireless_Bool,
+        Bool DeviceCanPowerOff => Prop_DeviceCanPowerOff_Bool,
+    ]
+);
+
+props!(
+    /// Firmware and hardware versions, and whether an update is available.
+    FIRMWARE: [
+        Bool FirmwareUpdateAvailable => Prop_Firmware_UpdateAvailable_Bool,
+        Bool FirmwareManualUpdate => Prop_Firmware_ManualUpdate_Bool,
+        String FirmwareManualUpdateURL => Prop_Firmware_ManualUpdateURL_String,
+        Bool FirmwareForceUpdateRequired => Prop_Firmware_ForceUpdateRequired_Bool,
+        String TrackingFirmwareVersion => Prop_TrackingFirmwareVersion_String,
+        String HardwareRevision => Prop_HardwareRevision_String,
+        Uint64 HardwareRevisionNumber => Prop_HardwareRevision_Uint64,
+        Uint64 FirmwareVersion => Prop_FirmwareVersion_Uint64,
+        Uint64 FPGAVersion => Prop_FPGAVersion_Uint64,
+        Uint64 VRCVersion => Prop_VRCVersion_Uint64,
+        Uint64 RadioVersion => Prop_RadioVersion_Uint64,
+        Uint64 DongleVersion => Prop_DongleVersion_Uint64,
+    ]
+);
+
+props!(
+    /// The headset's passthrough cameras.
+    CAMERA: [
+        Bool HasCamera => Prop_HasCamera_Bool,
+        Int32 NumCameras => Prop_NumCameras_Int32,
+        Uint64 CameraFirmwareVersion => Prop_CameraFirmwareVersion_Uint64,
+        String CameraFirmwareDescription => Prop_CameraFirmwareDescription_String,
+        Int32 CameraFrameLayout => Prop_CameraFrameLayout_Int32,
+        Int32 CameraStreamFormat => Prop_CameraStreamFormat_Int32,
+        Int32 CameraCompatibilityMode => Prop_CameraCompatibilityMode_Int32,
+    ]
+);
+
+/// Every category above, with its name.
+pub const ALL: &[(&str, &[PropInfo])] = &[
+    ("identification", IDENTIFICATION),
+    ("display", DISPLAY),
+    ("battery", BATTERY),
+    ("firmware", FIRMWARE),
+    ("camera", CAMERA),
+];
+
+/// The property in [`ALL`] with the raw value `prop`, like one from an event.
+pub fn find(prop: u32) -> Option<&'static PropInfo> {
+    ALL.iter()
+        .flat_map(|(_, props)| props.iter())
+        .find(|info| info.prop() as u32 == prop)
+}
+
+impl PropInfo {
+    pub fn prop(&self) -> sys::ETrackedDeviceProperty {
+        match &self.request {
+            PropRequest::Bool(prop)
+            | PropRequest::Float(prop)
+            | PropRequest::Int32(prop)
+            | PropRequest::Uint64(prop)
+            | PropRequest::String(prop) => prop.clone(),
+        }
+    }
+}
+
+impl<'c> SystemManager<'c> {
+    /// Reads a property as the type it is tagged with, such as one from [`props`](self).
+    pub fn read_any(
+        &mut self,
+        index: TrackedDeviceIndex,
+        request: &PropRequest,
+    ) -> PropResult<PropValue> {
+        self.get_property(index, request, &mut Vec::new())
+    }
+
+    /// Reads a property of unknown type, trying each type in turn until OpenVR doesn't
+    /// report `WrongDataType`.
+    pub fn read_any_type(
+        &mut self,
+        index: TrackedDeviceIndex,
+        prop: sys::ETrackedDeviceProperty,
+    ) -> PropResult<PropValue> {
+        read_as_any_type(self, index, prop, None)
+    }
+
+    /// Reads every property in [`ALL`] from a device, for dumping everything known
+    /// about it. Each property is read as its tagged type first, then as every other
+    /// type if that is the wrong one.
+    pub fn iter_properties(&mut self, index: TrackedDeviceIndex) -> PropertyIter<'_, 'c> {
+        PropertyIter {
+            system: self,
+            index,
+            category: 0,
+            prop: 0,
+        }
+    }
+}
+
+/// Reads `prop` as the same type as `hint`, or else as each type in turn until OpenVR
+/// doesn't report `WrongDataType`.
+pub(super) fn read_as_any_type(
+    system: &mut (impl System + ?Sized),
+    index: TrackedDeviceIndex,
+    prop: sys::ETrackedDeviceProperty,
+    hint: Option<&PropValue>,
+) -> PropResult<PropValue> {
+    let requests = [
+        PropRequest::Bool,
+        PropRequest::Float,
+        PropRequest::Int32,
+        PropRequest::Uint64,
+        PropRequest::String,
+    ];
+    let first = match hint {
+        Some(PropValue::Bool(_)) | None => 0,
+        Some(PropValue::Float(_)) => 1,
+        Some(PropValue::Int32(_)) => 2,
+        Some(PropValue::Uint64(_)) => 3,
+        Some(PropValue::String(_)) => 4,
+    };
+    let order = std::iter::once(first).chain((0..requests.len()).filter(|&i| i != first));
+    let mut result = Err(ETrackedPropertyError::new(
+        sys::ETrackedPropertyError::TrackedProp_WrongDataType,
+    )
+    .unwrap_err());
+    for i in order {
+        result = system.read_any(index, &requests[i](prop.clone()));
+        match &result {
+            Err(err) if err.inner() == sys::ETrackedPropertyError::TrackedProp_WrongDataType => {}
+            _ => break,
+        }
+    }
+    result
+}
+
+/// Returned by [`SystemManager::iter_properties()`].
+pub struct PropertyIter<'a, 'c> {
+    system: &'a mut SystemManager<'c>,
+    index: TrackedDeviceIndex,
+    category: usize,
+    prop: usize,
+}
+
+impl Iterator for PropertyIter<'_, '_> {
+    type Item = (&'static PropInfo, PropResult<PropValue>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let info = loop {
+            let (_, props) = ALL.get(self.category)?;
+            if let Some(info) = props.get(self.prop) {
+                self.prop += 1;
+                break info;
+            }
+            self.category += 1;
+            self.prop = 0;
+        };
+        let result = match self.system.read_any(self.index, &info.request) {
+            Err(err) if err.inner() == sys::ETrackedPropertyError::TrackedProp_WrongDataType => {
+                self.system.read_any_type(self.index, info.prop())
+            }
+            result => result,
+        };
+        Some((info, result))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_names_are_unique() {
+        let mut names: Vec<_> = ALL
+            .iter()
+            .flat_map(|(_, props)| props.iter().map(|p| p.name))
+            .collect();
+        let len = names.len();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), len);
+    }
+
+    #[test]
+    fn test_find() {
+        let raw = sys::ETrackedDeviceProperty::Prop_DeviceBatteryPercentage_Float as u32;
+        assert_eq!(find(raw).unwrap().name, "DeviceBatteryPercentage");
+        assert!(find(u32::MAX).is_none());
+    }
+}
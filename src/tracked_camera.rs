@@ -0,0 +1,263 @@
+use crate::pose::Matrix3x4;
+use crate::{
+    errors::EVRTrackedCameraError, sys, Context, Interface, InterfaceLock, TrackedDeviceIndex,
+};
+
+use derive_more::{From, Into};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::pin::Pin;
+
+#[cfg(feature = "image")]
+pub mod recording;
+
+pub struct TrackedCameraManager<'c> {
+    ctx: PhantomData<&'c Context>,
+    inner: Pin<&'c mut sys::IVRTrackedCamera>,
+    _lock: InterfaceLock<'c>,
+    /// The device of each acquired stream, to look up its frame size.
+    streams: HashMap<TrackedCameraHandle, TrackedDeviceIndex>,
+}
+
+type Result<T> = std::result::Result<T, EVRTrackedCameraError>;
+
+/// Handle to an acquired video stream. Release it with
+/// [`TrackedCameraManager::release_video_streaming_service()`].
+#[derive(From, Into, Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[repr(transparent)]
+pub struct TrackedCameraHandle(pub sys::TrackedCameraHandle_t);
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum CameraFrameType {
+    Distorted,
+    Undistorted,
+    MaximumUndistorted,
+}
+impl From<CameraFrameType> for sys::EVRTrackedCameraFrameType {
+    fn from(ty: CameraFrameType) -> Self {
+        use sys::EVRTrackedCameraFrameType::*;
+        match ty {
+            CameraFrameType::Distorted => VRTrackedCameraFrameType_Distorted,
+            CameraFrameType::Undistorted => VRTrackedCameraFrameType_Undistorted,
+            CameraFrameType::MaximumUndistorted => VRTrackedCameraFrameType_MaximumUndistorted,
+        }
+    }
+}
+
+/// Dimensions of the frames of a camera.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CameraFrameSize {
+    pub width: u32,
+    pub height: u32,
+    pub frame_buffer_size: u32,
+}
+
+/// Metadata of a single camera frame.
+#[derive(Debug, PartialEq)]
+pub struct CameraFrameHeader {
+    pub width: u32,
+    pub height: u32,
+    pub bytes_per_pixel: u32,
+    pub frame_sequence: u32,
+    /// Pose of the device at the time the frame was captured.
+    pub device_to_absolute_tracking: Matrix3x4,
+    pub pose_is_valid: bool,
+    pub frame_exposure_time: u64,
+}
+impl From<&sys::CameraVideoStreamFrameHeader_t> for CameraFrameHeader {
+    fn from(header: &sys::CameraVideoStreamFrameHeader_t) -> Self {
+        let pose = &header.trackedDevicePose;
+        Self {
+            width: header.nWidth,
+            height: header.nHeight,
+            bytes_per_pixel: header.nBytesPerPixel,
+            frame_sequence: header.nFrameSequence,
+            device_to_absolute_tracking: Matrix3x4(
+                <&Matrix3x4>::from(&pose.mDeviceToAbsoluteTracking).0,
+            ),
+            pose_is_valid: pose.bPoseIsValid,
+            frame_exposure_time: header.ulFrameExposureTime,
+        }
+    }
+}
+
+/// A camera frame copied into memory owned by the caller.
+#[derive(Debug, PartialEq)]
+pub struct CameraFrame {
+    pub header: CameraFrameHeader,
+    /// Pixel data, [`CameraFrameSize::frame_buffer_size`] bytes.
+    pub data: Vec<u8>,
+}
+
+#[cfg(feature = "image")]
+impl CameraFrame {
+    /// Converts RGBA and RGB frames to an image. Returns `None` for other pixel formats,
+    /// or if `data` is too short for the header's dimensions.
+    pub fn to_rgba_image(&self) -> Option<image::RgbaImage> {
+        let (width, height) = (self.header.width, self.header.height);
+        let pixels = width as usize * height as usize;
+        let data = match self.header.bytes_per_pixel {
+            4 => self.data.get(..pixels * 4)?.to_vec(),
+            3 => self
+                .data
+                .get(..pixels * 3)?
+                .chunks_exact(3)
+                .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], u8::MAX])
+                .collect(),
+            _ => return None,
+        };
+        image::RgbaImage::from_raw(width, height, data)
+    }
+}
+
+impl<'c> TrackedCameraManager<'c> {
+    pub(super) fn new(ctx: &'c Context) -> Self {
+        let (inner, lock) = unsafe { ctx.acquire(Interface::TrackedCamera) };
+        Self {
+            ctx: Default::default(),
+            inner,
+            _lock: lock,
+            streams: HashMap::new(),
+        }
+    }
+
+    /// Wraps c++ `HasCamera`.
+    pub fn has_camera(&mut self, index: TrackedDeviceIndex) -> Result<bool> {
+        let mut has_camera = false;
+        let err = unsafe { self.inner.as_mut().HasCamera(index.0, &mut has_camera) };
+        EVRTrackedCameraError::new(err)?;
+        Ok(has_camera)
+    }
+
+    /// Wraps c++ `GetCameraFrameSize`.
+    pub fn get_camera_frame_size(
+        &mut self,
+        index: TrackedDeviceIndex,
+        frame_type: CameraFrameType,
+    ) -> Result<CameraFrameSize> {
+        let mut size = CameraFrameSize {
+            width: 0,
+            height: 0,
+            frame_buffer_size: 0,
+        };
+        let err = unsafe {
+            self.inner.as_mut().GetCameraFrameSize(
+                index.0,
+                frame_type.into(),
+                &mut size.width,
+                &mut size.height,
+                &mut size.frame_buffer_size,
+            )
+        };
+        EVRTrackedCameraError::new(err)?;
+        Ok(size)
+    }
+
+    /// Starts streaming the camera of the given device.
+    ///
+    /// Wraps c++ `AcquireVideoStreamingService`.
+    pub fn acquire_video_streaming_service(
+        &mut self,
+        index: TrackedDeviceIndex,
+    ) -> Result<TrackedCameraHandle> {
+        let mut handle: sys::TrackedCameraHandle_t = 0;
+        let err = unsafe {
+            self.inner
+                .as_mut()
+                .AcquireVideoStreamingService(index.0, &mut handle)
+        };
+        EVRTrackedCameraError::new(err)?;
+        self.streams.insert(TrackedCameraHandle(handle), index);
+        Ok(TrackedCameraHandle(handle))
+    }
+
+    /// Wraps c++ `ReleaseVideoStreamingService`.
+    pub fn release_video_streaming_service(&mut self, handle: TrackedCameraHandle) -> Result<()> {
+        let err = unsafe { self.inner.as_mut().ReleaseVideoStreamingService(handle.0) };
+        self.streams.remove(&handle);
+        EVRTrackedCameraError::new(err)
+    }
+
+    /// Reads only the header of the latest frame, which is cheaper than copying the frame.
+    ///
+    /// Wraps c++ `GetVideoStreamFrameBuffer`.
+    pub fn get_video_stream_frame_header(
+        &mut self,
+        handle: TrackedCameraHandle,
+        frame_type: CameraFrameType,
+    ) -> Result<CameraFrameHeader> {
+        let mut header = MaybeUninit::<sys::CameraVideoStreamFrameHeader_t>::zeroed();
+        let err = unsafe {
+            self.inner.as_mut().GetVideoStreamFrameBuffer(
+                handle.0,
+                frame_type.into(),
+                std::ptr::null_mut(),
+                0,
+                header.as_mut_ptr(),
+                std::mem::size_of::<sys::CameraVideoStreamFrameHeader_t>() as u32,
+            )
+        };
+        EVRTrackedCameraError::new(err)?;
+        Ok(CameraFrameHeader::from(unsafe { &header.assume_init() }))
+    }
+
+    /// Copies the latest frame of the stream, into a buffer of the size
+    /// [`Self::get_camera_frame_size()`] reports.
+    ///
+    /// Returns a `NoFrameAvailable` error until the first frame has arrived, and an
+    /// `InvalidHandle` error if `handle` wasn't acquired through this manager.
+    ///
+    /// Wraps c++ `GetVideoStreamFrameBuffer`.
+    pub fn get_video_stream_frame_buffer(
+        &mut self,
+        handle: TrackedCameraHandle,
+        frame_type: CameraFrameType,
+    ) -> Result<CameraFrame> {
+        let Some(&index) = self.streams.get(&handle) else {
+            return Err(EVRTrackedCameraError::new(
+                sys::EVRTrackedCameraError::VRTrackedCameraError_InvalidHandle,
+            )
+            .unwrap_err());
+        };
+        let size = self.get_camera_frame_size(index, frame_type)?;
+        let mut data = vec![0u8; size.frame_buffer_size as usize];
+        let mut header = MaybeUninit::<sys::CameraVideoStreamFrameHeader_t>::zeroed();
+        let err = unsafe {
+            self.inner.as_mut().GetVideoStreamFrameBuffer(
+                handle.0,
+                frame_type.into(),
+                data.as_mut_ptr().cast(),
+                data.len() as u32,
+                header.as_mut_ptr(),
+                std::mem::size_of::<sys::CameraVideoStreamFrameHeader_t>() as u32,
+            )
+        };
+        EVRTrackedCameraError::new(err)?;
+        Ok(CameraFrame {
+            header: CameraFrameHeader::from(unsafe { &header.assume_init() }),
+            data,
+        })
+    }
+
+    /// Copies the latest frame of the stream as an image. Use
+    /// [`Self::get_video_stream_frame_buffer()`] and [`CameraFrame::to_rgba_image()`]
+    /// to also get the frame's header.
+    ///
+    /// Returns an `InvalidFrameBufferSize` error if the frame isn't RGBA or RGB.
+    #[cfg(feature = "image")]
+    pub fn capture_frame_image(
+        &mut self,
+        handle: TrackedCameraHandle,
+        frame_type: CameraFrameType,
+    ) -> Result<image::RgbaImage> {
+        let frame = self.get_video_stream_frame_buffer(handle, frame_type)?;
+        match frame.to_rgba_image() {
+            Some(image) => Ok(image),
+            None => Err(EVRTrackedCameraError::new(
+                sys::EVRTrackedCameraError::VRTrackedCameraError_InvalidFrameBufferSize,
+            )
+            .unwrap_err()),
+        }
+    }
+}
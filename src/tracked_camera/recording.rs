@@ -0,0 +1,189 @@
+//! Records camera frames as images with the time they were captured at, for room
+//! mapping experiments or attaching to bug reports.
+//!
+//! ```no_run
+//! # use ovr_overlay::tracked_camera::{CameraFrameType, TrackedCameraManager};
+//! # use ovr_overlay::tracked_camera::recording::FrameRecorder;
+//! # use ovr_overlay::TrackedDeviceIndex;
+//! # fn example(camera: &mut TrackedCameraManager) {
+//! let handle = camera
+//!     .acquire_video_streaming_service(TrackedDeviceIndex::HMD)
+//!     .unwrap();
+//! let mut recorder =
+//!     FrameRecorder::new(handle, CameraFrameType::Distorted).with_max_frames(300);
+//! for _ in 0..600 {
+//!     recorder.capture(camera).unwrap();
+//!     std::thread::sleep(std::time::Duration::from_millis(16));
+//! }
+//! camera.release_video_streaming_service(handle).unwrap();
+//! recorder.save("camera_frames").unwrap();
+//! # }
+//! ```
+
+use super::{CameraFrameHeader, CameraFrameType, TrackedCameraHandle, TrackedCameraManager};
+use crate::errors::EVRTrackedCameraError;
+use crate::sys;
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub struct RecordedFrame {
+    /// Time since the first recorded frame.
+    pub elapsed: Duration,
+    pub header: CameraFrameHeader,
+    pub image: image::RgbaImage,
+}
+
+/// Records each new frame of a stream once, keeping the latest `max_frames`.
+pub struct FrameRecorder {
+    handle: TrackedCameraHandle,
+    frame_type: CameraFrameType,
+    max_frames: Option<usize>,
+    started: Option<Instant>,
+    last_sequence: Option<u32>,
+    frames: VecDeque<RecordedFrame>,
+}
+
+impl FrameRecorder {
+    /// Records from an acquired stream. Unlimited by default.
+    pub fn new(handle: TrackedCameraHandle, frame_type: CameraFrameType) -> Self {
+        Self {
+            handle,
+            frame_type,
+            max_frames: None,
+            started: None,
+            last_sequence: None,
+            frames: VecDeque::new(),
+        }
+    }
+
+    /// Only keep the latest `max_frames` frames, dropping older ones.
+    pub fn with_max_frames(mut self, max_frames: usize) -> Self {
+        self.max_frames = Some(max_frames);
+        self
+    }
+
+    /// Records the latest frame if it wasn't recorded yet. Returns whether a frame was
+    /// recorded. Call this at least as often as the camera's frame rate to not miss
+    /// frames.
+    pub fn capture(
+        &mut self,
+        mngr: &mut TrackedCameraManager,
+    ) -> Result<bool, EVRTrackedCameraError> {
+        let header = match mngr.get_video_stream_frame_header(self.handle, self.frame_type) {
+            Ok(header) => header,
+            Err(err)
+                if err.inner()
+                    == sys::EVRTrackedCameraError::VRTrackedCameraError_NoFrameAvailable =>
+            {
+                return Ok(false);
+            }
+            Err(err) => return Err(err),
+        };
+        if self.last_sequence == Some(header.frame_sequence) {
+            return Ok(false);
+        }
+        let frame = mngr.get_video_stream_frame_buffer(self.handle, self.frame_type)?;
+        let Some(image) = frame.to_rgba_image() else {
+            return Err(EVRTrackedCameraError::new(
+                sys::EVRTrackedCameraError::VRTrackedCameraError_InvalidFrameBufferSize,
+            )
+            .unwrap_err());
+        };
+        self.push(frame.header, image, Instant::now());
+        Ok(true)
+    }
+
+    fn push(&mut self, header: CameraFrameHeader, image: image::RgbaImage, now: Instant) {
+        let started = *self.started.get_or_insert(now);
+        self.last_sequence = Some(header.frame_sequence);
+        self.frames.push_back(RecordedFrame {
+            elapsed: now - started,
+            header,
+            image,
+        });
+        if let Some(max_frames) = self.max_frames {
+            while self.frames.len() > max_frames {
+                self.frames.pop_front();
+            }
+        }
+    }
+
+    /// The recorded frames, oldest first.
+    pub fn frames(&self) -> &VecDeque<RecordedFrame> {
+        &self.frames
+    }
+
+    /// Removes the recorded frames and restarts the timestamps.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.started = None;
+    }
+
+    /// Writes the frames to `dir` as numbered PNG files, along with a `frames.csv`
+    /// listing each file's frame sequence number and time in milliseconds. `dir` is
+    /// created if it doesn't exist.
+    pub fn save(&self, dir: impl AsRef<Path>) -> image::ImageResult<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let mut csv = std::io::BufWriter::new(std::fs::File::create(dir.join("frames.csv"))?);
+        writeln!(csv, "file,sequence,elapsed_ms")?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            let file = format!("frame_{i:05}.png");
+            frame.image.save(dir.join(&file))?;
+            writeln!(
+                csv,
+                "{file},{},{}",
+                frame.header.frame_sequence,
+                frame.elapsed.as_millis()
+            )?;
+        }
+        csv.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::pose::Matrix3x4;
+
+    fn header(frame_sequence: u32) -> CameraFrameHeader {
+        CameraFrameHeader {
+            width: 1,
+            height: 1,
+            bytes_per_pixel: 4,
+            frame_sequence,
+            device_to_absolute_tracking: Matrix3x4::IDENTITY,
+            pose_is_valid: true,
+            frame_exposure_time: 0,
+        }
+    }
+
+    #[test]
+    fn test_max_frames() {
+        let handle = TrackedCameraHandle(1);
+        let mut recorder =
+            FrameRecorder::new(handle, CameraFrameType::Distorted).with_max_frames(2);
+        let t0 = Instant::now();
+        for i in 0..3 {
+            let image = image::RgbaImage::new(1, 1);
+            recorder.push(header(i), image, t0 + Duration::from_millis(i as u64 * 10));
+        }
+        let frames: Vec<_> = recorder
+            .frames()
+            .iter()
+            .map(|frame| (frame.header.frame_sequence, frame.elapsed))
+            .collect();
+        assert_eq!(
+            frames,
+            [
+                (1, Duration::from_millis(10)),
+                (2, Duration::from_millis(20))
+            ]
+        );
+        assert_eq!(recorder.last_sequence, Some(2));
+    }
+}
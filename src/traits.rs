@@ -0,0 +1,379 @@
+//! Traits over the manager methods that application logic uses most, implemented by
+//! the managers themselves.
+//!
+//! Writing logic against these instead of the concrete managers allows testing it
+//! with the in-memory fakes of [`crate::mock`] (with the `mock` feature), without a
+//! running SteamVR.
+
+use crate::animation::OverlayFade;
+use crate::errors::EVROverlayError;
+use crate::overlay::{OverlayHandle, OverlayManager};
+use crate::pose::{Matrix3x4, TrackingUniverseOrigin};
+use crate::{sys, TrackedDeviceIndex};
+
+use std::time::Duration;
+
+/// See [`OverlayManager`].
+pub trait Overlay {
+    fn create_overlay(
+        &mut self,
+        key: &str,
+        friendly_name: &str,
+    ) -> Result<OverlayHandle, EVROverlayError>;
+    fn destroy_overlay(&mut self, overlay: OverlayHandle) -> Result<(), EVROverlayError>;
+    fn set_visibility(
+        &mut self,
+        overlay: OverlayHandle,
+        is_visible: bool,
+    ) -> Result<(), EVROverlayError>;
+    fn is_visible(&mut self, overlay: OverlayHandle) -> bool;
+    fn set_opacity(&mut self, overlay: OverlayHandle, alpha: f32) -> Result<(), EVROverlayError>;
+    fn opacity(&mut self, overlay: OverlayHandle) -> Result<f32, EVROverlayError>;
+    fn set_width(
+        &mut self,
+        overlay: OverlayHandle,
+        width_in_meters: f32,
+    ) -> Result<(), EVROverlayError>;
+    fn width(&mut self, overlay: OverlayHandle) -> Result<f32, EVROverlayError>;
+    fn set_transform_absolute(
+        &mut self,
+        overlay: OverlayHandle,
+        origin: TrackingUniverseOrigin,
+        origin_to_overlay: &Matrix3x4,
+    ) -> Result<(), EVROverlayError>;
+    fn set_transform_tracked_device_relative(
+        &mut self,
+        overlay: OverlayHandle,
+        index: TrackedDeviceIndex,
+        device_to_overlay: &Matrix3x4,
+    ) -> Result<(), EVROverlayError>;
+    fn get_transform_tracked_device_relative(
+        &mut self,
+        overlay: OverlayHandle,
+        device_to_overlay: &mut Matrix3x4,
+    ) -> Result<TrackedDeviceIndex, EVROverlayError>;
+    #[cfg(feature = "ovr_system")]
+    fn poll_next_overlay_event(&mut self, overlay: OverlayHandle)
+        -> Option<crate::system::VREvent>;
+
+    /// Shows an overlay, fading it in over `duration`. Tick the returned fade every
+    /// frame until it finishes.
+    fn show_faded(
+        &mut self,
+        overlay: OverlayHandle,
+        duration: Duration,
+    ) -> Result<OverlayFade, EVROverlayError> {
+        OverlayFade::show(self, overlay, duration)
+    }
+
+    /// Fades an overlay out over `duration`, hiding it once the returned fade finishes.
+    fn hide_faded(
+        &mut self,
+        overlay: OverlayHandle,
+        duration: Duration,
+    ) -> Result<OverlayFade, EVROverlayError> {
+        OverlayFade::hide(self, overlay, duration)
+    }
+}
+
+impl Overlay for OverlayManager<'_> {
+    fn create_overlay(
+        &mut self,
+        key: &str,
+        friendly_name: &str,
+    ) -> Result<OverlayHandle, EVROverlayError> {
+        OverlayManager::create_overlay(self, key, friendly_name)
+    }
+
+    fn destroy_overlay(&mut self, overlay: OverlayHandle) -> Result<(), EVROverlayError> {
+        OverlayManager::destroy_overlay(self, overlay)
+    }
+
+    fn set_visibility(
+        &mut self,
+        overlay: OverlayHandle,
+        is_visible: bool,
+    ) -> Result<(), EVROverlayError> {
+        OverlayManager::set_visibility(self, overlay, is_visible)
+    }
+
+    fn is_visible(&mut self, overlay: OverlayHandle) -> bool {
+        OverlayManager::is_visible(self, overlay)
+    }
+
+    fn set_opacity(&mut self, overlay: OverlayHandle, alpha: f32) -> Result<(), EVROverlayError> {
+        OverlayManager::set_opacity(self, overlay, alpha)
+    }
+
+    fn opacity(&mut self, overlay: OverlayHandle) -> Result<f32, EVROverlayError> {
+        OverlayManager::opacity(self, overlay)
+    }
+
+    fn set_width(
+        &mut self,
+        overlay: OverlayHandle,
+        width_in_meters: f32,
+    ) -> Result<(), EVROverlayError> {
+        OverlayManager::set_width(self, overlay, width_in_meters)
+    }
+
+    fn width(&mut self, overlay: OverlayHandle) -> Result<f32, EVROverlayError> {
+        OverlayManager::width(self, overlay)
+    }
+
+    fn set_transform_absolute(
+        &mut self,
+        overlay: OverlayHandle,
+        origin: TrackingUniverseOrigin,
+        origin_to_overlay: &Matrix3x4,
+    ) -> Result<(), EVROverlayError> {
+        OverlayManager::set_transform_absolute(self, overlay, origin, origin_to_overlay)
+    }
+
+    fn set_transform_tracked_device_relative(
+        &mut self,
+        overlay: OverlayHandle,
+        index: TrackedDeviceIndex,
+        device_to_overlay: &Matrix3x4,
+    ) -> Result<(), EVROverlayError> {
+        OverlayManager::set_transform_tracked_device_relative(
+            self,
+            overlay,
+            index,
+            device_to_overlay,
+        )
+    }
+
+    fn get_transform_tracked_device_relative(
+        &mut self,
+        overlay: OverlayHandle,
+        device_to_overlay: &mut Matrix3x4,
+    ) -> Result<TrackedDeviceIndex, EVROverlayError> {
+        OverlayManager::get_transform_tracked_device_relative(self, overlay, device_to_overlay)
+    }
+
+    #[cfg(feature = "ovr_system")]
+    fn poll_next_overlay_event(
+        &mut self,
+        overlay: OverlayHandle,
+    ) -> Option<crate::system::VREvent> {
+        OverlayManager::poll_next_overlay_event(self, overlay)
+    }
+}
+
+#[cfg(feature = "ovr_system")]
+pub use self::system::System;
+#[cfg(feature = "ovr_system")]
+mod system {
+    use super::*;
+    use crate::errors::ETrackedPropertyError;
+    use crate::system::{PropRequest, PropValue, SystemManager, VREvent};
+
+    /// See [`SystemManager`].
+    pub trait System {
+        fn poll_next_event(&mut self) -> Option<VREvent>;
+        fn get_tracked_device_class(
+            &mut self,
+            index: TrackedDeviceIndex,
+        ) -> sys::ETrackedDeviceClass;
+        fn is_tracked_device_connected(&mut self, index: TrackedDeviceIndex) -> bool;
+        fn get_tracked_device_activity_level(
+            &mut self,
+            index: TrackedDeviceIndex,
+        ) -> sys::EDeviceActivityLevel;
+        fn get_controller_role_for_tracked_device_index(
+            &mut self,
+            index: TrackedDeviceIndex,
+        ) -> sys::ETrackedControllerRole;
+        fn get_tracked_device_index_for_controller_role(
+            &mut self,
+            role: sys::ETrackedControllerRole,
+        ) -> Option<TrackedDeviceIndex>;
+        fn get_device_to_absolute_tracking_pose_into(
+            &mut self,
+            origin: TrackingUniverseOrigin,
+            predicted_seconds_to_photons_from_now: f32,
+            poses: &mut [sys::TrackedDevicePose_t],
+        );
+        fn hmd_pose(&mut self, origin: TrackingUniverseOrigin) -> Option<Matrix3x4>;
+        fn get_properties(
+            &mut self,
+            index: TrackedDeviceIndex,
+            requests: &[PropRequest],
+        ) -> Vec<Result<PropValue, ETrackedPropertyError>>;
+        fn read_any(
+            &mut self,
+            index: TrackedDeviceIndex,
+            request: &PropRequest,
+        ) -> Result<PropValue, ETrackedPropertyError>;
+        fn trigger_haptic_pulse(
+            &mut self,
+            index: TrackedDeviceIndex,
+            axis_id: u32,
+            duration: Duration,
+        );
+    }
+
+    impl System for SystemManager<'_> {
+        fn poll_next_event(&mut self) -> Option<VREvent> {
+            SystemManager::poll_next_event(self)
+        }
+
+        fn get_tracked_device_class(
+            &mut self,
+            index: TrackedDeviceIndex,
+        ) -> sys::ETrackedDeviceClass {
+            SystemManager::get_tracked_device_class(self, index)
+        }
+
+        fn is_tracked_device_connected(&mut self, index: TrackedDeviceIndex) -> bool {
+            SystemManager::is_tracked_device_connected(self, index)
+        }
+
+        fn get_tracked_device_activity_level(
+            &mut self,
+            index: TrackedDeviceIndex,
+        ) -> sys::EDeviceActivityLevel {
+            SystemManager::get_tracked_device_activity_level(self, index)
+        }
+
+        fn get_controller_role_for_tracked_device_index(
+            &mut self,
+            index: TrackedDeviceIndex,
+        ) -> sys::ETrackedControllerRole {
+            SystemManager::get_controller_role_for_tracked_device_index(self, index)
+        }
+
+        fn get_tracked_device_index_for_controller_role(
+            &mut self,
+            role: sys::ETrackedControllerRole,
+        ) -> Option<TrackedDeviceIndex> {
+            SystemManager::get_tracked_device_index_for_controller_role(self, role)
+        }
+
+        fn get_device_to_absolute_tracking_pose_into(
+            &mut self,
+            origin: TrackingUniverseOrigin,
+            predicted_seconds_to_photons_from_now: f32,
+            poses: &mut [sys::TrackedDevicePose_t],
+        ) {
+            SystemManager::get_device_to_absolute_tracking_pose_into(
+                self,
+                origin,
+                predicted_seconds_to_photons_from_now,
+                poses,
+            )
+        }
+
+        fn hmd_pose(&mut self, origin: TrackingUniverseOrigin) -> Option<Matrix3x4> {
+            SystemManager::hmd_pose(self, origin)
+        }
+
+        fn get_properties(
+            &mut self,
+            index: TrackedDeviceIndex,
+            requests: &[PropRequest],
+        ) -> Vec<Result<PropValue, ETrackedPropertyError>> {
+            SystemManager::get_properties(self, index, requests)
+        }
+
+        fn read_any(
+            &mut self,
+            index: TrackedDeviceIndex,
+            request: &PropRequest,
+        ) -> Result<PropValue, ETrackedPropertyError> {
+            SystemManager::read_any(self, index, request)
+        }
+
+        fn trigger_haptic_pulse(
+            &mut self,
+            index: TrackedDeviceIndex,
+            axis_id: u32,
+            duration: Duration,
+        ) {
+            SystemManager::trigger_haptic_pulse(self, index, axis_id, duration)
+        }
+    }
+}
+
+#[cfg(feature = "ovr_input")]
+pub use self::input::Input;
+#[cfg(feature = "ovr_input")]
+mod input {
+    use crate::errors::EVRInputError;
+    use crate::input::{
+        ActionHandle, ActionSetHandle, ActiveActionSet, AnalogActionData, DigitalActionData,
+        InputManager, InputValueHandle, PoseActionData,
+    };
+    use crate::pose::TrackingUniverseOrigin;
+
+    /// See [`InputManager`].
+    pub trait Input {
+        fn get_action_set_handle(&mut self, name: &str) -> Result<ActionSetHandle, EVRInputError>;
+        fn get_action_handle(&mut self, name: &str) -> Result<ActionHandle, EVRInputError>;
+        fn update_actions(&mut self, sets: &mut [ActiveActionSet]) -> Result<(), EVRInputError>;
+        fn get_digital_action_data(
+            &mut self,
+            action: ActionHandle,
+            restrict: InputValueHandle,
+        ) -> Result<DigitalActionData, EVRInputError>;
+        fn get_analog_action_data(
+            &mut self,
+            action: ActionHandle,
+            restrict: InputValueHandle,
+        ) -> Result<AnalogActionData, EVRInputError>;
+        fn get_pose_action_data_relative_to_now(
+            &mut self,
+            action: ActionHandle,
+            universe: TrackingUniverseOrigin,
+            seconds_from_now: f32,
+            restrict: InputValueHandle,
+        ) -> Result<PoseActionData, EVRInputError>;
+    }
+
+    impl Input for InputManager<'_> {
+        fn get_action_set_handle(&mut self, name: &str) -> Result<ActionSetHandle, EVRInputError> {
+            InputManager::get_action_set_handle(self, name)
+        }
+
+        fn get_action_handle(&mut self, name: &str) -> Result<ActionHandle, EVRInputError> {
+            InputManager::get_action_handle(self, name)
+        }
+
+        fn update_actions(&mut self, sets: &mut [ActiveActionSet]) -> Result<(), EVRInputError> {
+            InputManager::update_actions(self, sets)
+        }
+
+        fn get_digital_action_data(
+            &mut self,
+            action: ActionHandle,
+            restrict: InputValueHandle,
+        ) -> Result<DigitalActionData, EVRInputError> {
+            InputManager::get_digital_action_data(self, action, restrict)
+        }
+
+        fn get_analog_action_data(
+            &mut self,
+            action: ActionHandle,
+            restrict: InputValueHandle,
+        ) -> Result<AnalogActionData, EVRInputError> {
+            InputManager::get_analog_action_data(self, action, restrict)
+        }
+
+        fn get_pose_action_data_relative_to_now(
+            &mut self,
+            action: ActionHandle,
+            universe: TrackingUniverseOrigin,
+            seconds_from_now: f32,
+            restrict: InputValueHandle,
+        ) -> Result<PoseActionData, EVRInputError> {
+            InputManager::get_pose_action_data_relative_to_now(
+                self,
+                action,
+                universe,
+                seconds_from_now,
+                restrict,
+            )
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! Vulkan texture submission using [`ash`] handle types instead of raw integers and
+//! pointers.
+
+use crate::overlay::{OverlayHandle, OverlayManager};
+use crate::sys;
+
+use ash::vk::{self, Handle};
+
+/// A Vulkan image, along with the objects OpenVR needs to read it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VulkanTexture {
+    pub image: vk::Image,
+    pub device: vk::Device,
+    pub physical_device: vk::PhysicalDevice,
+    pub instance: vk::Instance,
+    pub queue: vk::Queue,
+    pub queue_family_index: u32,
+    pub width: u32,
+    pub height: u32,
+    pub format: vk::Format,
+    pub sample_count: u32,
+}
+
+impl From<VulkanTexture> for sys::VRVulkanTextureData_t {
+    fn from(t: VulkanTexture) -> Self {
+        Self {
+            m_nImage: t.image.as_raw(),
+            m_pDevice: t.device.as_raw() as usize as _,
+            m_pPhysicalDevice: t.physical_device.as_raw() as usize as _,
+            m_pInstance: t.instance.as_raw() as usize as _,
+            m_pQueue: t.queue.as_raw() as usize as _,
+            m_nQueueFamilyIndex: t.queue_family_index,
+            m_nWidth: t.width,
+            m_nHeight: t.height,
+            m_nFormat: t.format.as_raw() as u32,
+            m_nSampleCount: t.sample_count,
+        }
+    }
+}
+
+impl<'c> OverlayManager<'c> {
+    pub fn set_image_vk(
+        &mut self,
+        overlay: OverlayHandle,
+        texture: &VulkanTexture,
+    ) -> Result<(), crate::errors::EVROverlayError> {
+        self.set_image_vulkan(overlay, &mut (*texture).into())
+    }
+}
+
+impl crate::overlay::double_buffer::OverlayTexture for VulkanTexture {
+    fn submit(
+        &mut self,
+        mngr: &mut OverlayManager,
+        overlay: OverlayHandle,
+    ) -> Result<(), crate::errors::EVROverlayError> {
+        mngr.set_image_vk(overlay, self)
+    }
+}
+
+#[cfg(feature = "ovr_compositor")]
+impl<'c> crate::compositor::CompositorManager<'c> {
+    /// Submits a Vulkan image as the frame for `eye`, optionally cropped to `bounds`.
+    pub fn submit_vk(
+        &mut self,
+        eye: sys::EVREye,
+        texture: &VulkanTexture,
+        bounds: Option<&crate::TextureBounds>,
+    ) -> Result<(), crate::errors::EVRCompositorError> {
+        self.submit_vulkan(eye, &mut (*texture).into(), bounds)
+    }
+}
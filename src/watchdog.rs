@@ -0,0 +1,172 @@
+//! Keeps a [`Context`] alive across SteamVR restarts.
+//!
+//! ```no_run
+//! # use ovr_overlay::{ApplicationType, watchdog::{Watchdog, WatchdogEvent}};
+//! # use std::time::Duration;
+//! let mut watchdog = Watchdog::new(ApplicationType::Overlay, Duration::from_secs(1));
+//! loop {
+//!     if let Some(WatchdogEvent::Connected) = watchdog.tick() {
+//!         // (re)create overlays, action handles, etc.
+//!     }
+//!     // The events are collected first, as the manager borrows the watchdog.
+//!     let events: Vec<_> = match watchdog.context() {
+//!         Some(ctx) => {
+//!             let mut system = ctx.system_mngr();
+//!             std::iter::from_fn(|| system.poll_next_event()).collect()
+//!         }
+//!         None => Vec::new(),
+//!     };
+//!     for event in &events {
+//!         watchdog.handle_event(event);
+//!     }
+//!     std::thread::sleep(Duration::from_millis(10));
+//! }
+//! ```
+
+use crate::system::VREvent;
+use crate::{sys, Context, InitError};
+
+use std::time::{Duration, Instant};
+
+/// Whether the watchdog currently holds a usable [`Context`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WatchdogState {
+    /// There is an active context.
+    Connected,
+    /// SteamVR is quitting, and the context will be shut down on the next tick.
+    Disconnecting,
+    /// There is no context, and SteamVR is being polled for.
+    WaitingForRuntime,
+}
+
+/// State changes reported by [`Watchdog::tick()`].
+#[derive(Debug)]
+pub enum WatchdogEvent {
+    /// A new context was created. Everything obtained from the previous context,
+    /// like overlay and action handles, must be recreated.
+    Connected,
+    /// The context was shut down because SteamVR went away.
+    Disconnected,
+    /// Initialization failed for a reason other than SteamVR not running. The
+    /// watchdog keeps retrying.
+    InitFailed(InitError),
+}
+
+/// Owns a [`Context`], shutting it down when SteamVR quits and creating a new one
+/// once SteamVR is back.
+///
+/// SteamVR quitting is noticed through `VREvent_Quit`. For application types that need
+/// a headset, the watchdog also checks every `poll_interval` that the runtime still
+/// reports one, which catches vrserver exiting without sending the event, like when it
+/// crashes. Other application types have to call [`Self::notify_runtime_lost()`] in
+/// that case.
+pub struct Watchdog {
+    application_type: sys::EVRApplicationType,
+    poll_interval: Duration,
+    ctx: Option<Context>,
+    state: WatchdogState,
+    /// The next init attempt while waiting, or the next liveness check while connected.
+    next_attempt: Instant,
+    /// Whether the shutdown being blocked by clones has been logged.
+    warned_clones: bool,
+}
+impl Watchdog {
+    /// Creates a watchdog that initializes as `application_type`, checking for
+    /// SteamVR every `poll_interval` while it is not running. No context is created
+    /// until the first [`Self::tick()`].
+    pub fn new(
+        application_type: impl Into<sys::EVRApplicationType>,
+        poll_interval: Duration,
+    ) -> Self {
+        Self {
+            application_type: application_type.into(),
+            poll_interval,
+            ctx: None,
+            state: WatchdogState::WaitingForRuntime,
+            next_attempt: Instant::now(),
+            warned_clones: false,
+        }
+    }
+
+    pub fn state(&self) -> WatchdogState {
+        self.state
+    }
+
+    /// The active context, if connected.
+    pub fn context(&self) -> Option<&Context> {
+        match self.state {
+            WatchdogState::Connected => self.ctx.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Feeds an event polled from [`crate::system::SystemManager::poll_next_event()`]
+    /// to the watchdog, so that it can notice SteamVR quitting.
+    pub fn handle_event(&mut self, event: &VREvent) {
+        if event.event_type == sys::EVREventType::VREvent_Quit {
+            self.notify_runtime_lost();
+        }
+    }
+
+    /// Tells the watchdog that SteamVR is gone, for example because calls started
+    /// failing with IPC errors without a `VREvent_Quit` being received first.
+    pub fn notify_runtime_lost(&mut self) {
+        if self.state == WatchdogState::Connected {
+            self.state = WatchdogState::Disconnecting;
+            self.warned_clones = false;
+        }
+    }
+
+    /// Drives the watchdog. Call this regularly from the thread that owns it, while no
+    /// managers are borrowed from [`Self::context()`].
+    ///
+    /// A disconnecting context can only be shut down once all of its clones are
+    /// dropped. Until then, the watchdog stays in [`WatchdogState::Disconnecting`].
+    pub fn tick(&mut self) -> Option<WatchdogEvent> {
+        match self.state {
+            WatchdogState::Connected => {
+                let now = Instant::now();
+                if !crate::needs_hmd(&self.application_type) || now < self.next_attempt {
+                    return None;
+                }
+                self.next_attempt = now + self.poll_interval;
+                // Once initialized, this asks the runtime, so it also fails when
+                // vrserver exited without a `VREvent_Quit`.
+                if !unsafe { sys::VR_IsHmdPresent() } {
+                    self.notify_runtime_lost();
+                }
+                None
+            }
+            WatchdogState::Disconnecting => {
+                let ctx = self.ctx.take()?;
+                if let Err(ctx) = ctx.shutdown() {
+                    if !self.warned_clones {
+                        log::warn!("can't shut down OpenVR while clones of the context exist");
+                        self.warned_clones = true;
+                    }
+                    self.ctx = Some(ctx);
+                    return None;
+                }
+                self.state = WatchdogState::WaitingForRuntime;
+                // Give the exiting vrserver time to go away before polling for it.
+                self.next_attempt = Instant::now() + self.poll_interval;
+                Some(WatchdogEvent::Disconnected)
+            }
+            WatchdogState::WaitingForRuntime => {
+                let now = Instant::now();
+                if now < self.next_attempt {
+                    return None;
+                }
+                self.next_attempt = now + self.poll_interval;
+                match Context::try_init_available(self.application_type.clone())? {
+                    Ok(ctx) => {
+                        self.ctx = Some(ctx);
+                        self.state = WatchdogState::Connected;
+                        Some(WatchdogEvent::Connected)
+                    }
+                    Err(err) => Some(WatchdogEvent::InitFailed(err)),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Submitting [`wgpu`] textures to OpenVR, by extracting the Vulkan handles behind
+//! them through `wgpu-hal`.
+//!
+//! Only the Vulkan backend is supported. wgpu no longer has a D3D11 backend, which is
+//! the only DirectX version OpenVR overlays accept.
+
+use crate::overlay::{OverlayHandle, OverlayManager};
+use crate::sys;
+use crate::vulkan::VulkanTexture;
+
+use ash::vk;
+use wgpu_hal::api::Vulkan;
+
+#[derive(Debug, thiserror::Error)]
+pub enum WgpuTextureError {
+    /// The device isn't using the Vulkan backend.
+    #[error("wgpu device is not using Vulkan")]
+    UnsupportedBackend,
+    /// OpenVR can't display textures of this format.
+    #[error("unsupported texture format {0:?}")]
+    UnsupportedFormat(wgpu::TextureFormat),
+    #[error("{0}")]
+    Overlay(#[source] crate::errors::EVROverlayError),
+    #[cfg(feature = "ovr_compositor")]
+    #[error("{0}")]
+    Compositor(#[source] crate::errors::EVRCompositorError),
+}
+
+/// The Vulkan format of the texture formats that OpenVR can display.
+fn vk_format(format: wgpu::TextureFormat) -> Option<vk::Format> {
+    use wgpu::TextureFormat::*;
+    Some(match format {
+        Rgba8Unorm => vk::Format::R8G8B8A8_UNORM,
+        Rgba8UnormSrgb => vk::Format::R8G8B8A8_SRGB,
+        Bgra8Unorm => vk::Format::B8G8R8A8_UNORM,
+        Bgra8UnormSrgb => vk::Format::B8G8R8A8_SRGB,
+        Rgb10a2Unorm => vk::Format::A2B10G10R10_UNORM_PACK32,
+        Rgba16Float => vk::Format::R16G16B16A16_SFLOAT,
+        Rgba32Float => vk::Format::R32G32B32A32_SFLOAT,
+        _ => return None,
+    })
+}
+
+/// Collects the Vulkan handles of `texture`, created on `device`.
+///
+/// # Safety
+/// `texture` must have been created on `device`, and must stay alive and unmodified
+/// until OpenVR is done reading it.
+///
+/// OpenVR reads Vulkan images in the `TRANSFER_SRC_OPTIMAL` layout, and nothing here
+/// transitions `texture` to it. wgpu has no way to request a layout, but leaves a
+/// texture in that one when the last command using it is a copy out of it, so end the
+/// submitted commands with one, e.g. a 1x1 copy into a scratch texture.
+pub unsafe fn vulkan_texture(
+    device: &wgpu::Device,
+    texture: &wgpu::Texture,
+) -> Result<VulkanTexture, WgpuTextureError> {
+    let format = texture.format();
+    let format = vk_format(format).ok_or(WgpuTextureError::UnsupportedFormat(format))?;
+    let image = texture
+        .as_hal::<Vulkan, _, _>(|texture| texture.map(|texture| texture.raw_handle()))
+        .ok_or(WgpuTextureError::UnsupportedBackend)?;
+    device
+        .as_hal::<Vulkan, _, _>(|device| {
+            device.map(|device| VulkanTexture {
+                image,
+                device: device.raw_device().handle(),
+                physical_device: device.raw_physical_device(),
+                instance: device.shared_instance().raw_instance().handle(),
+                queue: device.raw_queue(),
+                queue_family_index: device.queue_family_index(),
+                width: texture.width(),
+                height: texture.height(),
+                format,
+                sample_count: texture.sample_count(),
+            })
+        })
+        .ok_or(WgpuTextureError::UnsupportedBackend)
+}
+
+impl<'c> OverlayManager<'c> {
+    /// Sets the contents of `overlay` to a wgpu texture, which must already be in the
+    /// `TRANSFER_SRC_OPTIMAL` layout.
+    ///
+    /// # Safety
+    /// See [`vulkan_texture()`].
+    pub unsafe fn set_image_wgpu(
+        &mut self,
+        overlay: OverlayHandle,
+        device: &wgpu::Device,
+        texture: &wgpu::Texture,
+    ) -> Result<(), WgpuTextureError> {
+        let texture = vulkan_texture(device, texture)?;
+        self.set_image_vk(overlay, &texture)
+            .map_err(WgpuTextureError::Overlay)
+    }
+}
+
+#[cfg(feature = "ovr_compositor")]
+impl<'c> crate::compositor::CompositorManager<'c> {
+    /// Submits a wgpu texture as the frame for `eye`, optionally cropped to `bounds`.
+    /// The texture must already be in the `TRANSFER_SRC_OPTIMAL` layout.
+    ///
+    /// # Safety
+    /// See [`vulkan_texture()`].
+    pub unsafe fn submit_wgpu(
+        &mut self,
+        eye: sys::EVREye,
+        device: &wgpu::Device,
+        texture: &wgpu::Texture,
+        bounds: Option<&crate::TextureBounds>,
+    ) -> Result<(), WgpuTextureError> {
+        let texture = vulkan_texture(device, texture)?;
+        self.submit_vk(eye, &texture, bounds)
+            .map_err(WgpuTextureError::Compositor)
+    }
+}
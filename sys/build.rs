@@ -1,32 +1,363 @@
+use std::fmt::Write;
 use std::path::PathBuf;
 
 use normpath::PathExt;
 
+/// Items that are always generated: initialization, types shared between
+/// interfaces, and IVROverlay.
+const CORE: &[&str] = &[
+    // TrackedDeviceIndex_t constants
+    r#"generate_pod!("vr::TrackedDeviceIndex_t")"#,
+    r#"generate!("vr::k_unTrackedDeviceIndex_Hmd")"#,
+    r#"generate!("vr::k_unMaxTrackedDeviceCount")"#,
+    r#"generate!("vr::k_unTrackedDeviceIndexOther")"#,
+    r#"generate!("vr::k_unTrackedDeviceIndexInvalid")"#,
+    // init
+    r#"generate!("vr::VR_Init")"#,
+    r#"generate_pod!("vr::EVRApplicationType")"#,
+    r#"generate!("vr::VR_Shutdown")"#,
+    r#"generate!("vr::VR_IsHmdPresent")"#,
+    r#"generate!("vr::VR_IsRuntimeInstalled")"#,
+    r#"generate!("vr::VR_IsInterfaceVersionValid")"#,
+    r#"generate!("vr::VR_GetVRInitErrorAsSymbol")"#,
+    r#"generate!("vr::VR_GetVRInitErrorAsEnglishDescription")"#,
+    r#"generate_pod!("vr::EVRInitError")"#,
+    // shared types
+    r#"generate_pod!("vr::ETrackingUniverseOrigin")"#,
+    r#"generate!("vr::HmdMatrix34_t")"#,
+    r#"generate_pod!("vr::HmdVector3_t")"#,
+    r#"generate_pod!("vr::HmdVector2_t")"#,
+    r#"generate_pod!("vr::HmdQuaternion_t")"#,
+    r#"generate_pod!("vr::HmdQuad_t")"#,
+    r#"generate_pod!("vr::HmdColor_t")"#,
+    r#"generate_pod!("vr::VRTextureBounds_t")"#,
+    r#"generate_pod!("vr::TrackedDevicePose_t")"#,
+    r#"generate_pod!("vr::EVREye")"#,
+    r#"generate_pod!("vr::PropertyContainerHandle_t")"#,
+    r#"generate!("vr::k_unMaxPropertyStringSize")"#,
+    r#"generate_pod!("vr::VRVulkanTextureData_t")"#,
+    r#"generate_pod!("vr::Texture_t")"#,
+    // VREvents
+    r#"generate!("vr::VREvent_t")"#,
+    r#"generate!("vr::VREvent_Data_t")"#,
+    r#"generate_pod!("vr::VREvent_Property_t")"#,
+    // overlay
+    r#"generate!("vr::IVROverlay_Version")"#,
+    r#"generate!("vr::IVROverlay")"#,
+    r#"generate!("vr::VROverlay")"#,
+    r#"generate_pod!("vr::EVROverlayError")"#,
+    r#"generate_pod!("vr::VROverlayHandle_t")"#,
+    r#"generate_pod!("vr::VROverlayIntersectionParams_t")"#,
+    r#"generate_pod!("vr::VROverlayIntersectionResults_t")"#,
+];
+
+/// Items only generated when the interface's feature is enabled, keyed by feature.
+const INTERFACES: &[(&str, &[&str])] = &[
+    (
+        "ovr_applications",
+        &[
+            r#"generate!("vr::IVRApplications_Version")"#,
+            r#"generate!("vr::IVRApplications")"#,
+            r#"generate!("vr::VRApplications")"#,
+            r#"generate_pod!("vr::EVRApplicationError")"#,
+            r#"generate!("vr::k_unMaxApplicationKeyLength")"#,
+            r#"generate_pod!("vr::EVRApplicationProperty")"#,
+            r#"generate_pod!("vr::AppOverrideKeys_t")"#,
+        ],
+    ),
+    (
+        "ovr_chaperone_setup",
+        &[
+            r#"generate!("vr::IVRChaperoneSetup_Version")"#,
+            r#"generate!("vr::IVRChaperoneSetup")"#,
+            r#"generate!("vr::VRChaperoneSetup")"#,
+        ],
+    ),
+    (
+        "ovr_compositor",
+        &[
+            r#"generate!("vr::IVRCompositor_Version")"#,
+            r#"generate!("vr::IVRCompositor")"#,
+            r#"generate!("vr::VRCompositor")"#,
+            r#"generate_pod!("vr::EVRCompositorError")"#,
+            r#"generate_pod!("vr::Compositor_FrameTiming")"#,
+        ],
+    ),
+    (
+        "ovr_debug",
+        &[
+            r#"generate!("vr::IVRDebug_Version")"#,
+            r#"generate!("vr::IVRDebug")"#,
+            r#"generate!("vr::VRDebug")"#,
+            r#"generate_pod!("vr::EVRDebugError")"#,
+            r#"generate_pod!("vr::VrProfilerEventHandle_t")"#,
+            r#"generate!("vr::k_unMaxDriverDebugResponseSize")"#,
+        ],
+    ),
+    (
+        "ovr_driver_manager",
+        &[
+            r#"generate!("vr::IVRDriverManager_Version")"#,
+            r#"generate!("vr::IVRDriverManager")"#,
+            r#"generate!("vr::VRDriverManager")"#,
+            r#"generate_pod!("vr::DriverId_t")"#,
+            r#"generate_pod!("vr::DriverHandle_t")"#,
+        ],
+    ),
+    (
+        "ovr_extended_display",
+        &[
+            r#"generate!("vr::IVRExtendedDisplay_Version")"#,
+            r#"generate!("vr::IVRExtendedDisplay")"#,
+            r#"generate!("vr::VRExtendedDisplay")"#,
+        ],
+    ),
+    (
+        "ovr_headset_view",
+        &[
+            r#"generate!("vr::IVRHeadsetView_Version")"#,
+            r#"generate!("vr::IVRHeadsetView")"#,
+            r#"generate!("vr::VRHeadsetView")"#,
+            r#"generate_pod!("vr::HeadsetViewMode_t")"#,
+        ],
+    ),
+    (
+        "ovr_input",
+        &[
+            r#"generate!("vr::IVRInput_Version")"#,
+            r#"generate!("vr::IVRInput")"#,
+            r#"generate!("vr::VRInput")"#,
+            r#"generate_pod!("vr::EVRInputError")"#,
+            r#"generate_pod!("vr::VRActionSetHandle_t")"#,
+            r#"generate_pod!("vr::VRActionHandle_t")"#,
+            r#"generate_pod!("vr::VRInputValueHandle_t")"#,
+            r#"generate_pod!("vr::VRActiveActionSet_t")"#,
+            r#"generate_pod!("vr::InputDigitalActionData_t")"#,
+            r#"generate_pod!("vr::InputAnalogActionData_t")"#,
+            r#"generate_pod!("vr::InputPoseActionData_t")"#,
+            r#"generate_pod!("vr::InputOriginInfo_t")"#,
+            r#"generate_pod!("vr::InputBindingInfo_t")"#,
+            r#"generate_pod!("vr::VRSkeletalSummaryData_t")"#,
+            r#"generate_pod!("vr::EVRSummaryType")"#,
+            r#"generate!("vr::k_ulInvalidInputValueHandle")"#,
+        ],
+    ),
+    (
+        "ovr_iobuffer",
+        &[
+            r#"generate!("vr::IVRIOBuffer_Version")"#,
+            r#"generate!("vr::IVRIOBuffer")"#,
+            r#"generate!("vr::VRIOBuffer")"#,
+            r#"generate_pod!("vr::EIOBufferError")"#,
+            r#"generate_pod!("vr::EIOBufferMode")"#,
+            r#"generate_pod!("vr::IOBufferHandle_t")"#,
+        ],
+    ),
+    (
+        "ovr_notifications",
+        &[
+            r#"generate!("vr::IVRNotifications_Version")"#,
+            r#"generate!("vr::IVRNotifications")"#,
+            r#"generate!("vr::VRNotifications")"#,
+            r#"generate_pod!("vr::EVRNotificationError")"#,
+            r#"generate_pod!("vr::EVRNotificationType")"#,
+            r#"generate_pod!("vr::EVRNotificationStyle")"#,
+            r#"generate_pod!("vr::VRNotificationId")"#,
+            r#"generate_pod!("vr::NotificationBitmap_t")"#,
+        ],
+    ),
+    (
+        "ovr_overlay_view",
+        &[
+            r#"generate!("vr::IVROverlayView_Version")"#,
+            r#"generate!("vr::IVROverlayView")"#,
+            r#"generate!("vr::VROverlayView")"#,
+            r#"generate_pod!("vr::VROverlayView_t")"#,
+            r#"generate_pod!("vr::VRNativeDevice_t")"#,
+            r#"generate_pod!("vr::EDeviceType")"#,
+            r#"generate_pod!("vr::VRVulkanDevice_t")"#,
+        ],
+    ),
+    (
+        "ovr_render_models",
+        &[
+            r#"generate!("vr::IVRRenderModels_Version")"#,
+            r#"generate!("vr::IVRRenderModels")"#,
+            r#"generate!("vr::VRRenderModels")"#,
+            r#"generate_pod!("vr::EVRRenderModelError")"#,
+            r#"generate_pod!("vr::RenderModel_t")"#,
+            r#"generate_pod!("vr::RenderModel_Vertex_t")"#,
+            r#"generate_pod!("vr::TextureID_t")"#,
+            r#"generate_pod!("vr::RenderModel_ComponentState_t")"#,
+            r#"generate_pod!("vr::RenderModel_ControllerMode_State_t")"#,
+        ],
+    ),
+    (
+        "ovr_resources",
+        &[
+            r#"generate!("vr::IVRResources_Version")"#,
+            r#"generate!("vr::IVRResources")"#,
+            r#"generate!("vr::VRResources")"#,
+        ],
+    ),
+    (
+        "ovr_screenshots",
+        &[
+            r#"generate!("vr::IVRScreenshots_Version")"#,
+            r#"generate!("vr::IVRScreenshots")"#,
+            r#"generate!("vr::VRScreenshots")"#,
+            r#"generate_pod!("vr::EVRScreenshotError")"#,
+            r#"generate_pod!("vr::EVRScreenshotType")"#,
+            r#"generate_pod!("vr::EVRScreenshotPropertyFilenames")"#,
+            r#"generate_pod!("vr::ScreenshotHandle_t")"#,
+        ],
+    ),
+    (
+        "ovr_settings",
+        &[
+            r#"generate!("vr::IVRSettings_Version")"#,
+            r#"generate!("vr::IVRSettings")"#,
+            r#"generate!("vr::VRSettings")"#,
+            r#"generate!("vr::k_pch_SteamVR_Section")"#,
+            r#"generate!("vr::k_pch_CollisionBounds_Section")"#,
+        ],
+    ),
+    (
+        "ovr_system",
+        &[
+            r#"generate!("vr::IVRSystem_Version")"#,
+            r#"generate!("vr::IVRSystem")"#,
+            r#"generate!("vr::VRSystem")"#,
+        ],
+    ),
+    (
+        "ovr_tracked_camera",
+        &[
+            r#"generate!("vr::IVRTrackedCamera_Version")"#,
+            r#"generate!("vr::IVRTrackedCamera")"#,
+            r#"generate!("vr::VRTrackedCamera")"#,
+            r#"generate_pod!("vr::EVRTrackedCameraError")"#,
+            r#"generate_pod!("vr::EVRTrackedCameraFrameType")"#,
+            r#"generate_pod!("vr::TrackedCameraHandle_t")"#,
+            r#"generate_pod!("vr::CameraVideoStreamFrameHeader_t")"#,
+        ],
+    ),
+];
+
+/// Writes the `include_cpp!` invocation for the enabled interfaces, so autocxx only
+/// has to generate bindings for those.
+fn write_ffi(out_dir: &std::path::Path) -> PathBuf {
+    let mut ffi = String::from("include_cpp! {\n    #include \"openvr.h\"\n");
+    let enabled = INTERFACES.iter().filter(|(feature, _)| {
+        let var = format!("CARGO_FEATURE_{}", feature.to_uppercase());
+        std::env::var_os(var).is_some()
+    });
+    let items = CORE
+        .iter()
+        .chain(enabled.flat_map(|(_, items)| items.iter()));
+    for item in items {
+        writeln!(ffi, "    {item}").unwrap();
+    }
+    ffi.push_str("}\n");
+
+    let path = out_dir.join("ffi.rs");
+    std::fs::write(&path, ffi).expect("Could not write ffi.rs");
+    path
+}
+
 fn main() {
-    // include path openvr/headers
-    let include_path = relative("openvr/headers");
-    // This assumes all your C++ bindings are in main.rs
-    let mut b = autocxx_build::Builder::new(relative("src/lib.rs"), [&include_path])
-        .build()
-        .expect("Could not autogenerate bindings");
+    let dlopen = std::env::var_os("CARGO_FEATURE_DLOPEN").is_some();
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+
+    // include path openvr/headers, unless pointed at another OpenVR build
+    println!("cargo:rerun-if-env-changed={INCLUDE_DIR_VAR}");
+    let include_path = std::env::var_os(INCLUDE_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| relative("openvr/headers"));
+
+    // docs.rs builds without network access or an OpenVR runtime, and documentation
+    // doesn't need the C++ side of the bindings anyway. It is detected by its DOCS_RS
+    // variable, or by `--cfg docsrs` for building the same documentation locally.
+    println!("cargo:rerun-if-env-changed=DOCS_RS");
+    if std::env::var_os("DOCS_RS").is_some() || std::env::var_os("CARGO_CFG_DOCSRS").is_some() {
+        docs_stub(&out_dir, &include_path);
+        return;
+    }
+
+    let mut b = generate(&out_dir, &include_path, dlopen);
+    if dlopen {
+        b.define("OPENVR_BUILD_STATIC", None)
+            .file(relative("src/dlopen_shim.cpp"));
+        println!("cargo:rerun-if-changed=src/dlopen_shim.cpp");
+    }
     // arbitrary library name, pick anything
     b.flag_if_supported("-std=c++14").compile("foobar");
-    println!("cargo:rerun-if-changed=src/lib.rs");
-
-    // Link the C++ libraries
-    #[cfg(target_os = "windows")]
-    let input_files = [
-        relative("openvr/bin/win64/openvr_api.dll"),
-        relative("openvr/lib/win64/openvr_api.lib"),
-    ];
-    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
-    let input_files = [relative("openvr/bin/linux64/libopenvr_api.so")];
-    #[cfg(all(target_os = "linux", target_arch = "x86"))]
-    let input_files = [relative("openvr/bin/linux32/libopenvr_api.so")];
-    #[cfg(target_os = "macos")]
-    let input_files: [PathBuf; 1] = [panic!("Mac is unsupported")];
+    println!("cargo:rerun-if-changed=build.rs");
 
-    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    if dlopen {
+        // openvr_api is loaded at runtime by `dlopen::load()`.
+        return;
+    }
+
+    link_openvr(&out_dir);
+}
+
+/// The vendored openvr_api files for the target, if OpenVR ships a build for it.
+fn vendored_libs(os: &str, arch: &str, env: &str) -> Option<Vec<PathBuf>> {
+    let files = match (os, arch) {
+        ("windows", "x86_64" | "x86") => {
+            let dir = if arch == "x86_64" { "win64" } else { "win32" };
+            let dll = relative(&format!("openvr/bin/{dir}/openvr_api.dll"));
+            if env == "gnu" {
+                // MinGW's ld links against the DLL directly, and doesn't reliably
+                // understand MSVC import libraries.
+                vec![dll]
+            } else {
+                vec![dll, relative(&format!("openvr/lib/{dir}/openvr_api.lib"))]
+            }
+        }
+        ("linux", "x86_64") => vec![relative("openvr/bin/linux64/libopenvr_api.so")],
+        ("linux", "x86") => vec![relative("openvr/bin/linux32/libopenvr_api.so")],
+        ("linux", "aarch64") => vec![relative("openvr/bin/linuxarm64/libopenvr_api.so")],
+        _ => return None,
+    };
+    files.iter().all(|f| f.exists()).then_some(files)
+}
+
+/// Overrides the directory containing openvr.h.
+const INCLUDE_DIR_VAR: &str = "OPENVR_INCLUDE_DIR";
+/// Overrides the directory containing openvr_api, taking precedence over both the
+/// vendored library and pkg-config.
+const LIB_DIR_VAR: &str = "OPENVR_LIB_DIR";
+
+/// Links against openvr_api from `OPENVR_LIB_DIR` or the vendored files, falling back
+/// to a system install found through pkg-config.
+fn link_openvr(out_dir: &std::path::Path) {
+    println!("cargo:rerun-if-env-changed={LIB_DIR_VAR}");
+    if let Some(lib_dir) = std::env::var_os(LIB_DIR_VAR) {
+        println!("cargo:rustc-link-lib=dylib=openvr_api");
+        println!(
+            "cargo:rustc-link-search=native={}",
+            PathBuf::from(lib_dir).display()
+        );
+        return;
+    }
+
+    // The build script runs on the host, so `cfg!(target_os)` would be wrong when
+    // cross compiling.
+    let os = std::env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+    let env = std::env::var("CARGO_CFG_TARGET_ENV").unwrap();
+
+    let Some(input_files) = vendored_libs(&os, &arch, &env) else {
+        if let Err(err) = pkg_config::Config::new().probe("openvr") {
+            panic!(
+                "There is no vendored OpenVR library for {arch}-{os}, and no system one \
+                 was found: {err}. Set {LIB_DIR_VAR} to the directory containing it."
+            );
+        }
+        return;
+    };
     for f in input_files {
         let file_name = f.file_name().unwrap();
         std::fs::copy(&f, out_dir.join(file_name)).unwrap_or_else(|err| {
@@ -38,6 +369,30 @@ fn main() {
     println!("cargo:rustc-link-search=native={:?}", out_dir);
 }
 
+/// Runs autocxx on the enabled interfaces, returning the build for the C++ side of
+/// the bindings.
+fn generate(out_dir: &std::path::Path, include_path: &std::path::Path, dlopen: bool) -> cc::Build {
+    // With dlopen, the OpenVR entry points are defined by our shim rather than imported
+    // from openvr_api, so the header must not declare them as dllimport.
+    let clang_args: &[&str] = if dlopen {
+        &["-DOPENVR_BUILD_STATIC"]
+    } else {
+        &[]
+    };
+    let ffi = write_ffi(out_dir);
+    autocxx_build::Builder::new(ffi, [include_path])
+        .extra_clang_args(clang_args)
+        .build()
+        .expect("Could not autogenerate bindings")
+}
+
+/// Generates only the Rust side of the bindings. Neither the C++ side nor the dlopen
+/// shim is compiled, and nothing is linked, so only the Rust API can be used (e.g. by
+/// rustdoc), not a final binary.
+fn docs_stub(out_dir: &std::path::Path, include_path: &std::path::Path) {
+    let _ = generate(out_dir, include_path, false);
+}
+
 fn relative(s: &str) -> PathBuf {
     let result = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     result.join(s).normalize().unwrap().into_path_buf()
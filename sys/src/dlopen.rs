@@ -0,0 +1,123 @@
+//! Loads `openvr_api` at runtime instead of linking against it.
+//!
+//! [`load()`] must succeed before OpenVR is initialized. Until then, initializing
+//! fails with `VRInitError_Init_InstallationNotFound`, so applications can run
+//! with VR features disabled when no runtime is available.
+
+use std::ffi::{c_void, OsStr};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Must match `FnTable` in dlopen_shim.cpp.
+#[repr(C)]
+struct FnTable {
+    init_internal2: *const c_void,
+    shutdown_internal: *const c_void,
+    get_generic_interface: *const c_void,
+    get_init_token: *const c_void,
+    is_interface_version_valid: *const c_void,
+    is_hmd_present: *const c_void,
+    is_runtime_installed: *const c_void,
+    get_vr_init_error_as_symbol: *const c_void,
+    get_vr_init_error_as_english_description: *const c_void,
+}
+
+extern "C" {
+    fn ovr_overlay_sys_set_fn_table(table: *const FnTable);
+}
+
+// Kept alive for the rest of the process, since interfaces handed out by the
+// library may still be referenced.
+static LIBRARY: Mutex<Option<libloading::Library>> = Mutex::new(None);
+
+#[derive(Debug)]
+pub enum LoadError {
+    /// No path was given, and no SteamVR installation could be found.
+    RuntimeNotFound,
+    Library(libloading::Error),
+}
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RuntimeNotFound => write!(f, "could not find a SteamVR installation"),
+            Self::Library(e) => write!(f, "could not load openvr_api: {e}"),
+        }
+    }
+}
+impl std::error::Error for LoadError {}
+
+#[cfg(target_os = "windows")]
+const LIBRARY_NAME: &str = "openvr_api.dll";
+#[cfg(not(target_os = "windows"))]
+const LIBRARY_NAME: &str = "libopenvr_api.so";
+
+#[cfg(all(target_os = "windows", target_pointer_width = "64"))]
+const RUNTIME_BIN_DIR: &str = "bin/win64";
+#[cfg(all(target_os = "windows", target_pointer_width = "32"))]
+const RUNTIME_BIN_DIR: &str = "bin/win32";
+#[cfg(all(not(target_os = "windows"), target_arch = "x86_64"))]
+const RUNTIME_BIN_DIR: &str = "bin/linux64";
+#[cfg(all(not(target_os = "windows"), target_arch = "x86"))]
+const RUNTIME_BIN_DIR: &str = "bin/linux32";
+#[cfg(all(not(target_os = "windows"), target_arch = "aarch64"))]
+const RUNTIME_BIN_DIR: &str = "bin/linuxarm64";
+
+/// Returns true if a library has been loaded.
+pub fn is_loaded() -> bool {
+    LIBRARY.lock().unwrap_or_else(|e| e.into_inner()).is_some()
+}
+
+/// Loads `openvr_api` from `path`, or if `None`, from the SteamVR installation
+/// registered in `openvrpaths.vrpath`. Loading again replaces the previous library,
+/// which must not be done while OpenVR is initialized.
+pub fn load(path: Option<&Path>) -> Result<(), LoadError> {
+    let path = match path {
+        Some(path) => path.to_owned(),
+        None => find_runtime_library().ok_or(LoadError::RuntimeNotFound)?,
+    };
+    load_from(path.as_os_str())
+}
+
+fn load_from(path: &OsStr) -> Result<(), LoadError> {
+    let library = unsafe { libloading::Library::new(path) }.map_err(LoadError::Library)?;
+    let sym = |name: &[u8]| -> Result<*const c_void, LoadError> {
+        let sym = unsafe { library.get::<*const c_void>(name) }.map_err(LoadError::Library)?;
+        Ok(*sym)
+    };
+    let table = FnTable {
+        init_internal2: sym(b"VR_InitInternal2\0")?,
+        shutdown_internal: sym(b"VR_ShutdownInternal\0")?,
+        get_generic_interface: sym(b"VR_GetGenericInterface\0")?,
+        get_init_token: sym(b"VR_GetInitToken\0")?,
+        is_interface_version_valid: sym(b"VR_IsInterfaceVersionValid\0")?,
+        is_hmd_present: sym(b"VR_IsHmdPresent\0")?,
+        is_runtime_installed: sym(b"VR_IsRuntimeInstalled\0")?,
+        get_vr_init_error_as_symbol: sym(b"VR_GetVRInitErrorAsSymbol\0")?,
+        get_vr_init_error_as_english_description: sym(b"VR_GetVRInitErrorAsEnglishDescription\0")?,
+    };
+    let mut guard = LIBRARY.lock().unwrap_or_else(|e| e.into_inner());
+    unsafe { ovr_overlay_sys_set_fn_table(&table) };
+    *guard = Some(library);
+    Ok(())
+}
+
+/// Finds `openvr_api` in the runtime listed in `openvrpaths.vrpath`.
+fn find_runtime_library() -> Option<PathBuf> {
+    let config_dir = if cfg!(target_os = "windows") {
+        PathBuf::from(std::env::var_os("LOCALAPPDATA")?)
+    } else if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME") {
+        PathBuf::from(dir)
+    } else {
+        PathBuf::from(std::env::var_os("HOME")?).join(".config")
+    };
+    let vrpath = std::fs::read_to_string(config_dir.join("openvr/openvrpaths.vrpath")).ok()?;
+    let vrpath: serde_json::Value = serde_json::from_str(&vrpath).ok()?;
+    vrpath
+        .get("runtime")?
+        .as_array()?
+        .iter()
+        .filter_map(|runtime| runtime.as_str())
+        .map(|runtime| Path::new(runtime).join(RUNTIME_BIN_DIR).join(LIBRARY_NAME))
+        .find(|library| library.exists())
+}
@@ -0,0 +1,108 @@
+// Defines the entry points that openvr.h expects openvr_api to export, forwarding
+// them to the library loaded at runtime by `dlopen.rs`. Until a library is loaded,
+// they behave as if no runtime were installed.
+
+#include "openvr.h"
+
+namespace
+{
+	// Must match `FnTable` in dlopen.rs.
+	struct FnTable
+	{
+		void *InitInternal2;
+		void *ShutdownInternal;
+		void *GetGenericInterface;
+		void *GetInitToken;
+		void *IsInterfaceVersionValid;
+		void *IsHmdPresent;
+		void *IsRuntimeInstalled;
+		void *GetVRInitErrorAsSymbol;
+		void *GetVRInitErrorAsEnglishDescription;
+	};
+
+	FnTable g_table = {};
+
+	template <typename F>
+	F get( void *fn )
+	{
+		return reinterpret_cast<F>( fn );
+	}
+}
+
+extern "C" void ovr_overlay_sys_set_fn_table( const FnTable *table )
+{
+	g_table = table ? *table : FnTable{};
+}
+
+namespace vr
+{
+	uint32_t VR_CALLTYPE VR_InitInternal2( EVRInitError *peError, EVRApplicationType eApplicationType, const char *pStartupInfo )
+	{
+		if ( !g_table.InitInternal2 )
+		{
+			if ( peError )
+				*peError = VRInitError_Init_InstallationNotFound;
+			return 0;
+		}
+		return get<uint32_t( VR_CALLTYPE * )( EVRInitError *, EVRApplicationType, const char * )>( g_table.InitInternal2 )( peError, eApplicationType, pStartupInfo );
+	}
+
+	void VR_CALLTYPE VR_ShutdownInternal()
+	{
+		if ( g_table.ShutdownInternal )
+			get<void( VR_CALLTYPE * )()>( g_table.ShutdownInternal )();
+	}
+
+	void *VR_CALLTYPE VR_GetGenericInterface( const char *pchInterfaceVersion, EVRInitError *peError )
+	{
+		if ( !g_table.GetGenericInterface )
+		{
+			if ( peError )
+				*peError = VRInitError_Init_NotInitialized;
+			return nullptr;
+		}
+		return get<void *( VR_CALLTYPE * )( const char *, EVRInitError * )>( g_table.GetGenericInterface )( pchInterfaceVersion, peError );
+	}
+
+	uint32_t VR_CALLTYPE VR_GetInitToken()
+	{
+		if ( !g_table.GetInitToken )
+			return 0;
+		return get<uint32_t( VR_CALLTYPE * )()>( g_table.GetInitToken )();
+	}
+
+	bool VR_CALLTYPE VR_IsInterfaceVersionValid( const char *pchInterfaceVersion )
+	{
+		if ( !g_table.IsInterfaceVersionValid )
+			return false;
+		return get<bool( VR_CALLTYPE * )( const char * )>( g_table.IsInterfaceVersionValid )( pchInterfaceVersion );
+	}
+
+	bool VR_CALLTYPE VR_IsHmdPresent()
+	{
+		if ( !g_table.IsHmdPresent )
+			return false;
+		return get<bool( VR_CALLTYPE * )()>( g_table.IsHmdPresent )();
+	}
+
+	bool VR_CALLTYPE VR_IsRuntimeInstalled()
+	{
+		if ( !g_table.IsRuntimeInstalled )
+			return false;
+		return get<bool( VR_CALLTYPE * )()>( g_table.IsRuntimeInstalled )();
+	}
+
+	const char *VR_CALLTYPE VR_GetVRInitErrorAsSymbol( EVRInitError error )
+	{
+		if ( !g_table.GetVRInitErrorAsSymbol )
+			return "VRInitError_Init_InstallationNotFound";
+		return get<const char *( VR_CALLTYPE * )( EVRInitError )>( g_table.GetVRInitErrorAsSymbol )( error );
+	}
+
+	const char *VR_CALLTYPE VR_GetVRInitErrorAsEnglishDescription( EVRInitError error )
+	{
+		if ( !g_table.GetVRInitErrorAsEnglishDescription )
+			return "openvr_api has not been loaded";
+		return get<const char *( VR_CALLTYPE * )( EVRInitError )>( g_table.GetVRInitErrorAsEnglishDescription )( error );
+	}
+}